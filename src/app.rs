@@ -1,5 +1,7 @@
-use crate::domain::{compute_totals, flatten_tasks, GlobalMode, GlobalState, Item, RunStatus, ScheduleDay, StateEvent, UiMode};
-use crate::notifications;
+use crate::config::Config;
+use crate::domain::{active_blockers, compute_totals, find_item_mut, find_path, flatten_tasks, item_at, item_at_mut, remove_at, siblings_mut, unblock, waits_on, walk_items, walk_items_mut, GlobalMode, GlobalState, Item, ItemPath, RunStatus, ScheduleDay, Session, StateEvent, UiMode};
+use crate::notifications::{notifier_from_config, Notification, Notifier};
+use crate::persistence::{ModeTimeline, UndoEntry};
 use anyhow::Result;
 use chrono::{Duration, Timelike};
 use std::time::Instant;
@@ -23,30 +25,146 @@ pub struct InputFormState {
     pub editing_item_id: Option<uuid::Uuid>, // If Some, we're editing an existing item
 }
 
-/// Undo action for reverting recent changes
-#[derive(Debug, Clone)]
-pub enum UndoAction {
-    MarkedDone {
-        item: Item,
-        was_subtask: bool,
-        parent_task_index: Option<usize>,
-        subtask_index: Option<usize>,
-        task_index: usize,
-    },
-    Deleted {
-        item: Item,
-        was_subtask: bool,
-        parent_task_index: Option<usize>,
-        subtask_index: Option<usize>,
-        task_index: usize,
-    },
-    Archived {
-        item: Item,
-        was_subtask: bool,
-        parent_task_index: Option<usize>,
-        subtask_index: Option<usize>,
-        task_index: usize,
-    },
+/// A data file that changed on disk since this session last read or wrote it
+/// (holds the content now on disk, None if the file was deleted)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalChange {
+    Tasks(Option<String>),
+    Journal(Option<String>),
+}
+
+/// Time the computer was suspended (or the wall clock jumped) while the app
+/// was running, noticed by comparing wall-clock and monotonic time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AwayGap {
+    pub from: chrono::DateTime<chrono::Local>,
+    pub to: chrono::DateTime<chrono::Local>,
+}
+
+impl AwayGap {
+    pub fn duration(&self) -> Duration {
+        self.to.signed_duration_since(self.from)
+    }
+}
+
+/// What to do with time spent away
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AwayChoice {
+    /// Count it as if running tasks and the current mode had continued
+    Count,
+    /// Drop it: running tasks are paused for the gap and no mode is recorded
+    Discard,
+    /// Record it as time in a mode (running tasks only keep it if the mode
+    /// doesn't pause timers)
+    Mode(GlobalMode),
+}
+
+/// Tasks left running by a session that ended without saving (terminal
+/// closed, killed, power loss), found at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashGap {
+    /// Last heartbeat written by that session
+    pub heartbeat: chrono::DateTime<chrono::Local>,
+    /// "HH:MM" being typed when choosing the end time
+    pub time_input: Option<String>,
+}
+
+/// When to end the RUNNING intervals left open by a crash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashGapChoice {
+    /// At the last heartbeat (roughly when the app stopped)
+    Heartbeat,
+    /// At a time chosen by the user
+    At(chrono::DateTime<chrono::Local>),
+    /// Now, counting the whole gap as work
+    Keep,
+}
+
+/// Running time taken off tasks when the idle check timed out: they were
+/// paused as of the last keypress instead of when the check gave up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdleTrim {
+    /// Last keypress
+    pub last_input: chrono::DateTime<chrono::Local>,
+    /// When the idle check timed out
+    pub to: chrono::DateTime<chrono::Local>,
+    /// Items that were running, each with the start of its trimmed time (the
+    /// last keypress, or when it started running if that was later)
+    pub items: Vec<(Uuid, chrono::DateTime<chrono::Local>)>,
+}
+
+impl IdleTrim {
+    /// Time taken off an item that started its trimmed time at `from`
+    pub fn trimmed_since(&self, from: chrono::DateTime<chrono::Local>) -> Duration {
+        self.to.signed_duration_since(from)
+    }
+}
+
+/// What is being typed in the history editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryInput {
+    /// A session the timer missed ("HH:MM-HH:MM")
+    Add,
+    /// New times for the selected session ("HH:MM-HH:MM")
+    Edit,
+    /// A break inside the selected session ("HH:MM-HH:MM", or "HH:MM" to split it in two)
+    Split,
+}
+
+/// History editor for the running sessions of one item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEditor {
+    pub item_id: Uuid,
+    /// Index of the selected session
+    pub selected: usize,
+    /// Kind of input and the text typed so far
+    pub input: Option<(HistoryInput, String)>,
+    /// Why the last edit was rejected
+    pub error: Option<String>,
+}
+
+/// Form for putting the selected item on hold (UiMode::SettingWaiting)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaitingForm {
+    pub item_id: Uuid,
+    /// What it's waiting on
+    pub reason: String,
+    /// Follow-up date as typed (YYYY-MM-DD, +N days or tomorrow; empty for none)
+    pub follow_up: String,
+    pub editing_field: usize, // 0 = reason, 1 = follow-up
+    /// Why the follow-up date was rejected
+    pub error: Option<String>,
+}
+
+/// Picker for the tasks the selected item waits on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockerPicker {
+    pub item_id: Uuid,
+    /// Index of the selected candidate
+    pub selected: usize,
+}
+
+/// How far the left/right keys shift a session in the history editor
+const HISTORY_SHIFT_MINUTES: i64 = 5;
+
+/// How many changes can be undone
+const UNDO_LIMIT: usize = 100;
+
+/// How often the heartbeat is written to meta.json
+const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long to wait before trying a failed day rollover again
+const ROLLOVER_RETRY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How to resolve an external change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalChangeChoice {
+    /// Discard in-memory changes and load the file from disk
+    Reload,
+    /// Combine both versions (by task ID for the daily file, by line for the journal)
+    Merge,
+    /// Keep the in-memory version and overwrite the file
+    Overwrite,
 }
 
 /// Main application state
@@ -59,7 +177,17 @@ pub struct AppState {
     pub modal: Option<ModalState>,
     pub input_form: Option<InputFormState>,
     pub last_tick: Instant,
+    pub last_tick_wall: chrono::DateTime<chrono::Local>, // Wall-clock time of the last tick
+    pub away_gap: Option<AwayGap>, // Suspend or clock jump waiting for the user to decide
+    pub crash_gap: Option<CrashGap>, // Intervals left open by a crashed session
+    pub history_editor: Option<HistoryEditor>, // Session list being edited (UiMode::EditingHistory)
+    pub blocker_picker: Option<BlockerPicker>, // Choosing what the selected item waits on (UiMode::PickingBlockers)
+    pub waiting_form: Option<WaitingForm>, // Putting the selected item on hold (UiMode::SettingWaiting)
+    pub heartbeat: Option<chrono::DateTime<chrono::Local>>, // Persisted in meta.json while running
+    pub last_heartbeat_write: Option<Instant>,
     pub last_idle_check: Instant,
+    pub last_input: chrono::DateTime<chrono::Local>, // Wall-clock time of the last keypress
+    pub idle_trim: Option<IdleTrim>, // Time trimmed by an idle check timeout, until the user has seen it
     pub idle_check_deadline: Option<Instant>,
     pub use_emoji: bool,
     pub estimate_step: Duration,
@@ -69,7 +197,9 @@ pub struct AppState {
     pub journal_needs_save: bool,
     pub journal_cursor_pos: usize, // Cursor position in journal
     pub file_date: chrono::NaiveDate, // Track which day's file we're using
-    pub undo_stack: Vec<UndoAction>, // Track recent actions for undo
+    pub undo_stack: Vec<UndoEntry>, // State before each recent change, for undo (persisted in undo.json)
+    pub redo_stack: Vec<UndoEntry>, // State before each undo, for redo
+    pub undo_log_dirty: bool, // Stacks changed since undo.json was last written
 
     // Global activity tracking for Focus Garden
     pub app_start_time: Instant,
@@ -85,16 +215,10 @@ pub struct AppState {
 
     // Global mode tracking
     pub global_mode: GlobalMode,
-    pub last_mode_change: Instant,
-    pub last_mode_change_timestamp: Option<chrono::DateTime<chrono::Local>>, // Wall clock time of last mode change
-    pub mode_time_working: Duration,
-    pub mode_time_break: Duration,
-    pub mode_time_lunch: Duration,
-    pub mode_time_gym: Duration,
-    pub mode_time_dinner: Duration,
-    pub mode_time_personal: Duration,
-    pub mode_time_sleep: Duration,
+    pub mode_timeline: ModeTimeline, // Today's mode segments (modes-YYYY-MM-DD.json)
     pub paused_by_mode_task_ids: Vec<Uuid>, // Tasks that were paused by mode change
+    pub mode_since: Option<String>, // "since HH:MM" typed in the mode selector
+    pub editing_mode_since: bool,
 
     // Animation frame counter for ASCII animations (increments every tick)
     pub animation_frame: u32,
@@ -107,50 +231,102 @@ pub struct AppState {
 
     // Toggle visibility of daily planner pane
     pub show_planner: bool,
+
+    // Content of the daily/journal files as last read or written by this
+    // session (None if the file didn't exist), used to detect external edits
+    pub daily_base: Option<String>,
+    pub journal_base: Option<String>,
+    // The day's event log as last replayed, so saves only log what changed
+    pub day_recorder: crate::persistence::DayRecorder,
+    pub external_change: Option<ExternalChange>,
+
+    // Shown in place of the keybinding hints until the next key press
+    pub status_message: Option<String>,
+
+    // Day rollover in progress: the old day already closed out (so a retry
+    // doesn't close it again), and when a failed attempt may be retried
+    pub closed_day: Option<chrono::NaiveDate>,
+    pub rollover_retry_at: Option<Instant>,
+
+    // The last live-reload failure shown, so a file that keeps failing
+    // isn't reported on every pass
+    pub reload_error: Option<String>,
+
+    // Modification times seen by the last live-reload check
+    pub daily_mtime: Option<std::time::SystemTime>,
+    pub journal_mtime: Option<std::time::SystemTime>,
+
+    // User configuration (config.toml)
+    pub config: Config,
+
+    // Delivers notifications using the configured backend
+    pub notifier: Box<dyn Notifier>,
+}
+
+/// Merge two journal versions: ours, followed by any lines only found on disk
+fn merge_journal(ours: &str, theirs: &str) -> String {
+    let our_lines: std::collections::HashSet<&str> = ours.lines().collect();
+    let extra: Vec<&str> = theirs.lines().filter(|line| !our_lines.contains(line)).collect();
+
+    if extra.is_empty() {
+        return ours.to_string();
+    }
+
+    let mut merged = ours.trim_end_matches('\n').to_string();
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    merged.push_str(&extra.join("\n"));
+    merged.push('\n');
+    merged
 }
 
 impl AppState {
-    pub fn new(tasks: Vec<Item>, done_today: Vec<Item>, archived_today: Vec<Item>, journal_content: String) -> Self {
+    pub fn new(
+        tasks: Vec<Item>,
+        done_today: Vec<Item>,
+        archived_today: Vec<Item>,
+        journal_content: String,
+        config: Config,
+    ) -> Self {
         let now = Instant::now();
 
         // Load metadata (global mode, etc.)
-        let mut metadata = Self::load_metadata_internal().unwrap_or_default();
-
-        // Check if it's a new day and reset mode times if needed
-        let current_date = chrono::Local::now().date_naive();
-        let should_reset_mode_times = if let Some(last_timestamp) = &metadata.last_mode_change_timestamp {
-            // Parse the last timestamp and check if it's from a different day
-            if let Ok(last_time) = chrono::DateTime::parse_from_rfc3339(last_timestamp) {
-                let last_date = last_time.date_naive();
-                last_date != current_date
-            } else {
-                false
-            }
+        let metadata = Self::load_metadata_internal().unwrap_or_default();
+
+        // Fall back to the default mode if the saved one is no longer configured
+        let global_mode = if config.modes.get(&metadata.global_mode).is_some() {
+            metadata.global_mode.clone()
         } else {
-            // No timestamp means first run or old format - don't reset
-            false
+            config.modes.default_mode()
         };
 
-        // Reset mode times if it's a new day
-        if should_reset_mode_times {
-            metadata.mode_time_working_secs = 0;
-            metadata.mode_time_break_secs = 0;
-            metadata.mode_time_lunch_secs = 0;
-            metadata.mode_time_gym_secs = 0;
-            metadata.mode_time_dinner_secs = 0;
-            metadata.mode_time_personal_secs = 0;
-            metadata.mode_time_sleep_secs = 0;
-            // Clear paused task IDs since UUIDs are regenerated for new day
-            metadata.paused_by_mode_task_ids.clear();
-            // Update timestamp to current time
-            metadata.last_mode_change_timestamp = Some(chrono::Local::now().to_rfc3339());
-
-            // Save the reset metadata immediately
-            use crate::persistence::{save_metadata, meta_file};
-            if let Ok(meta_path) = meta_file() {
-                let _ = save_metadata(meta_path, &metadata);
+        // Load today's mode timeline and open a segment for the current mode
+        let file_date = crate::day::today(config.day_start());
+        let mut mode_timeline = Self::load_mode_timeline_internal(file_date).unwrap_or_default();
+        mode_timeline.begin(global_mode.clone(), chrono::Local::now());
+
+        // Remember what the data files looked like when we loaded them
+        let daily_base = crate::persistence::daily_file(file_date)
+            .and_then(crate::persistence::read_if_exists)
+            .unwrap_or(None);
+        let journal_base = crate::persistence::journal_file_for_date(file_date)
+            .and_then(crate::persistence::read_if_exists)
+            .unwrap_or(None);
+
+        // Changes from an earlier session today can still be undone
+        let undo_log = crate::persistence::undo_file()
+            .and_then(crate::persistence::load_undo_log)
+            .unwrap_or_default();
+        let (undo_stack, redo_stack) = if undo_log.date == Some(file_date) {
+            let (mut undo, mut redo) = (undo_log.undo, undo_log.redo);
+            for entry in undo.iter_mut().chain(redo.iter_mut()) {
+                entry.close_running(file_date);
             }
-        }
+            (undo, redo)
+        } else {
+            (Vec::new(), Vec::new())
+        };
 
         // Convert paused task IDs from strings to UUIDs
         let paused_by_mode_task_ids: Vec<Uuid> = metadata
@@ -159,20 +335,6 @@ impl AppState {
             .filter_map(|s| Uuid::parse_str(s).ok())
             .collect();
 
-        // Load mode times from metadata (stored as seconds)
-        let mode_time_working = Duration::seconds(metadata.mode_time_working_secs);
-        let mode_time_break = Duration::seconds(metadata.mode_time_break_secs);
-        let mode_time_lunch = Duration::seconds(metadata.mode_time_lunch_secs);
-        let mode_time_gym = Duration::seconds(metadata.mode_time_gym_secs);
-        let mode_time_dinner = Duration::seconds(metadata.mode_time_dinner_secs);
-        let mode_time_personal = Duration::seconds(metadata.mode_time_personal_secs);
-        let mode_time_sleep = Duration::seconds(metadata.mode_time_sleep_secs);
-
-        // Parse the last mode change timestamp
-        let last_mode_change_timestamp = metadata.last_mode_change_timestamp
-            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(&ts).ok())
-            .map(|dt| dt.with_timezone(&chrono::Local));
-
         Self {
             tasks,
             done_today,
@@ -182,17 +344,29 @@ impl AppState {
             modal: None,
             input_form: None,
             last_tick: now,
+            last_tick_wall: chrono::Local::now(),
+            away_gap: None,
+            crash_gap: None,
+            history_editor: None,
+            blocker_picker: None,
+            waiting_form: None,
+            heartbeat: None,
+            last_heartbeat_write: None,
             last_idle_check: now,
+            last_input: chrono::Local::now(),
+            idle_trim: None,
             idle_check_deadline: None,
             use_emoji: true,
-            estimate_step: Duration::minutes(15),
+            estimate_step: config.estimate_step(),
             needs_save: false,
             show_done: true,
             journal_content,
             journal_needs_save: false,
             journal_cursor_pos: 0,
-            file_date: chrono::Local::now().date_naive(),
-            undo_stack: Vec::new(),
+            file_date,
+            undo_stack,
+            redo_stack,
+            undo_log_dirty: false,
 
             // Initialize global activity tracking
             app_start_time: now,
@@ -207,17 +381,11 @@ impl AppState {
             current_phrase_index: 0,
 
             // Initialize global mode tracking from loaded metadata
-            global_mode: metadata.global_mode,
-            last_mode_change: now,
-            last_mode_change_timestamp,
-            mode_time_working,
-            mode_time_break,
-            mode_time_lunch,
-            mode_time_gym,
-            mode_time_dinner,
-            mode_time_personal,
-            mode_time_sleep,
+            global_mode,
+            mode_timeline,
             paused_by_mode_task_ids,
+            mode_since: None,
+            editing_mode_since: false,
 
             // Initialize animation frame counter
             animation_frame: 0,
@@ -228,6 +396,20 @@ impl AppState {
 
             // Initialize planner visibility (hidden by default)
             show_planner: false,
+
+            daily_base,
+            journal_base,
+            day_recorder: Default::default(),
+            external_change: None,
+            status_message: None,
+            closed_day: None,
+            rollover_retry_at: None,
+            reload_error: None,
+            daily_mtime: None,
+            journal_mtime: None,
+
+            notifier: notifier_from_config(&config.notifications),
+            config,
         }
     }
 
@@ -238,40 +420,209 @@ impl AppState {
         load_metadata(meta_path)
     }
 
+    /// Load the mode timeline for a given day
+    fn load_mode_timeline_internal(date: chrono::NaiveDate) -> Result<ModeTimeline> {
+        use crate::persistence::{load_mode_timeline, mode_file_for_date};
+        load_mode_timeline(mode_file_for_date(date)?)
+    }
+
+    /// Save the mode timeline for the day being tracked
+    pub fn save_mode_timeline(&self) -> Result<()> {
+        use crate::persistence::{mode_file_for_date, save_mode_timeline};
+        save_mode_timeline(mode_file_for_date(self.file_date)?, &self.mode_timeline)
+    }
+
     /// Save metadata to meta.json
     pub fn save_metadata(&self) -> Result<()> {
-        use crate::persistence::{save_metadata, meta_file, AppMetadata};
+        use crate::persistence::{load_metadata, save_metadata, meta_file};
 
-        // Get current mode times (includes time in current mode up to now)
-        let mode_times = self.get_mode_times();
+        // Keep fields owned by other parts of the app (e.g. last_carry_over)
+        let meta_path = meta_file()?;
+        let mut metadata = load_metadata(&meta_path).unwrap_or_default();
+        metadata.global_mode = self.global_mode.clone();
+        metadata.paused_by_mode_task_ids = self
+            .paused_by_mode_task_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        metadata.heartbeat = self.heartbeat;
 
-        let metadata = AppMetadata {
-            global_mode: self.global_mode,
-            paused_by_mode_task_ids: self
-                .paused_by_mode_task_ids
-                .iter()
-                .map(|id| id.to_string())
-                .collect(),
-            // Save mode times as seconds
-            mode_time_working_secs: mode_times[0].1.num_seconds(),
-            mode_time_break_secs: mode_times[1].1.num_seconds(),
-            mode_time_lunch_secs: mode_times[2].1.num_seconds(),
-            mode_time_gym_secs: mode_times[3].1.num_seconds(),
-            mode_time_dinner_secs: mode_times[4].1.num_seconds(),
-            mode_time_personal_secs: mode_times[5].1.num_seconds(),
-            mode_time_sleep_secs: mode_times[6].1.num_seconds(),
-            // Use the timestamp from AppState (only updated when mode changes)
-            last_mode_change_timestamp: self.last_mode_change_timestamp.map(|dt| dt.to_rfc3339()),
+        save_metadata(meta_path, &metadata)
+    }
+
+    /// Look for RUNNING intervals left open by a session that didn't exit
+    /// cleanly, and ask what to do with them (call once at startup, before
+    /// the first heartbeat)
+    pub fn detect_crash_gap(&mut self) {
+        let Ok(metadata) = Self::load_metadata_internal() else {
+            return;
+        };
+        self.check_crash_gap(metadata.heartbeat, chrono::Local::now());
+    }
+
+    /// Ask about the open intervals behind a heartbeat found at startup,
+    /// unless it is recent enough to belong to a session that is still running
+    fn check_crash_gap(&mut self, heartbeat: Option<chrono::DateTime<chrono::Local>>, now: chrono::DateTime<chrono::Local>) {
+        if let Some(heartbeat) = heartbeat {
+            let live = now - heartbeat < Duration::from_std(HEARTBEAT_INTERVAL * 2).unwrap_or(Duration::zero());
+            if !live && self.tasks.iter().any(|task| task.has_open_interval(heartbeat)) {
+                self.crash_gap = Some(CrashGap {
+                    heartbeat,
+                    time_input: None,
+                });
+                self.ui_mode = UiMode::CrashRecovery;
+            }
+        }
+    }
+
+    /// Close the intervals left open by a crashed session
+    pub fn resolve_crash_gap(&mut self, choice: CrashGapChoice) {
+        let before = self.undo_entry("Crash recovery");
+        let Some(gap) = self.crash_gap.take() else {
+            return;
+        };
+        let end = match choice {
+            CrashGapChoice::Heartbeat => gap.heartbeat,
+            CrashGapChoice::At(at) => at,
+            CrashGapChoice::Keep => chrono::Local::now(),
         };
 
-        let meta_path = meta_file()?;
-        save_metadata(meta_path, &metadata)
+        for task in &mut self.tasks {
+            task.close_open_interval(gap.heartbeat, end);
+            task.sync_elapsed_from_history();
+        }
+        self.ui_mode = UiMode::Normal;
+        self.needs_save = true;
+        self.push_undo(before);
     }
 
-    /// Check if the current date has changed (crossed midnight)
+    /// Parse the "HH:MM" typed in the crash prompt as a time on the day of
+    /// the last heartbeat (no later than now)
+    pub fn crash_gap_input_time(&self) -> Option<chrono::DateTime<chrono::Local>> {
+        let gap = self.crash_gap.as_ref()?;
+        let time = chrono::NaiveTime::parse_from_str(gap.time_input.as_deref()?, "%H:%M").ok()?;
+        let day_start = self.config.day_start();
+        let at = crate::day::time_on(crate::day::day_of(gap.heartbeat, day_start), time, day_start)?;
+        Some(std::cmp::min(at, chrono::Local::now()))
+    }
+
+    /// Record that this session is still alive (at most every few seconds),
+    /// so a crash can be told apart from a clean exit on the next start
+    pub fn write_heartbeat(&mut self) -> Result<()> {
+        if self.last_heartbeat_write.is_some_and(|at| at.elapsed() < HEARTBEAT_INTERVAL) {
+            return Ok(());
+        }
+        use crate::persistence::{lock_centre_dir, meta_file, save_heartbeat};
+
+        self.heartbeat = Some(chrono::Local::now());
+        self.last_heartbeat_write = Some(Instant::now());
+        // Only the heartbeat: the rest of meta.json may hold a mode switch
+        // made by another session since this one last saved
+        let _lock = lock_centre_dir()?;
+        save_heartbeat(meta_file()?, self.heartbeat)
+    }
+
+    /// Check if a new day has started (see `[day] starts_at`)
     pub fn has_day_changed(&self) -> bool {
-        let current_date = chrono::Local::now().date_naive();
-        current_date != self.file_date
+        crate::day::today(self.config.day_start()) != self.file_date
+    }
+
+    /// Roll over to the new day without restarting
+    ///
+    /// The old day's file is closed out with running intervals split at the
+    /// day boundary (midnight unless configured otherwise), unfinished tasks are carried into the new day (generating
+    /// reports for the days left behind, like `load_and_migrate`) and a fresh
+    /// mode timeline is started. Running tasks keep running.
+    ///
+    /// If setting up the new day fails, the old day stays closed out and a
+    /// retry picks up from there.
+    pub fn rollover_day(&mut self) -> Result<()> {
+        use crate::persistence::{lock_centre_dir, roll_over_to_today};
+
+        let day_start = self.config.day_start();
+        let today = crate::day::today(day_start);
+
+        // Save the old day as it is in memory, then close its running intervals at the boundary
+        if self.closed_day != Some(self.file_date) {
+            let boundary = crate::day::start_of(self.file_date + Duration::days(1), day_start);
+            self.save_tasks()?;
+            self.save_journal_merging()?;
+            {
+                let _lock = lock_centre_dir()?;
+                let mut closed = self.tasks.clone();
+                for item in &mut closed {
+                    item.close_running_at(boundary);
+                    item.sync_elapsed_from_history();
+                }
+                let items = (closed, self.done_today.clone(), self.archived_today.clone());
+                self.day_recorder.record(self.file_date, &items, Some(&self.global_mode))?;
+            }
+            self.mode_timeline.extend_to(boundary);
+            self.save_mode_timeline()?;
+            self.closed_day = Some(self.file_date);
+        }
+
+        // Carry unfinished tasks over; tasks we already have in memory keep
+        // their unsplit history and running timers
+        let ((carried, done, archived), warnings) = roll_over_to_today(&self.config)?;
+        let previous = std::mem::take(&mut self.tasks);
+        let active = carried
+            .into_iter()
+            .map(|item| previous.iter().find(|task| task.id == item.id).cloned().unwrap_or(item))
+            .collect();
+
+        self.file_date = today;
+        self.closed_day = None;
+        self.replace_items(active, done, archived);
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.undo_log_dirty = true;
+        self.daily_base = self.read_daily_from_disk()?;
+        self.daily_mtime = None;
+
+        // Switch to the new day's journal
+        self.journal_base = self.read_journal_from_disk()?;
+        self.journal_content = self.journal_base.clone().unwrap_or_default();
+        self.journal_cursor_pos = 0;
+        self.journal_needs_save = false;
+        self.journal_mtime = None;
+
+        // Reset today's mode and activity counters
+        self.mode_timeline = Self::load_mode_timeline_internal(today).unwrap_or_default();
+        self.mode_timeline.begin(self.global_mode.clone(), crate::day::start_of(today, day_start));
+        self.running_time = Duration::zero();
+        self.paused_time = Duration::zero();
+        self.idle_time = Duration::zero();
+        self.completed_sessions.clear();
+        self.current_session_start = None;
+        self.app_start_time = Instant::now();
+        self.last_state_change = Instant::now();
+        self.resurface_waiting();
+        if !warnings.is_empty() {
+            self.status_message = Some(warnings.join("; "));
+        }
+
+        self.save()
+    }
+
+    /// Roll over to the new day, unless an attempt failed less than a
+    /// minute ago. A failure is shown in the status bar. Returns whether the
+    /// rollover happened.
+    pub fn try_rollover_day(&mut self) -> bool {
+        if self.rollover_retry_at.is_some_and(|at| Instant::now() < at) {
+            return false;
+        }
+        match self.rollover_day() {
+            Ok(()) => {
+                self.rollover_retry_at = None;
+                true
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Couldn't roll over to the new day (retrying in a minute): {:#}", e));
+                self.rollover_retry_at = Some(Instant::now() + ROLLOVER_RETRY);
+                false
+            }
+        }
     }
 
     /// Toggle showing done tasks
@@ -322,25 +673,351 @@ impl AppState {
         self.planner_scroll_offset = target_slot.max(0) as usize;
     }
 
-    /// Get the currently selected item (returns task_index and optional subtask_index)
-    pub fn get_selected_item(&self) -> Option<(usize, Option<usize>)> {
+    /// Get the path of the currently selected item (see `ItemPath`)
+    pub fn selected_path(&self) -> Option<ItemPath> {
         let flat_rows = flatten_tasks(&self.tasks);
         if self.selected_index >= flat_rows.len() {
             return None;
         }
 
-        let row = &flat_rows[self.selected_index];
-        Some((row.task_index, row.subtask_index))
+        Some(flat_rows[self.selected_index].path.clone())
+    }
+
+    /// Get the currently selected item
+    pub fn selected_item(&self) -> Option<&Item> {
+        item_at(&self.tasks, &self.selected_path()?)
+    }
+
+    /// Select the item with the given ID at any depth (expanding its parents if needed)
+    /// Returns false if no active item has that ID
+    pub fn select_by_id(&mut self, id: Uuid) -> bool {
+        let Some(path) = find_path(&self.tasks, id) else {
+            return false;
+        };
+        for depth in 1..path.len() {
+            if let Some(parent) = item_at_mut(&mut self.tasks, &path[..depth]) {
+                parent.expanded = true;
+            }
+        }
+
+        match flatten_tasks(&self.tasks).iter().position(|row| row.path == path) {
+            Some(index) => {
+                self.selected_index = index;
+                true
+            }
+            None => false,
+        }
     }
 
     /// Get a mutable reference to the selected item
     pub fn get_selected_item_mut(&mut self) -> Option<&mut Item> {
-        let (task_idx, subtask_idx) = self.get_selected_item()?;
+        let path = self.selected_path()?;
+        item_at_mut(&mut self.tasks, &path)
+    }
 
-        if let Some(st_idx) = subtask_idx {
-            self.tasks.get_mut(task_idx)?.subtasks.get_mut(st_idx)
-        } else {
-            self.tasks.get_mut(task_idx)
+    /// Open the history editor for the selected item
+    pub fn open_history_editor(&mut self) {
+        let Some(item) = self.get_selected_item_mut() else {
+            return;
+        };
+        self.history_editor = Some(HistoryEditor {
+            item_id: item.id,
+            selected: item.sessions().len().saturating_sub(1),
+            input: None,
+            error: None,
+        });
+        self.ui_mode = UiMode::EditingHistory;
+    }
+
+    /// Put the selected item on hold (opens the waiting form), or take it off
+    /// hold if it is already waiting
+    pub fn toggle_waiting(&mut self) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        let item_id = item.id;
+
+        if item.status == RunStatus::Waiting {
+            let before = self.undo_entry("Stop waiting");
+            if let Some(item) = find_item_mut(&mut self.tasks, item_id) {
+                item.stop_waiting();
+                self.needs_save = true;
+            }
+            self.select_by_id(item_id);
+            self.push_undo(before);
+            return;
+        }
+
+        self.waiting_form = Some(WaitingForm {
+            item_id,
+            reason: String::new(),
+            follow_up: String::new(),
+            editing_field: 0,
+            error: None,
+        });
+        self.ui_mode = UiMode::SettingWaiting;
+    }
+
+    /// Put the form's item on hold with the typed reason and follow-up date
+    pub fn submit_waiting_form(&mut self) {
+        let Some(form) = self.waiting_form.clone() else {
+            return;
+        };
+        let follow_up = match crate::day::parse_follow_up(&form.follow_up, self.file_date) {
+            Ok(follow_up) => follow_up,
+            Err(e) => {
+                if let Some(form) = self.waiting_form.as_mut() {
+                    form.error = Some(e.to_string());
+                    form.editing_field = 1;
+                }
+                return;
+            }
+        };
+
+        self.close_waiting_form();
+        self.set_waiting(form.item_id, form.reason.trim().to_string(), follow_up);
+    }
+
+    /// Put an item on hold, waiting on `reason` until `follow_up`
+    pub fn set_waiting(&mut self, item_id: Uuid, reason: String, follow_up: Option<chrono::NaiveDate>) {
+        let before = self.undo_entry("Set waiting");
+        if let Some(path) = find_path(&self.tasks, item_id) {
+            if let Some(item) = item_at_mut(&mut self.tasks, &path) {
+                item.set_waiting(reason, follow_up);
+            }
+            // It may have been running
+            self.sync_parent_status(&path);
+            self.needs_save = true;
+        }
+        self.select_by_id(item_id);
+        self.push_undo(before);
+    }
+
+    /// Close the waiting form without changing anything
+    pub fn close_waiting_form(&mut self) {
+        self.waiting_form = None;
+        self.ui_mode = UiMode::Normal;
+    }
+
+    /// Take waiting items whose follow-up date has come off hold, with a
+    /// notification for each
+    pub fn resurface_waiting(&mut self) {
+        let today = self.file_date;
+        let mut due = Vec::new();
+        walk_items_mut(&mut self.tasks, &mut |item| {
+            if item.follow_up_due(today) {
+                due.push(match item.waiting_reason.as_str() {
+                    "" => item.title.trim().to_string(),
+                    reason => format!("{} ({})", item.title.trim(), reason),
+                });
+                item.stop_waiting();
+            }
+        });
+
+        for title in due {
+            self.notifier.notify(&Notification::FollowUp(title));
+            self.needs_save = true;
+        }
+    }
+
+    /// Open the picker for the tasks the selected item waits on
+    pub fn open_blocker_picker(&mut self) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        self.blocker_picker = Some(BlockerPicker {
+            item_id: item.id,
+            selected: 0,
+        });
+        self.ui_mode = UiMode::PickingBlockers;
+    }
+
+    /// Close the blocker picker
+    pub fn close_blocker_picker(&mut self) {
+        self.blocker_picker = None;
+        self.ui_mode = UiMode::Normal;
+    }
+
+    /// The item the blocker picker is for, and the tasks it could wait on
+    /// (everything active except itself, its subtasks and its parents)
+    pub fn blocker_candidates(&self) -> Option<(&Item, Vec<&Item>)> {
+        let picker = self.blocker_picker.as_ref()?;
+        let path = find_path(&self.tasks, picker.item_id)?;
+        let item = item_at(&self.tasks, &path)?;
+        let excluded: Vec<Uuid> = (1..path.len())
+            .filter_map(|depth| item_at(&self.tasks, &path[..depth]).map(|parent| parent.id))
+            .chain(item.walk().iter().map(|inner| inner.id))
+            .collect();
+        let candidates = walk_items(&self.tasks).filter(|other| !excluded.contains(&other.id)).collect();
+        Some((item, candidates))
+    }
+
+    /// Select the previous/next candidate in the blocker picker
+    pub fn move_blocker_selection(&mut self, forward: bool) {
+        let count = self.blocker_candidates().map(|(_, candidates)| candidates.len()).unwrap_or(0);
+        if let Some(picker) = self.blocker_picker.as_mut() {
+            picker.selected = if forward {
+                std::cmp::min(picker.selected + 1, count.saturating_sub(1))
+            } else {
+                picker.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Add or remove the selected candidate as a blocker of the picker's item
+    /// (a blocker already waiting on the item is refused, as that would be a cycle)
+    pub fn toggle_selected_blocker(&mut self) {
+        let Some((item_id, blocker_id)) = self.blocker_picker.as_ref().and_then(|picker| {
+            let (_, candidates) = self.blocker_candidates()?;
+            Some((picker.item_id, candidates.get(picker.selected)?.id))
+        }) else {
+            return;
+        };
+
+        let before = self.undo_entry("Set blockers");
+        let cycle = waits_on(&self.tasks, blocker_id, item_id);
+        if let Some(item) = find_item_mut(&mut self.tasks, item_id) {
+            if let Some(pos) = item.blocked_by.iter().position(|id| *id == blocker_id) {
+                item.blocked_by.remove(pos);
+                self.needs_save = true;
+            } else if !cycle {
+                item.blocked_by.push(blocker_id);
+                self.needs_save = true;
+            }
+        }
+        self.push_undo(before);
+    }
+
+    /// Close the history editor
+    pub fn close_history_editor(&mut self) {
+        self.history_editor = None;
+        self.ui_mode = UiMode::Normal;
+    }
+
+    /// The item being edited in the history editor and its sessions
+    pub fn history_item(&self) -> Option<(&Item, Vec<Session>)> {
+        let editor = self.history_editor.as_ref()?;
+        let item = walk_items(&self.tasks).find(|item| item.id == editor.item_id)?;
+        Some((item, item.sessions()))
+    }
+
+    /// Select the previous/next session in the history editor
+    pub fn move_history_selection(&mut self, forward: bool) {
+        let count = self.history_item().map(|(_, sessions)| sessions.len()).unwrap_or(0);
+        if let Some(editor) = self.history_editor.as_mut() {
+            editor.selected = if forward {
+                std::cmp::min(editor.selected + 1, count.saturating_sub(1))
+            } else {
+                editor.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Start typing times in the history editor (edits start from the
+    /// selected session's times)
+    pub fn start_history_input(&mut self, kind: HistoryInput) {
+        let selected = self.history_editor.as_ref().map(|editor| editor.selected);
+        let session = self
+            .history_item()
+            .and_then(|(_, sessions)| sessions.get(selected?).copied());
+        if kind != HistoryInput::Add && session.is_none() {
+            return;
+        }
+
+        let text = match (kind, session) {
+            (HistoryInput::Edit, Some(session)) => format!(
+                "{}-{}",
+                session.start.format("%H:%M"),
+                session.end.unwrap_or_else(chrono::Local::now).format("%H:%M")
+            ),
+            _ => String::new(),
+        };
+        if let Some(editor) = self.history_editor.as_mut() {
+            editor.input = Some((kind, text));
+            editor.error = None;
+        }
+    }
+
+    /// Apply the times typed in the history editor
+    pub fn submit_history_input(&mut self) {
+        let Some((kind, text)) = self.history_editor.as_mut().and_then(|editor| editor.input.take()) else {
+            return;
+        };
+        let Some((start, end)) = self.parse_history_times(&text) else {
+            if let Some(editor) = self.history_editor.as_mut() {
+                editor.error = Some(format!("Couldn't read \"{}\" (use HH:MM-HH:MM)", text));
+            }
+            return;
+        };
+
+        self.edit_history(|item, selected| match kind {
+            HistoryInput::Add => {
+                item.add_session(start, end)?;
+                Ok(item.sessions().iter().position(|session| session.start == start))
+            }
+            HistoryInput::Edit => item.move_session(selected, start, end).map(|_| None),
+            HistoryInput::Split => item.split_session(selected, start, end).map(|_| Some(selected + 1)),
+        });
+    }
+
+    /// Shift the selected session earlier or later (only the start of a
+    /// session that is still running)
+    pub fn shift_history_session(&mut self, later: bool) {
+        let step = Duration::minutes(if later { HISTORY_SHIFT_MINUTES } else { -HISTORY_SHIFT_MINUTES });
+        self.edit_history(|item, selected| {
+            let Some(session) = item.sessions().get(selected).copied() else {
+                return Ok(None);
+            };
+            let end = session.end.unwrap_or_else(chrono::Local::now);
+            item.move_session(selected, session.start + step, end + step)?;
+            Ok(None)
+        });
+    }
+
+    /// Delete the selected session in the history editor
+    pub fn delete_history_session(&mut self) {
+        self.edit_history(|item, selected| item.delete_session(selected).map(|_| None));
+    }
+
+    /// Run an edit on the item in the history editor with the selected
+    /// session index; the edit may return a session to select afterwards
+    fn edit_history(&mut self, edit: impl FnOnce(&mut Item, usize) -> Result<Option<usize>>) {
+        let Some(editor) = self.history_editor.clone() else {
+            return;
+        };
+        let before = self.undo_entry("Edit history");
+        let Some(item) = self.find_item_by_id_mut(editor.item_id) else {
+            return;
+        };
+
+        let result = edit(item, editor.selected);
+        let count = item.sessions().len();
+        let Some(editor) = self.history_editor.as_mut() else {
+            return;
+        };
+        match result {
+            Ok(select) => {
+                editor.selected = std::cmp::min(select.unwrap_or(editor.selected), count.saturating_sub(1));
+                editor.error = None;
+                self.needs_save = true;
+                self.push_undo(before);
+            }
+            Err(e) => editor.error = Some(e.to_string()),
+        }
+    }
+
+    /// Parse "HH:MM-HH:MM" (or a single "HH:MM") as times on the day being tracked
+    fn parse_history_times(&self, text: &str) -> Option<(chrono::DateTime<chrono::Local>, chrono::DateTime<chrono::Local>)> {
+        let parse = |time: &str| {
+            let time = chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()?;
+            crate::day::time_on(self.file_date, time, self.config.day_start())
+        };
+        match text.split_once('-') {
+            Some((start, end)) => Some((parse(start)?, parse(end)?)),
+            None => {
+                let at = parse(text)?;
+                Some((at, at))
+            }
         }
     }
 
@@ -359,186 +1036,265 @@ impl AppState {
         }
     }
 
-    /// Move selected task/subtask up in the list
+    /// Move selected task/subtask up in the list (among its siblings)
     pub fn move_item_up(&mut self) {
-        if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-            if let Some(st_idx) = subtask_idx {
-                // Moving a subtask up
-                if st_idx > 0 {
-                    self.tasks[task_idx].subtasks.swap(st_idx, st_idx - 1);
-                    self.selected_index = self.selected_index.saturating_sub(1);
-                    self.needs_save = true;
-                }
-            } else {
-                // Moving a task up
-                if task_idx > 0 {
-                    self.tasks.swap(task_idx, task_idx - 1);
-                    // Adjust selection to follow the moved task
-                    self.selected_index = self.selected_index.saturating_sub(
-                        if !self.tasks[task_idx].subtasks.is_empty() && self.tasks[task_idx].expanded {
-                            self.tasks[task_idx].subtasks.len() + 1
-                        } else {
-                            1
-                        }
-                    );
-                    self.needs_save = true;
-                }
+        let before = self.undo_entry("Move");
+        self.move_among_siblings(false);
+        self.push_undo(before);
+    }
+
+    /// Move selected task/subtask down in the list (among its siblings)
+    pub fn move_item_down(&mut self) {
+        let before = self.undo_entry("Move");
+        self.move_among_siblings(true);
+        self.push_undo(before);
+    }
+
+    /// Swap the selected item with the sibling above or below it, keeping it
+    /// selected. A subtask already at the top (bottom) of its parent's list
+    /// moves to the end (start) of the previous (next) parent's subtasks.
+    fn move_among_siblings(&mut self, down: bool) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        let idx = path[path.len() - 1];
+        let Some(siblings) = siblings_mut(&mut self.tasks, &path) else {
+            return;
+        };
+        let Some(target) = Self::adjacent_sibling(siblings, idx, path.len() == 1, down) else {
+            if path.len() > 1 {
+                self.move_to_adjacent_parent(&path, down);
             }
+            return;
+        };
+
+        siblings.swap(idx, target);
+        let id = siblings[target].id;
+        self.select_by_id(id);
+        self.needs_save = true;
+    }
+
+    /// Index of the sibling listed right below (or above) `siblings[idx]`.
+    /// Waiting top-level tasks are listed after the others (see
+    /// `flatten_tasks`), so at the top level only tasks in the same group count.
+    fn adjacent_sibling(siblings: &[Item], idx: usize, top_level: bool, down: bool) -> Option<usize> {
+        let waiting = |i: usize| top_level && siblings[i].status == RunStatus::Waiting;
+        let same_group = |i: &usize| waiting(*i) == waiting(idx);
+        if down {
+            (idx + 1..siblings.len()).find(same_group)
+        } else {
+            (0..idx).rev().find(same_group)
         }
     }
 
-    /// Move selected task/subtask down in the list
-    pub fn move_item_down(&mut self) {
-        if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-            if let Some(st_idx) = subtask_idx {
-                // Moving a subtask down
-                let subtask_count = self.tasks[task_idx].subtasks.len();
-                if st_idx + 1 < subtask_count {
-                    self.tasks[task_idx].subtasks.swap(st_idx, st_idx + 1);
-                    self.selected_index += 1;
-                    self.needs_save = true;
-                }
-            } else {
-                // Moving a task down
-                if task_idx + 1 < self.tasks.len() {
-                    self.tasks.swap(task_idx, task_idx + 1);
-                    // Adjust selection to follow the moved task
-                    self.selected_index += if !self.tasks[task_idx + 1].subtasks.is_empty() && self.tasks[task_idx + 1].expanded {
-                        self.tasks[task_idx + 1].subtasks.len() + 1
-                    } else {
-                        1
-                    };
-                    self.needs_save = true;
+    /// Move the subtask at `path` to the parent's next (or previous) sibling
+    fn move_to_adjacent_parent(&mut self, path: &[usize], down: bool) {
+        let mut parent_path = path[..path.len() - 1].to_vec();
+        let parent_idx = parent_path[parent_path.len() - 1];
+        let Some(parent_siblings) = siblings_mut(&mut self.tasks, &parent_path) else {
+            return;
+        };
+        let Some(target_idx) = Self::adjacent_sibling(parent_siblings, parent_idx, parent_path.len() == 1, down) else {
+            return;
+        };
+        *parent_path.last_mut().unwrap() = target_idx;
+        let Some(new_parent) = item_at(&self.tasks, &parent_path).map(|item| item.id) else {
+            return;
+        };
+        self.reparent_selected(Some(new_parent), if down { 0 } else { usize::MAX });
+    }
+
+    /// Move the selected subtask out of its parent, placing it right after the parent
+    pub fn promote_item(&mut self) {
+        let before = self.undo_entry("Promote");
+        if let Some(path) = self.selected_path().filter(|path| path.len() > 1) {
+            let parent_path = &path[..path.len() - 1];
+            let grandparent = (parent_path.len() > 1)
+                .then(|| item_at(&self.tasks, &parent_path[..parent_path.len() - 1]).map(|item| item.id))
+                .flatten();
+            self.reparent_selected(grandparent, parent_path[parent_path.len() - 1] + 1);
+        }
+        self.push_undo(before);
+    }
+
+    /// Make the selected item the last subtask of the sibling above it
+    pub fn demote_item(&mut self) {
+        let before = self.undo_entry("Demote");
+        if let Some(mut path) = self.selected_path() {
+            let last = path.len() - 1;
+            if path[last] > 0 {
+                path[last] -= 1;
+                if let Some(new_parent) = item_at(&self.tasks, &path).map(|item| item.id) {
+                    self.reparent_selected(Some(new_parent), usize::MAX);
                 }
             }
         }
+        self.push_undo(before);
+    }
+
+    /// Take the selected item (with its subtasks, tracking and history) out of
+    /// the tree and insert it under `parent` (the top level if `None`) at
+    /// `index` (clamped to the end), keeping it selected
+    fn reparent_selected(&mut self, parent: Option<Uuid>, index: usize) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        let Some(item) = item_at(&self.tasks, &path) else {
+            return;
+        };
+        // The new parent must exist outside the moved subtree
+        if let Some(parent) = parent {
+            if item.walk().iter().any(|inner| inner.id == parent) || find_path(&self.tasks, parent).is_none() {
+                return;
+            }
+        }
+        let old_parent = (path.len() > 1)
+            .then(|| item_at(&self.tasks, &path[..path.len() - 1]).map(|item| item.id))
+            .flatten();
+
+        let Some(item) = remove_at(&mut self.tasks, &path) else {
+            return;
+        };
+        let id = item.id;
+        let running = item.status == RunStatus::Running;
+        let siblings = match parent.and_then(|parent| find_item_mut(&mut self.tasks, parent)) {
+            Some(parent) => {
+                parent.expanded = true;
+                &mut parent.subtasks
+            }
+            None => &mut self.tasks,
+        };
+        siblings.insert(index.min(siblings.len()), item);
+
+        // A running item takes its parents' timers with it
+        if running {
+            if let Some(mut old_path) = old_parent.and_then(|old| find_path(&self.tasks, old)) {
+                // Any child position will do: only the path's ancestors are synced
+                old_path.push(0);
+                self.sync_parent_status(&old_path);
+            }
+            if let Some(new_path) = find_path(&self.tasks, id) {
+                self.sync_parent_status(&new_path);
+            }
+        }
+
+        self.select_by_id(id);
+        self.needs_save = true;
     }
 
     /// Toggle run/pause for selected item
     pub fn toggle_run_pause(&mut self) {
-        // Prevent starting tasks if not in Working mode
-        if self.global_mode.should_pause_timers() {
+        let before = self.undo_entry("Start/pause");
+
+        // Prevent starting tasks in modes that pause timers
+        if self.config.modes.should_pause_timers(&self.global_mode) {
             // Only allow pausing tasks in non-working modes, not starting them
-            if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-                if let Some(st_idx) = subtask_idx {
+            if let Some(path) = self.selected_path() {
+                if let Some(item) = item_at_mut(&mut self.tasks, &path) {
                     // Only pause if running
-                    if self.tasks[task_idx].subtasks[st_idx].status == RunStatus::Running {
-                        self.tasks[task_idx].subtasks[st_idx].pause();
-                        self.sync_parent_status(task_idx);
-                        self.needs_save = true;
-                    }
-                } else {
-                    // Only pause if running
-                    if self.tasks[task_idx].status == RunStatus::Running {
-                        self.tasks[task_idx].pause();
+                    if item.status == RunStatus::Running {
+                        item.pause();
+                        self.sync_parent_status(&path);
                         self.needs_save = true;
                     }
                 }
             }
+            self.push_undo(before);
             return;
         }
 
         // Normal toggle behavior when in Working mode
-        if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-            // Toggle the selected item
-            if let Some(st_idx) = subtask_idx {
-                // Toggling a subtask
-                self.tasks[task_idx].subtasks[st_idx].toggle_run_pause();
-
-                // Sync parent task status based on subtasks
-                self.sync_parent_status(task_idx);
-            } else {
-                // Toggling a parent task
-                self.tasks[task_idx].toggle_run_pause();
+        if let Some(path) = self.selected_path() {
+            // A blocked task can't start until its blockers are done
+            let running = item_at(&self.tasks, &path).map(|item| item.status) == Some(RunStatus::Running);
+            let blockers: Vec<String> = active_blockers(&self.tasks, &path)
+                .iter()
+                .map(|blocker| format!("\"{}\"", blocker.title))
+                .collect();
+            if !running && !blockers.is_empty() {
+                let title = item_at(&self.tasks, &path).map(|item| item.title.clone()).unwrap_or_default();
+                self.status_message = Some(format!("Can't start \"{}\": blocked by {}", title, blockers.join(", ")));
+                return;
+            }
+
+            if let Some(item) = item_at_mut(&mut self.tasks, &path) {
+                item.toggle_run_pause();
             }
+
+            // Sync the parents' status based on their subtasks
+            self.sync_parent_status(&path);
             self.needs_save = true;
         }
+        self.push_undo(before);
     }
 
-    /// Sync parent task status based on subtask states
+    /// Sync the status of the parents of the item at `path`, from the nearest up
     /// Note: For tasks with subtasks, the parent timer runs when any subtask is running
-    fn sync_parent_status(&mut self, task_idx: usize) {
-        let parent = &mut self.tasks[task_idx];
-
-        // Only sync if parent has subtasks
-        if parent.subtasks.is_empty() {
-            return;
-        }
+    fn sync_parent_status(&mut self, path: &[usize]) {
+        for depth in (1..path.len()).rev() {
+            let Some(parent) = item_at_mut(&mut self.tasks, &path[..depth]) else {
+                continue;
+            };
 
-        // Check if any subtask is running
-        let has_running = parent.subtasks.iter().any(|st| st.status == RunStatus::Running);
+            // Check if any subtask is running
+            let has_running = parent.subtasks.iter().any(|st| st.status == RunStatus::Running);
 
-        if has_running {
-            // If any subtask is running, start the parent timer
-            if parent.status != RunStatus::Running {
-                parent.start();
-            }
-        } else {
-            // No subtasks running - check if all are paused/idle
-            let all_paused_or_idle = parent.subtasks.iter()
-                .all(|st| st.status == RunStatus::Paused || st.status == RunStatus::Idle);
+            if has_running {
+                // If any subtask is running, start the parent timer
+                if parent.status != RunStatus::Running {
+                    parent.start();
+                }
+            } else {
+                // No subtasks running - check if all are paused/idle (or on hold)
+                let all_paused_or_idle = parent.subtasks.iter()
+                    .all(|st| matches!(st.status, RunStatus::Paused | RunStatus::Idle | RunStatus::Waiting));
 
-            if all_paused_or_idle && parent.status == RunStatus::Running {
-                // All subtasks paused/idle, so pause parent
-                parent.pause();
+                if all_paused_or_idle && parent.status == RunStatus::Running {
+                    // All subtasks paused/idle, so pause parent
+                    parent.pause();
+                }
             }
         }
     }
 
     /// Increase estimate for selected item
     pub fn increase_estimate(&mut self) {
+        let before = self.undo_entry("Change estimate");
         let step = self.estimate_step;
         if let Some(item) = self.get_selected_item_mut() {
             item.increase_estimate(step);
             self.needs_save = true;
         }
+        self.push_undo(before);
     }
 
     /// Decrease estimate for selected item
     pub fn decrease_estimate(&mut self) {
+        let before = self.undo_entry("Change estimate");
         let step = self.estimate_step;
         if let Some(item) = self.get_selected_item_mut() {
             item.decrease_estimate(step);
             self.needs_save = true;
         }
+        self.push_undo(before);
     }
 
     /// Mark selected item as done
     pub fn mark_done(&mut self) -> Result<()> {
-        if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-            // Clone the item before removing it (for undo)
-            let item_to_undo = if let Some(st_idx) = subtask_idx {
-                self.tasks[task_idx].subtasks[st_idx].clone()
-            } else {
-                self.tasks[task_idx].clone()
-            };
+        if let Some(path) = self.selected_path() {
+            let before = self.undo_entry("Mark done");
 
             // Remove the item
-            let mut item = if let Some(st_idx) = subtask_idx {
-                self.tasks[task_idx].subtasks.remove(st_idx)
-            } else {
-                self.tasks.remove(task_idx)
+            let Some(mut item) = remove_at(&mut self.tasks, &path) else {
+                return Ok(());
             };
 
-            // Mark as done
+            // Mark as done, unblocking whatever waited on it
             item.mark_done();
+            unblock(&mut self.tasks, &item);
 
             // Send notification
-            notifications::notify_task_done(&item.title);
-
-            // Save undo information before adding to done_today
-            self.undo_stack.push(UndoAction::MarkedDone {
-                item: item_to_undo,
-                was_subtask: subtask_idx.is_some(),
-                parent_task_index: if subtask_idx.is_some() { Some(task_idx) } else { None },
-                subtask_index: subtask_idx,
-                task_index: task_idx,
-            });
-
-            // Keep only the last 10 undo actions
-            if self.undo_stack.len() > 10 {
-                self.undo_stack.remove(0);
-            }
+            self.notifier.notify(&Notification::TaskDone(item.title.clone()));
 
             // Add to done_today list (will be in DONE section of daily file)
             self.done_today.push(item);
@@ -550,124 +1306,131 @@ impl AppState {
             }
 
             self.needs_save = true;
+            self.push_undo(before);
         }
 
         Ok(())
     }
 
-    /// Undo the last action (supports undoing mark_done, delete, and archive)
-    pub fn undo(&mut self) -> Result<()> {
-        if let Some(action) = self.undo_stack.pop() {
-            match action {
-                UndoAction::MarkedDone {
-                    item,
-                    was_subtask,
-                    parent_task_index,
-                    subtask_index: _,
-                    task_index,
-                } => {
-                    // Find and remove the item from done_today
-                    if let Some(done_idx) = self.done_today.iter().position(|i| i.id == item.id) {
-                        self.done_today.remove(done_idx);
-                    }
+    /// Snapshot of the current state, to undo a change about to be made
+    pub fn undo_entry(&self, label: &str) -> UndoEntry {
+        use crate::persistence::serialize_daily_file_with_date;
 
-                    // Restore the item to its original position
-                    if was_subtask {
-                        // Restore as subtask
-                        if let Some(parent_idx) = parent_task_index {
-                            if parent_idx < self.tasks.len() {
-                                // Insert at the end of subtasks (original position may not be valid anymore)
-                                self.tasks[parent_idx].subtasks.push(item);
-                            }
-                        }
-                    } else {
-                        // Restore as task
-                        // Insert at original position if valid, otherwise at the end
-                        let insert_pos = task_index.min(self.tasks.len());
-                        self.tasks.insert(insert_pos, item);
-                    }
+        UndoEntry {
+            label: label.to_string(),
+            daily: serialize_daily_file_with_date(&self.tasks, &self.done_today, &self.archived_today, self.file_date),
+            global_mode: self.global_mode.clone(),
+            paused_by_mode_task_ids: self.paused_by_mode_task_ids.iter().map(|id| id.to_string()).collect(),
+            mode_timeline: self.mode_timeline.clone(),
+            tomorrow: None,
+            at: Some(chrono::Local::now()),
+        }
+    }
 
-                    self.needs_save = true;
-                }
-                UndoAction::Deleted {
-                    item,
-                    was_subtask,
-                    parent_task_index,
-                    subtask_index: _,
-                    task_index,
-                } => {
-                    // Restore the item to its original position
-                    if was_subtask {
-                        // Restore as subtask
-                        if let Some(parent_idx) = parent_task_index {
-                            if parent_idx < self.tasks.len() {
-                                // Insert at the end of subtasks (original position may not be valid anymore)
-                                self.tasks[parent_idx].subtasks.push(item);
-                            }
-                        }
-                    } else {
-                        // Restore as task
-                        // Insert at original position if valid, otherwise at the end
-                        let insert_pos = task_index.min(self.tasks.len());
-                        self.tasks.insert(insert_pos, item);
-                    }
+    /// Record a change for undo, given the state before it (nothing is
+    /// recorded if the state didn't change)
+    ///
+    /// Only the state before each change is stored; the state after it is
+    /// the next entry's (or the current state).
+    pub fn push_undo(&mut self, before: UndoEntry) {
+        let items = (self.tasks.clone(), self.done_today.clone(), self.archived_today.clone());
+        if before.holds(&items, &self.global_mode, &self.paused_by_mode_task_ids, &self.mode_timeline) {
+            return;
+        }
+        self.undo_stack.push(before);
+        if self.undo_stack.len() > UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+        self.undo_log_dirty = true;
+    }
 
-                    self.needs_save = true;
-                }
-                UndoAction::Archived {
-                    item,
-                    was_subtask,
-                    parent_task_index,
-                    subtask_index: _,
-                    task_index,
-                } => {
-                    // Find and remove the item from archived_today
-                    if let Some(archived_idx) = self.archived_today.iter().position(|i| i.id == item.id) {
-                        self.archived_today.remove(archived_idx);
-                    }
+    /// Undo the last change
+    pub fn undo(&mut self) -> Result<()> {
+        if let Some(entry) = self.undo_stack.pop() {
+            let current = self.restore_undo_entry(entry)?;
+            self.redo_stack.push(current);
+            self.undo_log_dirty = true;
+        }
+        Ok(())
+    }
 
-                    // Restore the item to its original position
-                    if was_subtask {
-                        // Restore as subtask
-                        if let Some(parent_idx) = parent_task_index {
-                            if parent_idx < self.tasks.len() {
-                                // Insert at the end of subtasks (original position may not be valid anymore)
-                                self.tasks[parent_idx].subtasks.push(item);
-                            }
-                        }
-                    } else {
-                        // Restore as task
-                        // Insert at original position if valid, otherwise at the end
-                        let insert_pos = task_index.min(self.tasks.len());
-                        self.tasks.insert(insert_pos, item);
-                    }
+    /// Redo the last undone change
+    pub fn redo(&mut self) -> Result<()> {
+        if let Some(entry) = self.redo_stack.pop() {
+            let current = self.restore_undo_entry(entry)?;
+            self.undo_stack.push(current);
+            self.undo_log_dirty = true;
+        }
+        Ok(())
+    }
 
-                    self.needs_save = true;
-                }
-            }
+    /// Go back to the state in an undo/redo entry, returning the current
+    /// state as an entry that reverses it
+    fn restore_undo_entry(&mut self, entry: UndoEntry) -> Result<UndoEntry> {
+        use crate::persistence::{daily_file, lock_centre_dir, read_if_exists, record_day};
+
+        let mut current = self.undo_entry(&entry.label);
+
+        // Tomorrow's file goes back too if the change wrote it
+        if let Some(tomorrow) = &entry.tomorrow {
+            let _lock = lock_centre_dir()?;
+            let tomorrow_date = self.file_date + chrono::Duration::days(1);
+            current.tomorrow = Some(read_if_exists(daily_file(tomorrow_date)?)?.unwrap_or_default());
+            let items = Self::parse_daily_content(Some(tomorrow))?;
+            record_day(tomorrow_date, &items, None)?;
         }
 
+        let (active, done, archived) = Self::parse_daily_content(Some(&entry.daily))?;
+        self.replace_items(active, done, archived);
+        self.global_mode = entry.global_mode;
+        self.paused_by_mode_task_ids = entry
+            .paused_by_mode_task_ids
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect();
+        self.mode_timeline = entry.mode_timeline;
+        self.mode_timeline.extend_to(chrono::Local::now());
+        self.needs_save = true;
+        Ok(current)
+    }
+
+    /// Save the undo/redo stacks for the day being tracked (if they changed)
+    pub fn save_undo_log(&mut self) -> Result<()> {
+        use crate::persistence::{save_undo_log, undo_file, UndoLog};
+
+        if !self.undo_log_dirty {
+            return Ok(());
+        }
+        let log = UndoLog {
+            date: Some(self.file_date),
+            undo: self.undo_stack.clone(),
+            redo: self.redo_stack.clone(),
+        };
+        save_undo_log(undo_file()?, &log)?;
+        self.undo_log_dirty = false;
         Ok(())
     }
 
     /// Postpone selected item to tomorrow (will create/update tomorrow's file)
     pub fn postpone_to_tomorrow(&mut self) -> Result<()> {
-        if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-            let mut item = if let Some(st_idx) = subtask_idx {
-                self.tasks[task_idx].subtasks.remove(st_idx)
-            } else {
-                self.tasks.remove(task_idx)
+        if let Some(path) = self.selected_path() {
+            let mut before = self.undo_entry("Postpone");
+            let Some(mut item) = remove_at(&mut self.tasks, &path) else {
+                return Ok(());
             };
 
             // Postpone (pauses if running, sets to Idle)
             item.postpone();
 
             // Load tomorrow's file (if it exists), add the item, and save
-            use crate::persistence::{daily_file, parse_daily_file, atomic_write};
-            let tomorrow_date = chrono::Local::now().date_naive() + chrono::Duration::days(1);
+            use crate::persistence::{daily_file, parse_daily_file, lock_centre_dir, read_if_exists};
+            let _lock = lock_centre_dir()?;
+            let tomorrow_date = self.file_date + chrono::Duration::days(1);
             let tomorrow_path = daily_file(tomorrow_date)?;
+            before.tomorrow = Some(read_if_exists(&tomorrow_path)?.unwrap_or_default());
 
-            let (mut tomorrow_active, _tomorrow_done, _tomorrow_archived) = if tomorrow_path.exists() {
+            let (mut tomorrow_active, tomorrow_done, tomorrow_archived) = if tomorrow_path.exists() {
                 let content = std::fs::read_to_string(&tomorrow_path)?;
                 parse_daily_file(&content)?
             } else {
@@ -677,10 +1440,10 @@ impl AppState {
             // Add the postponed item to tomorrow's active tasks
             tomorrow_active.push(item);
 
-            // Save tomorrow's file with only active tasks (done/archived stay in their original day)
-            use crate::persistence::serialize_daily_file_with_date;
-            let tomorrow_content = serialize_daily_file_with_date(&tomorrow_active, &_tomorrow_done, &_tomorrow_archived, tomorrow_date);
-            atomic_write(&tomorrow_path, &tomorrow_content)?;
+            // Write tomorrow's file back with everything it already held (its
+            // own done/archived items included), logging the change in its event log
+            use crate::persistence::record_day;
+            record_day(tomorrow_date, &(tomorrow_active, tomorrow_done, tomorrow_archived), None)?;
 
             // Adjust selection if needed
             let flat_rows = flatten_tasks(&self.tasks);
@@ -689,6 +1452,7 @@ impl AppState {
             }
 
             self.needs_save = true;
+            self.push_undo(before);
         }
 
         Ok(())
@@ -696,38 +1460,17 @@ impl AppState {
 
     /// Archive selected item (moves to ARCHIVED section of daily file)
     pub fn archive_selected(&mut self) -> Result<()> {
-        if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-            // Clone the item before removing it (for undo)
-            let item_to_undo = if let Some(st_idx) = subtask_idx {
-                self.tasks[task_idx].subtasks[st_idx].clone()
-            } else {
-                self.tasks[task_idx].clone()
-            };
+        if let Some(path) = self.selected_path() {
+            let before = self.undo_entry("Archive");
 
             // Remove the item
-            let item = if let Some(st_idx) = subtask_idx {
-                self.tasks[task_idx].subtasks.remove(st_idx)
-            } else {
-                self.tasks.remove(task_idx)
+            let Some(item) = remove_at(&mut self.tasks, &path) else {
+                return Ok(());
             };
 
             // Add to archived_today list (will be in ARCHIVED section of daily file)
             self.archived_today.push(item);
 
-            // Save undo information
-            self.undo_stack.push(UndoAction::Archived {
-                item: item_to_undo,
-                was_subtask: subtask_idx.is_some(),
-                parent_task_index: if subtask_idx.is_some() { Some(task_idx) } else { None },
-                subtask_index: subtask_idx,
-                task_index: task_idx,
-            });
-
-            // Keep only the last 10 undo actions
-            if self.undo_stack.len() > 10 {
-                self.undo_stack.remove(0);
-            }
-
             // Adjust selection if needed
             let flat_rows = flatten_tasks(&self.tasks);
             if self.selected_index >= flat_rows.len() && flat_rows.len() > 0 {
@@ -735,6 +1478,7 @@ impl AppState {
             }
 
             self.needs_save = true;
+            self.push_undo(before);
         }
 
         Ok(())
@@ -742,41 +1486,16 @@ impl AppState {
 
     /// Delete the selected task or subtask
     pub fn delete_selected(&mut self) {
-        if let Some((task_idx, subtask_idx)) = self.get_selected_item() {
-            // Clone the item before removing it (for undo)
-            let item_to_undo = if let Some(st_idx) = subtask_idx {
-                self.tasks[task_idx].subtasks[st_idx].clone()
-            } else {
-                // Only delete task if it has no subtasks
-                if !self.tasks[task_idx].subtasks.is_empty() {
-                    // Don't delete tasks with subtasks - they must delete or archive subtasks first
-                    return;
-                }
-                self.tasks[task_idx].clone()
-            };
-
-            // Remove the item
-            if let Some(st_idx) = subtask_idx {
-                // Delete subtask
-                self.tasks[task_idx].subtasks.remove(st_idx);
-            } else {
-                // Delete entire task
-                self.tasks.remove(task_idx);
+        if let Some(path) = self.selected_path() {
+            // Only delete items without subtasks
+            if item_at(&self.tasks, &path).is_some_and(|item| !item.subtasks.is_empty()) {
+                // Don't delete tasks with subtasks - they must delete or archive subtasks first
+                return;
             }
+            let before = self.undo_entry("Delete");
 
-            // Save undo information
-            self.undo_stack.push(UndoAction::Deleted {
-                item: item_to_undo,
-                was_subtask: subtask_idx.is_some(),
-                parent_task_index: if subtask_idx.is_some() { Some(task_idx) } else { None },
-                subtask_index: subtask_idx,
-                task_index: task_idx,
-            });
-
-            // Keep only the last 10 undo actions
-            if self.undo_stack.len() > 10 {
-                self.undo_stack.remove(0);
-            }
+            // Remove the item
+            remove_at(&mut self.tasks, &path);
 
             // Adjust selection if needed
             let flat_rows = flatten_tasks(&self.tasks);
@@ -787,6 +1506,7 @@ impl AppState {
             }
 
             self.needs_save = true;
+            self.push_undo(before);
         }
     }
 
@@ -819,8 +1539,8 @@ impl AppState {
     /// Start editing the selected task/subtask (opens input form with existing data)
     pub fn start_edit_task(&mut self) {
         // Get selection info first (before borrowing mutably)
-        if let Some((_task_idx, subtask_idx)) = self.get_selected_item() {
-            let is_subtask = subtask_idx.is_some();
+        if let Some(path) = self.selected_path() {
+            let is_subtask = path.len() > 1;
 
             // Now get the item data for editing
             if let Some(item) = self.get_selected_item_mut() {
@@ -876,6 +1596,7 @@ impl AppState {
     /// Submit input form and create/update task/subtask
     pub fn submit_input_form(&mut self) {
         if let Some(form) = self.input_form.take() {
+            let before = self.undo_entry(if form.editing_item_id.is_some() { "Edit" } else { "Add" });
             if !form.title.trim().is_empty() {
                 // Parse tags from comma-separated string
                 let tags: Vec<String> = form.tags
@@ -886,41 +1607,24 @@ impl AppState {
 
                 if let Some(item_id) = form.editing_item_id {
                     // Editing existing item - find it and update
-                    let mut found = false;
-                    for task in &mut self.tasks {
-                        if task.id == item_id {
-                            task.title = form.title.clone();
-                            task.notes = form.notes.clone();
-                            task.tags = tags.clone();
-                            found = true;
-                            break;
-                        }
-                        for subtask in &mut task.subtasks {
-                            if subtask.id == item_id {
-                                subtask.title = form.title.clone();
-                                subtask.notes = form.notes.clone();
-                                subtask.tags = tags.clone();
-                                found = true;
-                                break;
-                            }
-                        }
-                        if found {
-                            break;
-                        }
-                    }
-                    if found {
+                    if let Some(item) = find_item_mut(&mut self.tasks, item_id) {
+                        item.title = form.title.clone();
+                        item.notes = form.notes.clone();
+                        item.tags = tags.clone();
                         self.needs_save = true;
                     }
                 } else {
                     // Creating new item
-                    let estimate = Duration::hours(1); // Default 1 hour
+                    let estimate = self.config.default_estimate();
 
                     if form.is_subtask {
-                        if let Some((task_idx, _)) = self.get_selected_item() {
+                        // The new subtask goes under the selected item, at any depth
+                        if let Some(parent) = self.get_selected_item_mut() {
                             let mut subtask = Item::new(form.title, estimate, ScheduleDay::Today);
                             subtask.notes = form.notes;
                             subtask.tags = tags;
-                            self.tasks[task_idx].add_subtask(subtask);
+                            parent.expanded = true;
+                            parent.add_subtask(subtask);
                             self.needs_save = true;
                         }
                     } else {
@@ -933,6 +1637,7 @@ impl AppState {
                 }
             }
             self.ui_mode = UiMode::Normal;
+            self.push_undo(before);
         }
     }
 
@@ -951,17 +1656,18 @@ impl AppState {
 
     /// Add a subtask directly (for testing and programmatic use)
     pub fn add_subtask(&mut self, title: String, estimate: Duration) {
-        if let Some((task_idx, _)) = self.get_selected_item() {
+        if let Some(parent) = self.get_selected_item_mut() {
             let subtask = Item::new(title, estimate, ScheduleDay::Today);
-            self.tasks[task_idx].add_subtask(subtask);
+            parent.expanded = true;
+            parent.add_subtask(subtask);
             self.needs_save = true;
         }
     }
 
-    /// Toggle collapse/expand for selected task
+    /// Toggle collapse/expand for the selected task or subtask
     pub fn toggle_expand(&mut self) {
-        if let Some((task_idx, None)) = self.get_selected_item() {
-            self.tasks[task_idx].expanded = !self.tasks[task_idx].expanded;
+        if let Some(item) = self.get_selected_item_mut() {
+            item.expanded = !item.expanded;
         }
     }
 
@@ -974,6 +1680,20 @@ impl AppState {
 
         // Track global state timing
         let now = Instant::now();
+
+        // Monotonic time stops during suspend (and ignores clock changes), so
+        // wall-clock time running ahead of it means time passed without us
+        let wall_now = chrono::Local::now();
+        let monotonic = Duration::from_std(now.duration_since(self.last_tick)).unwrap_or(Duration::zero());
+        if let Some(threshold) = self.config.away_threshold() {
+            if wall_now.signed_duration_since(self.last_tick_wall) - monotonic >= threshold {
+                self.record_away_gap(self.last_tick_wall + monotonic, wall_now);
+            }
+        }
+        self.last_tick_wall = wall_now;
+        if self.away_gap.is_some() && self.ui_mode == UiMode::Normal {
+            self.ui_mode = UiMode::AwayCheck;
+        }
         let current_state = self.get_global_state();
 
         // Accumulate time in previous state
@@ -1014,6 +1734,9 @@ impl AppState {
             self.last_state_change = now;
         }
 
+        // Keep the current mode segment up to date
+        self.mode_timeline.extend_to(chrono::Local::now());
+
         self.last_tick = now;
 
         // Increment animation frame counter (wraps at u32::MAX)
@@ -1022,38 +1745,24 @@ impl AppState {
 
     /// Check if any running items have hit their estimate
     pub fn check_estimate_hits(&mut self) {
-        if self.modal.is_some() {
+        if self.modal.is_some() || self.ui_mode == UiMode::ExternalChange {
             return; // Already showing a modal
         }
 
         for task in &self.tasks {
-            if task.is_over_estimate() {
-                // Send notification
-                notifications::notify_estimate_reached(&task.title);
-
-                self.modal = Some(ModalState {
-                    item_id: task.id,
-                    message: format!(
-                        "Task \"{}\" has reached its estimate ({:.1}h).",
-                        task.title,
-                        task.track.estimate_hours()
-                    ),
-                });
-                self.ui_mode = UiMode::Modal;
-                return;
-            }
-
-            for subtask in &task.subtasks {
-                if subtask.is_over_estimate() {
+            for item in task.walk() {
+                if item.is_over_estimate() {
                     // Send notification
-                    notifications::notify_estimate_reached(&subtask.title);
+                    self.notifier.notify(&Notification::EstimateReached(item.title.clone()));
 
+                    let kind = if item.id == task.id { "Task" } else { "Subtask" };
                     self.modal = Some(ModalState {
-                        item_id: subtask.id,
+                        item_id: item.id,
                         message: format!(
-                            "Subtask \"{}\" has reached its estimate ({:.1}h).",
-                            subtask.title,
-                            subtask.track.estimate_hours()
+                            "{} \"{}\" has reached its estimate ({:.1}h).",
+                            kind,
+                            item.title,
+                            item.track.estimate_hours()
                         ),
                     });
                     self.ui_mode = UiMode::Modal;
@@ -1065,22 +1774,13 @@ impl AppState {
 
     /// Find item by UUID (for modal actions)
     fn find_item_by_id_mut(&mut self, id: Uuid) -> Option<&mut Item> {
-        for task in &mut self.tasks {
-            if task.id == id {
-                return Some(task);
-            }
-            for subtask in &mut task.subtasks {
-                if subtask.id == id {
-                    return Some(subtask);
-                }
-            }
-        }
-        None
+        find_item_mut(&mut self.tasks, id)
     }
 
     /// Handle modal choice: Done
     pub fn modal_done(&mut self) -> Result<()> {
         if let Some(modal) = self.modal.take() {
+            let before = self.undo_entry("Mark done");
             if let Some(item) = self.find_item_by_id_mut(modal.item_id) {
                 item.mark_done();
             }
@@ -1090,38 +1790,37 @@ impl AppState {
             // Actually remove the done item and log it
             // This is a bit tricky - we need to find and remove the item
             self.remove_done_items()?;
+            self.push_undo(before);
         }
         Ok(())
     }
 
     /// Helper to remove done items and move them to done_today list
     fn remove_done_items(&mut self) -> Result<()> {
-        // Remove done tasks
-        let mut i = 0;
-        while i < self.tasks.len() {
-            if self.tasks[i].status == RunStatus::Done {
-                let item = self.tasks.remove(i);
-                self.done_today.push(item);
-            } else {
-                // Remove done subtasks
-                let mut j = 0;
-                while j < self.tasks[i].subtasks.len() {
-                    if self.tasks[i].subtasks[j].status == RunStatus::Done {
-                        let subtask = self.tasks[i].subtasks.remove(j);
-                        self.done_today.push(subtask);
-                    } else {
-                        j += 1;
-                    }
+        // Remove done tasks, then done subtasks at every depth below the rest
+        fn take_done(items: &mut Vec<Item>, done: &mut Vec<Item>) {
+            let mut i = 0;
+            while i < items.len() {
+                if items[i].status == RunStatus::Done {
+                    done.push(items.remove(i));
+                } else {
+                    take_done(&mut items[i].subtasks, done);
+                    i += 1;
                 }
-                i += 1;
             }
         }
+        let already_done = self.done_today.len();
+        take_done(&mut self.tasks, &mut self.done_today);
+        for done in &self.done_today[already_done..] {
+            unblock(&mut self.tasks, done);
+        }
 
         Ok(())
     }
 
     /// Handle modal choice: Extend (with custom duration)
     pub fn modal_extend(&mut self, additional: Duration) {
+        let before = self.undo_entry("Extend estimate");
         if let Some(modal) = self.modal.take() {
             if let Some(item) = self.find_item_by_id_mut(modal.item_id) {
                 item.increase_estimate(additional);
@@ -1130,10 +1829,12 @@ impl AppState {
             self.ui_mode = UiMode::Normal;
             self.needs_save = true;
         }
+        self.push_undo(before);
     }
 
     /// Handle modal choice: Pause
     pub fn modal_pause(&mut self) {
+        let before = self.undo_entry("Pause");
         if let Some(modal) = self.modal.take() {
             if let Some(item) = self.find_item_by_id_mut(modal.item_id) {
                 item.pause();
@@ -1141,6 +1842,7 @@ impl AppState {
             self.ui_mode = UiMode::Normal;
             self.needs_save = true;
         }
+        self.push_undo(before);
     }
 
     /// Handle modal choice: Tomorrow
@@ -1148,60 +1850,331 @@ impl AppState {
         if let Some(modal) = self.modal.take() {
             let item_id = modal.item_id;
 
-            // Find and remove the item, postpone to tomorrow
-            for (task_idx, task) in self.tasks.iter_mut().enumerate() {
-                if task.id == item_id {
-                    let item = self.tasks.remove(task_idx);
-                    // Temporarily store the item ID for postponing
-                    if let Some((idx, _)) = self.tasks.iter().enumerate().find(|(_, t)| t.id == item.id) {
-                        self.selected_index = idx;
-                    }
-                    self.tasks.insert(task_idx.min(self.tasks.len()), item);
-                    self.ui_mode = UiMode::Normal;
-                    // Postpone will be handled by the caller
-                    let _ = self.postpone_to_tomorrow();
-                    return;
+            // Select the item and postpone it to tomorrow
+            self.ui_mode = UiMode::Normal;
+            if self.select_by_id(item_id) {
+                let _ = self.postpone_to_tomorrow();
+            }
+        }
+    }
+
+    /// Save state to disk (uses new daily file format)
+    ///
+    /// If the daily file changed on disk since we last read or wrote it,
+    /// nothing is written and the external-change prompt is opened instead.
+    pub fn save(&mut self) -> Result<()> {
+        use crate::persistence::lock_centre_dir;
+
+        // Wait for the user to decide how to handle an external change, or
+        // for the new day if the old one was already closed out
+        if self.external_change.is_some() || self.closed_day == Some(self.file_date) {
+            return Ok(());
+        }
+
+        let _lock = lock_centre_dir()?;
+
+        let disk = self.read_daily_from_disk()?;
+        if disk != self.daily_base {
+            // Don't interrupt forms or other modals; retry on the next save
+            if self.ui_mode == UiMode::Normal {
+                self.external_change = Some(ExternalChange::Tasks(disk));
+                self.ui_mode = UiMode::ExternalChange;
+            }
+            return Ok(());
+        }
+
+        self.write_daily_file()?;
+
+        // Save metadata (global mode, etc.)
+        self.save_metadata()?;
+
+        // Save today's mode timeline
+        self.save_mode_timeline()?;
+
+        // Save the undo log so changes can be undone after a restart
+        self.save_undo_log()?;
+
+        Ok(())
+    }
+
+    /// Save everything, merging with any external change instead of prompting
+    /// (used on exit, when there's no UI left to ask)
+    pub fn save_merging(&mut self) -> Result<()> {
+        self.save_tasks()?;
+        self.save_metadata()?;
+        self.save_mode_timeline()?;
+        self.save_undo_log()
+    }
+
+    /// Save only the daily task file, merging with any external change
+    /// (used by the CLI, which never changes modes)
+    pub fn save_tasks(&mut self) -> Result<()> {
+        use crate::persistence::lock_centre_dir;
+
+        let _lock = lock_centre_dir()?;
+
+        let disk = self.read_daily_from_disk()?;
+        if disk != self.daily_base {
+            self.merge_daily_with(disk)?;
+        }
+        self.external_change = None;
+
+        self.write_daily_file()
+    }
+
+    /// Write the daily file (caller holds the centre lock)
+    fn write_daily_file(&mut self) -> Result<()> {
+        // Save daily file using file_date (not current system date)
+        // This ensures we save to the correct day's file even if midnight has passed.
+        // Changes go to the day's event log first and the file is regenerated from it.
+        let items = (self.tasks.clone(), self.done_today.clone(), self.archived_today.clone());
+        let daily_content = self.day_recorder.record(self.file_date, &items, Some(&self.global_mode))?;
+
+        self.daily_base = Some(daily_content);
+        self.needs_save = false;
+        Ok(())
+    }
+
+    fn read_daily_from_disk(&self) -> Result<Option<String>> {
+        use crate::persistence::{daily_file, read_if_exists};
+        read_if_exists(daily_file(self.file_date)?)
+    }
+
+    fn read_journal_from_disk(&self) -> Result<Option<String>> {
+        use crate::persistence::{journal_file_for_date, read_if_exists};
+        read_if_exists(journal_file_for_date(self.file_date)?)
+    }
+
+    /// Parse daily file content (a missing file has no items)
+    fn parse_daily_content(content: Option<&str>) -> Result<crate::persistence::DailyItems> {
+        match content {
+            Some(content) if !content.is_empty() => crate::persistence::parse_daily_file(content),
+            _ => Ok((Vec::new(), Vec::new(), Vec::new())),
+        }
+    }
+
+    /// Parse daily file content, or None if it fails to parse or any item in
+    /// it had to be skipped
+    fn parse_daily_content_cleanly(content: Option<&str>) -> Option<crate::persistence::DailyItems> {
+        match content {
+            Some(content) if !content.is_empty() => match crate::persistence::parse_daily_file_checked(content) {
+                Ok((items, warnings)) if warnings.is_empty() => Some(items),
+                _ => None,
+            },
+            _ => Some((Vec::new(), Vec::new(), Vec::new())),
+        }
+    }
+
+    /// Replace all items, keeping the selection on the same task when possible
+    pub fn replace_items(&mut self, active: Vec<Item>, done: Vec<Item>, archived: Vec<Item>) {
+        let selected_id = self.selected_item().map(|item| item.id);
+
+        self.tasks = active;
+        self.done_today = done;
+        self.archived_today = archived;
+
+        // Elapsed comes from history; running items keep running from now
+        for task in &mut self.tasks {
+            task.sync_elapsed_from_history();
+            task.resume_running_timer();
+        }
+
+        if !selected_id.is_some_and(|id| self.select_by_id(id)) {
+            let row_count = flatten_tasks(&self.tasks).len();
+            self.selected_index = self.selected_index.min(row_count.saturating_sub(1));
+        }
+    }
+
+    /// Three-way merge the in-memory items with the daily file content on disk
+    fn merge_daily_with(&mut self, disk: Option<String>) -> Result<()> {
+        use crate::persistence::merge_daily;
+
+        let base = Self::parse_daily_content(self.daily_base.as_deref())?;
+        let theirs = Self::parse_daily_content(disk.as_deref())?;
+        let ours = (self.tasks.clone(), self.done_today.clone(), self.archived_today.clone());
+
+        let (active, done, archived) = merge_daily(&base, &ours, &theirs);
+        self.replace_items(active, done, archived);
+        self.daily_base = disk;
+        self.needs_save = true;
+        Ok(())
+    }
+
+    /// Pick up edits made to today's daily file or journal by other programs
+    ///
+    /// Called from the run loop. Files are only re-read when their mtime
+    /// changes. Without unsaved changes the file is reloaded in place (running
+    /// timers and selection follow task IDs); otherwise the external-change
+    /// prompt is opened. Returns true if anything was reloaded.
+    pub fn reload_external_changes(&mut self) -> Result<bool> {
+        use crate::persistence::{daily_file, journal_file_for_date};
+
+        if self.external_change.is_some() {
+            return Ok(false);
+        }
+
+        let mut reloaded = false;
+
+        let daily_path = daily_file(self.file_date)?;
+        let mtime = std::fs::metadata(&daily_path).and_then(|m| m.modified()).ok();
+        if mtime != self.daily_mtime {
+            let disk = self.read_daily_from_disk()?;
+            if disk != self.daily_base {
+                // Only reload on its own when the whole file parsed; a half-written
+                // edit would otherwise drop tasks (and the next save would lose them)
+                let clean = if self.needs_save { None } else { Self::parse_daily_content_cleanly(disk.as_deref()) };
+                if let Some((active, done, archived)) = clean {
+                    let before = self.undo_entry("Reload");
+                    self.replace_items(active, done, archived);
+                    self.daily_base = disk;
+                    self.push_undo(before);
+                    reloaded = true;
+                } else if self.ui_mode == UiMode::Normal {
+                    self.external_change = Some(ExternalChange::Tasks(disk));
+                    self.ui_mode = UiMode::ExternalChange;
+                    return Ok(reloaded);
+                } else {
+                    // Try again once the form/modal is closed
+                    return Ok(reloaded);
                 }
+            }
+            self.daily_mtime = mtime;
+        }
+
+        let journal_path = journal_file_for_date(self.file_date)?;
+        let mtime = std::fs::metadata(&journal_path).and_then(|m| m.modified()).ok();
+        if mtime != self.journal_mtime {
+            let disk = self.read_journal_from_disk()?;
+            if disk != self.journal_base {
+                if !self.journal_needs_save && self.ui_mode != UiMode::EditingJournal {
+                    self.journal_content = disk.clone().unwrap_or_default();
+                    self.journal_cursor_pos = self.journal_cursor_pos.min(self.journal_content.len());
+                    self.journal_base = disk;
+                    reloaded = true;
+                } else if matches!(self.ui_mode, UiMode::Normal | UiMode::EditingJournal) {
+                    self.external_change = Some(ExternalChange::Journal(disk));
+                    self.ui_mode = UiMode::ExternalChange;
+                    return Ok(reloaded);
+                } else {
+                    return Ok(reloaded);
+                }
+            }
+            self.journal_mtime = mtime;
+        }
+
+        Ok(reloaded)
+    }
 
-                for (st_idx, subtask) in task.subtasks.iter().enumerate() {
-                    if subtask.id == item_id {
-                        let item = task.subtasks.remove(st_idx);
-                        task.subtasks.insert(st_idx.min(task.subtasks.len()), item);
-                        self.ui_mode = UiMode::Normal;
-                        let _ = self.postpone_to_tomorrow();
-                        return;
+    /// Pick up external edits like `reload_external_changes`, showing a
+    /// failure in the status bar. Returns whether anything was reloaded.
+    pub fn try_reload_external_changes(&mut self) -> bool {
+        match self.reload_external_changes() {
+            Ok(reloaded) => {
+                self.reload_error = None;
+                reloaded
+            }
+            Err(e) => {
+                self.report_reload_error(&e);
+                false
+            }
+        }
+    }
+
+    /// Show a live-reload failure, unless it's the one already reported
+    fn report_reload_error(&mut self, e: &anyhow::Error) {
+        let message = format!("Couldn't reload today's files: {:#}", e);
+        if self.reload_error.as_ref() != Some(&message) {
+            self.status_message = Some(message.clone());
+            self.reload_error = Some(message);
+        }
+    }
+
+    /// Resolve the pending external change with the user's choice
+    pub fn resolve_external_change(&mut self, choice: ExternalChangeChoice) -> Result<()> {
+        match self.external_change.take() {
+            Some(ExternalChange::Tasks(disk)) => match choice {
+                ExternalChangeChoice::Reload => {
+                    let before = self.undo_entry("Reload");
+                    let (active, done, archived) = Self::parse_daily_content(disk.as_deref())?;
+                    self.replace_items(active, done, archived);
+                    self.daily_base = disk;
+                    self.needs_save = false;
+                    self.push_undo(before);
+                }
+                ExternalChangeChoice::Merge => {
+                    let before = self.undo_entry("Merge");
+                    self.merge_daily_with(disk)?;
+                    self.push_undo(before);
+                }
+                ExternalChangeChoice::Overwrite => {
+                    self.daily_base = disk;
+                    self.needs_save = true;
+                }
+            },
+            Some(ExternalChange::Journal(disk)) => {
+                match choice {
+                    ExternalChangeChoice::Reload => {
+                        self.journal_content = disk.clone().unwrap_or_default();
+                        self.journal_needs_save = false;
+                    }
+                    ExternalChangeChoice::Merge => {
+                        self.journal_content = merge_journal(&self.journal_content, disk.as_deref().unwrap_or(""));
+                        self.journal_needs_save = true;
+                    }
+                    ExternalChangeChoice::Overwrite => {
+                        self.journal_needs_save = true;
                     }
                 }
+                self.journal_base = disk;
+                self.journal_cursor_pos = self.journal_cursor_pos.min(self.journal_content.len());
             }
+            None => {}
         }
-    }
-
-    /// Save state to disk (uses new daily file format)
-    pub fn save(&mut self) -> Result<()> {
-        use crate::persistence::{serialize_daily_file, daily_file, atomic_write};
-
-        // Save daily file using file_date (not current system date)
-        // This ensures we save to the correct day's file even if midnight has passed
-        let daily_content = serialize_daily_file(&self.tasks, &self.done_today, &self.archived_today);
-        let file_path = daily_file(self.file_date)?;
-        atomic_write(file_path, &daily_content)?;
-
-        // Save metadata (global mode, etc.)
-        self.save_metadata()?;
 
-        self.needs_save = false;
+        self.ui_mode = UiMode::Normal;
         Ok(())
     }
 
     /// Save journal if needed
+    ///
+    /// Like `save`, an external change to the journal opens the prompt
+    /// instead of overwriting it.
     pub fn save_journal(&mut self) -> Result<()> {
-        if self.journal_needs_save {
-            use crate::persistence::{journal_file_for_date, atomic_write};
-            // Use file_date to ensure we save to the correct day's journal even if midnight has passed
-            let journal_path = journal_file_for_date(self.file_date)?;
-            atomic_write(journal_path, &self.journal_content)?;
-            self.journal_needs_save = false;
+        self.save_journal_with(false)
+    }
+
+    /// Save journal if needed, merging with any external change (used on exit)
+    pub fn save_journal_merging(&mut self) -> Result<()> {
+        self.save_journal_with(true)
+    }
+
+    fn save_journal_with(&mut self, merge_on_conflict: bool) -> Result<()> {
+        use crate::persistence::{journal_file_for_date, atomic_write, lock_centre_dir};
+
+        if !self.journal_needs_save || (self.external_change.is_some() && !merge_on_conflict) {
+            return Ok(());
+        }
+
+        let _lock = lock_centre_dir()?;
+
+        let disk = self.read_journal_from_disk()?;
+        if disk != self.journal_base {
+            if merge_on_conflict {
+                self.journal_content = merge_journal(&self.journal_content, disk.as_deref().unwrap_or(""));
+            } else {
+                if matches!(self.ui_mode, UiMode::Normal | UiMode::EditingJournal) {
+                    self.external_change = Some(ExternalChange::Journal(disk));
+                    self.ui_mode = UiMode::ExternalChange;
+                }
+                return Ok(());
+            }
         }
+
+        // Use file_date to ensure we save to the correct day's journal even if midnight has passed
+        let journal_path = journal_file_for_date(self.file_date)?;
+        atomic_write(journal_path, &self.journal_content)?;
+        self.journal_base = Some(self.journal_content.clone());
+        self.journal_needs_save = false;
         Ok(())
     }
 
@@ -1225,17 +2198,13 @@ impl AppState {
 
     /// Determine the current global activity state (only considers active tasks, not done)
     pub fn get_global_state(&self) -> GlobalState {
-        let has_running = self.tasks.iter().any(|t| {
-            t.status == RunStatus::Running || t.subtasks.iter().any(|s| s.status == RunStatus::Running)
-        });
+        let has_running = walk_items(&self.tasks).any(|item| item.status == RunStatus::Running);
 
         if has_running {
             return GlobalState::Running;
         }
 
-        let has_paused = self.tasks.iter().any(|t| {
-            t.status == RunStatus::Paused || t.subtasks.iter().any(|s| s.status == RunStatus::Paused)
-        });
+        let has_paused = walk_items(&self.tasks).any(|item| item.status == RunStatus::Paused);
 
         if has_paused {
             return GlobalState::Paused;
@@ -1447,122 +2416,129 @@ impl AppState {
 
     /// Open the mode selector modal
     pub fn open_mode_selector(&mut self) {
+        self.mode_since = None;
+        self.editing_mode_since = false;
         self.ui_mode = UiMode::ModeSelector;
     }
 
+    /// Switch to a mode chosen in the selector, backdated to the "since"
+    /// time if one was typed (an unreadable time keeps the selector open)
+    pub fn select_mode(&mut self, mode: GlobalMode) {
+        let since = match self.mode_since.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(text) => match self.parse_mode_since(text) {
+                Some(since) => Some(since),
+                None => {
+                    self.editing_mode_since = true;
+                    return;
+                }
+            },
+        };
+        self.mode_since = None;
+        self.editing_mode_since = false;
+        match since {
+            Some(since) => self.set_global_mode_since(mode, Some(since)),
+            None => self.set_global_mode(mode),
+        }
+    }
+
+    /// Parse a "since HH:MM" time: earlier today, not before the day started
+    fn parse_mode_since(&self, text: &str) -> Option<chrono::DateTime<chrono::Local>> {
+        let time = chrono::NaiveTime::parse_from_str(text, "%H:%M").ok()?;
+        let day_start = self.config.day_start();
+        let since = crate::day::time_on(self.file_date, time, day_start)?;
+        (since >= crate::day::start_of(self.file_date, day_start) && since <= chrono::Local::now()).then_some(since)
+    }
+
     /// Set the global mode and pause/resume tasks accordingly
     pub fn set_global_mode(&mut self, mode: GlobalMode) {
-        let previous_mode = self.global_mode;
-        self.global_mode = mode;
-
-        // Record time spent in previous mode
-        let now = Instant::now();
-        let elapsed_since_change = now.duration_since(self.last_mode_change);
-        let duration = Duration::from_std(elapsed_since_change).unwrap_or(Duration::zero());
+        self.set_global_mode_since(mode, None);
+    }
 
-        match previous_mode {
-            GlobalMode::Working => self.mode_time_working = self.mode_time_working + duration,
-            GlobalMode::Break => self.mode_time_break = self.mode_time_break + duration,
-            GlobalMode::Lunch => self.mode_time_lunch = self.mode_time_lunch + duration,
-            GlobalMode::Gym => self.mode_time_gym = self.mode_time_gym + duration,
-            GlobalMode::Dinner => self.mode_time_dinner = self.mode_time_dinner + duration,
-            GlobalMode::Personal => self.mode_time_personal = self.mode_time_personal + duration,
-            GlobalMode::Sleep => self.mode_time_sleep = self.mode_time_sleep + duration,
+    /// Set the global mode as of an earlier time (or now with None)
+    ///
+    /// A backdated switch reassigns the mode time since then, and tasks it
+    /// pauses or resumes get their pause/resume recorded at that time.
+    pub fn set_global_mode_since(&mut self, mode: GlobalMode, since: Option<chrono::DateTime<chrono::Local>>) {
+        let before = self.undo_entry("Switch mode");
+        if mode != self.global_mode {
+            self.notifier.notify(&Notification::ModeChanged(self.config.modes.label(&mode)));
         }
 
-        self.last_mode_change = now;
-        // Update the wall clock timestamp to track when mode changed (for day boundary detection)
-        self.last_mode_change_timestamp = Some(chrono::Local::now());
+        let was_paused = self.config.modes.should_pause_timers(&self.global_mode);
+        let pauses = self.config.modes.should_pause_timers(&mode);
+
+        // Close the previous mode's segment and start a new one
+        let now = chrono::Local::now();
+        match since {
+            Some(since) => {
+                self.mode_timeline.extend_to(now);
+                self.mode_timeline.reassign(since, now, Some(mode.clone()));
+            }
+            None => self.mode_timeline.switch_to(mode.clone(), now),
+        }
+        self.global_mode = mode;
 
         // Handle task state changes based on mode
-        if mode.should_pause_timers() && !previous_mode.should_pause_timers() {
-            // Switching from Working to non-working mode: Pause all running tasks
-            self.pause_all_for_mode();
-        } else if !mode.should_pause_timers() && previous_mode.should_pause_timers() {
-            // Switching from non-working to Working mode: Resume previously paused tasks
-            self.resume_all_from_mode();
+        if pauses && !was_paused {
+            // Switching from a working mode to one that pauses: Pause all running tasks
+            self.pause_all_for_mode(since);
+        } else if !pauses && was_paused {
+            // Switching back to a working mode: Resume previously paused tasks
+            self.resume_all_from_mode(since);
         }
 
         self.ui_mode = UiMode::Normal;
         self.needs_save = true;
+        self.push_undo(before);
     }
 
-    /// Pause all running tasks due to mode change
-    fn pause_all_for_mode(&mut self) {
+    /// Pause all running tasks due to mode change (as of `since`, if given)
+    fn pause_all_for_mode(&mut self, since: Option<chrono::DateTime<chrono::Local>>) {
         // Store which tasks were running
         self.paused_by_mode_task_ids.clear();
 
-        for task in &mut self.tasks {
-            if task.status == RunStatus::Running {
-                self.paused_by_mode_task_ids.push(task.id);
-                task.pause();
-            }
-            for subtask in &mut task.subtasks {
-                if subtask.status == RunStatus::Running {
-                    self.paused_by_mode_task_ids.push(subtask.id);
-                    subtask.pause();
+        let paused_ids = &mut self.paused_by_mode_task_ids;
+        walk_items_mut(&mut self.tasks, &mut |item| {
+            if item.status == RunStatus::Running {
+                paused_ids.push(item.id);
+                match since {
+                    Some(since) => item.pause_since(since),
+                    None => item.pause(),
                 }
             }
-        }
+        });
     }
 
-    /// Resume tasks that were paused due to mode change
-    fn resume_all_from_mode(&mut self) {
+    /// Resume tasks that were paused due to mode change (as of `since`, if given)
+    fn resume_all_from_mode(&mut self, since: Option<chrono::DateTime<chrono::Local>>) {
         // Resume tasks that were paused by mode change
-        for task in &mut self.tasks {
-            if self.paused_by_mode_task_ids.contains(&task.id) {
-                task.start();
-            }
-            for subtask in &mut task.subtasks {
-                if self.paused_by_mode_task_ids.contains(&subtask.id) {
-                    subtask.start();
+        let paused_ids = &self.paused_by_mode_task_ids;
+        walk_items_mut(&mut self.tasks, &mut |item| {
+            if paused_ids.contains(&item.id) {
+                match since {
+                    Some(since) => item.resume_since(since),
+                    None => item.start(),
                 }
             }
-        }
+        });
 
         self.paused_by_mode_task_ids.clear();
     }
 
-    /// Get time spent in each mode
-    pub fn get_mode_times(&self) -> [(GlobalMode, Duration); 7] {
-        // Include time in current mode
-        let now = Instant::now();
-        let elapsed_in_current = now.duration_since(self.last_mode_change);
-        let current_duration = Duration::from_std(elapsed_in_current).unwrap_or(Duration::zero());
-
-        let mut times = [
-            (GlobalMode::Working, self.mode_time_working),
-            (GlobalMode::Break, self.mode_time_break),
-            (GlobalMode::Lunch, self.mode_time_lunch),
-            (GlobalMode::Gym, self.mode_time_gym),
-            (GlobalMode::Dinner, self.mode_time_dinner),
-            (GlobalMode::Personal, self.mode_time_personal),
-            (GlobalMode::Sleep, self.mode_time_sleep),
-        ];
-
-        // Add current mode time
-        for (mode, time) in times.iter_mut() {
-            if *mode == self.global_mode {
-                *time = *time + current_duration;
-            }
-        }
-
+    /// Get time spent in each mode today, in configured mode order
+    pub fn get_mode_times(&self) -> Vec<(GlobalMode, Duration)> {
+        let modes = self.config.modes.all();
+        let mut times = self.mode_timeline.totals();
+        times.sort_by_key(|(mode, _)| {
+            modes.iter().position(|def| def.name == mode.name()).unwrap_or(modes.len())
+        });
         times
     }
 
     /// Check if there are any running tasks
     fn has_running_tasks(&self) -> bool {
-        for task in &self.tasks {
-            if task.status == RunStatus::Running {
-                return true;
-            }
-            for subtask in &task.subtasks {
-                if subtask.status == RunStatus::Running {
-                    return true;
-                }
-            }
-        }
-        false
+        walk_items(&self.tasks).any(|item| item.status == RunStatus::Running)
     }
 
     /// Check for idle time and show modal if needed
@@ -1572,23 +2548,119 @@ impl AppState {
         // If idle check is already active and deadline has passed, auto-pause all
         if let Some(deadline) = self.idle_check_deadline {
             if now >= deadline {
-                self.auto_pause_all();
+                self.trim_idle_time();
                 self.idle_check_deadline = None;
-                self.ui_mode = UiMode::Normal;
+                self.ui_mode = if self.idle_trim.is_some() { UiMode::IdleTrimmed } else { UiMode::Normal };
                 self.last_idle_check = now;
                 return;
             }
         }
 
-        // Check if 30 minutes have passed since last check
+        // Check if the configured interval has passed since last check
+        let Some(interval) = self.config.idle_check_interval() else {
+            return;
+        };
         if self.ui_mode == UiMode::Normal
             && self.has_running_tasks()
-            && now.duration_since(self.last_idle_check).as_secs() >= 30 * 60
+            && now.duration_since(self.last_idle_check) >= interval
         {
             // Show idle check modal
             self.ui_mode = UiMode::IdleCheck;
-            self.idle_check_deadline = Some(now + std::time::Duration::from_secs(30 * 60));
+            self.idle_check_deadline = Some(now + self.config.idle_auto_pause());
+            self.notifier.notify(&Notification::IdleCheck);
+        }
+    }
+
+    /// Note that the user pressed a key (called by the event loop)
+    pub fn record_input(&mut self) {
+        self.last_input = chrono::Local::now();
+    }
+
+    /// Pause running tasks as of the last keypress (or when they started
+    /// running, if later), since nobody was working while the idle check went
+    /// unanswered
+    fn trim_idle_time(&mut self) {
+        let before = self.undo_entry("Idle timeout");
+        let to = chrono::Local::now();
+        let last_input = std::cmp::min(self.last_input, to);
+        let mut items = Vec::new();
+        walk_items_mut(&mut self.tasks, &mut |item| {
+            if item.status == RunStatus::Running {
+                let started = item
+                    .state_history
+                    .iter()
+                    .rev()
+                    .find(|event| event.to_status == RunStatus::Running)
+                    .map(|event| event.timestamp);
+                let from = started.map_or(last_input, |started| std::cmp::max(last_input, started));
+                items.push((item.id, from));
+                item.pause_since(from);
+            }
+        });
+
+        if !items.is_empty() {
+            self.idle_trim = Some(IdleTrim { last_input, to, items });
+        }
+        self.needs_save = true;
+        self.push_undo(before);
+    }
+
+    /// Close the idle trim prompt, optionally giving the trimmed time back
+    /// (the tasks stay paused either way)
+    pub fn resolve_idle_trim(&mut self, keep: bool) {
+        let Some(trim) = self.idle_trim.take() else {
+            return;
+        };
+        self.ui_mode = UiMode::Normal;
+
+        if keep {
+            let before = self.undo_entry("Keep idle time");
+            for (id, from) in &trim.items {
+                if let Some(item) = self.find_item_by_id_mut(*id) {
+                    // Only fails if the item was edited meanwhile (or nothing was
+                    // trimmed); nothing to give back then
+                    let _ = item.add_session(*from, trim.to);
+                }
+            }
+            self.needs_save = true;
+            self.push_undo(before);
+        }
+    }
+
+    /// Remember a gap in wall-clock time until the user decides what it was
+    /// (gaps noticed before that are combined)
+    fn record_away_gap(&mut self, from: chrono::DateTime<chrono::Local>, to: chrono::DateTime<chrono::Local>) {
+        match &mut self.away_gap {
+            Some(gap) => gap.to = to,
+            None => self.away_gap = Some(AwayGap { from, to }),
+        }
+    }
+
+    /// Apply the user's decision about time spent away
+    pub fn resolve_away(&mut self, choice: AwayChoice) {
+        let Some(gap) = self.away_gap.take() else {
+            return;
+        };
+        self.ui_mode = UiMode::Normal;
+        let before = self.undo_entry("Time away");
+
+        let (pause_tasks, mode) = match choice {
+            AwayChoice::Count => (false, Some(self.global_mode.clone())),
+            AwayChoice::Discard => (true, None),
+            AwayChoice::Mode(mode) => (self.config.modes.should_pause_timers(&mode), Some(mode)),
+        };
+
+        for task in &mut self.tasks {
+            if pause_tasks {
+                task.exclude_interval(gap.from, gap.to);
+            }
+            // The in-memory timers missed the gap; take elapsed time from history
+            task.sync_elapsed_from_history();
+            task.resume_running_timer();
         }
+        self.mode_timeline.reassign(gap.from, gap.to, mode);
+        self.needs_save = true;
+        self.push_undo(before);
     }
 
     /// Confirm user is still working (reset idle check)
@@ -1600,31 +2672,23 @@ impl AppState {
 
     /// Auto-pause all running tasks
     pub fn auto_pause_all(&mut self) {
-        for task in &mut self.tasks {
-            if task.status == RunStatus::Running {
-                task.pause();
-            }
-            for subtask in &mut task.subtasks {
-                if subtask.status == RunStatus::Running {
-                    subtask.pause();
-                }
+        let before = self.undo_entry("Pause all");
+        walk_items_mut(&mut self.tasks, &mut |item| {
+            if item.status == RunStatus::Running {
+                item.pause();
             }
-        }
+        });
         self.needs_save = true;
+        self.push_undo(before);
     }
 
     /// Move all running and paused tasks to idle (for app exit)
     pub fn auto_idle_all(&mut self) {
-        for task in &mut self.tasks {
-            if task.status == RunStatus::Running || task.status == RunStatus::Paused {
-                task.set_idle();
+        walk_items_mut(&mut self.tasks, &mut |item| {
+            if item.status == RunStatus::Running || item.status == RunStatus::Paused {
+                item.set_idle();
             }
-            for subtask in &mut task.subtasks {
-                if subtask.status == RunStatus::Running || subtask.status == RunStatus::Paused {
-                    subtask.set_idle();
-                }
-            }
-        }
+        });
         self.needs_save = true;
     }
 
@@ -1638,9 +2702,7 @@ impl AppState {
     /// Scroll the done pane down
     pub fn scroll_done_down(&mut self) {
         // Calculate total number of lines (tasks + subtasks)
-        let total_lines: usize = self.done_today.iter()
-            .map(|task| 1 + task.subtasks.len())
-            .sum();
+        let total_lines = walk_items(&self.done_today).count();
 
         // Allow scrolling as long as there are items
         if total_lines > 0 {
@@ -1686,10 +2748,14 @@ mod tests {
             Duration::hours(2),
             ScheduleDay::Today,
         );
-        let mut app = AppState::new(vec![task1, task2], Vec::new(), Vec::new(), String::new());
+        let mut app = AppState::new(vec![task1, task2], Vec::new(), Vec::new(), String::new(), Config::default());
         // Ensure tests always start in Working mode (ignore any loaded metadata)
-        app.global_mode = GlobalMode::Working;
-        app.last_mode_change_timestamp = Some(chrono::Local::now());
+        app.global_mode = GlobalMode::new("Working");
+        // Don't send real notifications from tests
+        app.notifier = Box::new(crate::notifications::NoopNotifier);
+        // Nor pick up undo history from a real session
+        app.undo_stack.clear();
+        app.redo_stack.clear();
         app
     }
 
@@ -1718,11 +2784,9 @@ mod tests {
     }
 
     #[test]
-    fn test_get_selected_item() {
+    fn test_selected_path() {
         let app = create_test_app();
-        let (task_idx, subtask_idx) = app.get_selected_item().unwrap();
-        assert_eq!(task_idx, 0);
-        assert!(subtask_idx.is_none());
+        assert_eq!(app.selected_path(), Some(vec![0]));
     }
 
     #[test]
@@ -1736,6 +2800,189 @@ mod tests {
         assert_eq!(app.tasks[0].status, RunStatus::Paused);
     }
 
+    #[test]
+    fn test_select_by_id() {
+        let mut app = create_test_app();
+        let subtask = Item::new("Subtask".to_string(), Duration::minutes(30), ScheduleDay::Today);
+        let subtask_id = subtask.id;
+        app.tasks[0].add_subtask(subtask);
+        app.tasks[0].expanded = false;
+
+        let task2_id = app.tasks[1].id;
+        assert!(app.select_by_id(task2_id));
+        assert_eq!(app.selected_path(), Some(vec![1]));
+
+        // Selecting a subtask of a collapsed task expands it
+        assert!(app.select_by_id(subtask_id));
+        assert!(app.tasks[0].expanded);
+        assert_eq!(app.selected_path(), Some(vec![0, 0]));
+
+        assert!(!app.select_by_id(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_away_gap_detected_and_discarded() {
+        let mut app = create_test_app();
+        let start = chrono::Local::now() - Duration::hours(2);
+        app.tasks[0].state_history[0].timestamp = start;
+        app.tasks[0].start();
+        app.tasks[0].state_history[1].timestamp = start;
+        app.mode_timeline = ModeTimeline::default();
+        app.mode_timeline.begin(app.global_mode.clone(), start);
+
+        // Wall clock moved an hour further than the monotonic clock (suspend)
+        app.last_tick_wall -= Duration::hours(1);
+        app.tick();
+        assert_eq!(app.ui_mode, UiMode::AwayCheck);
+        let away = app.away_gap.unwrap().duration().num_seconds();
+        assert!((away - 3600).abs() < 5, "expected ~1h away, got {}s", away);
+
+        app.resolve_away(AwayChoice::Discard);
+        assert_eq!(app.ui_mode, UiMode::Normal);
+        assert!(app.away_gap.is_none());
+        assert_eq!(app.tasks[0].status, RunStatus::Running);
+        assert!((app.tasks[0].track.elapsed.num_seconds() - 3600).abs() < 5);
+        assert_eq!(app.mode_timeline.segments.len(), 2);
+        assert!((app.get_mode_times()[0].1.num_seconds() - 3600).abs() < 5);
+    }
+
+    #[test]
+    fn test_resolve_crash_gap() {
+        let mut app = create_test_app();
+        let now = chrono::Local::now();
+        let heartbeat = now - Duration::hours(2);
+        for (idx, task) in app.tasks.iter_mut().enumerate() {
+            task.state_history[0].timestamp = now - Duration::hours(4);
+            task.start();
+            task.state_history[1].timestamp = now - Duration::hours(3);
+            if idx == 1 {
+                // Started after the crashed session's last heartbeat (e.g. by the CLI)
+                task.state_history[1].timestamp = now - Duration::hours(1);
+            }
+            task.coerce_running_to_paused();
+        }
+
+        // A heartbeat this recent belongs to a session that is still running
+        app.check_crash_gap(Some(heartbeat), heartbeat + Duration::seconds(40));
+        assert!(app.crash_gap.is_none());
+        app.check_crash_gap(Some(heartbeat), now);
+        assert_eq!(app.ui_mode, UiMode::CrashRecovery);
+
+        app.crash_gap.as_mut().unwrap().time_input = Some("99:99".to_string());
+        assert_eq!(app.crash_gap_input_time(), None);
+
+        app.resolve_crash_gap(CrashGapChoice::Heartbeat);
+        assert!(app.crash_gap.is_none());
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 60);
+        assert!(!app.tasks[0].has_open_interval(now));
+        // Not part of the crashed session, so left alone
+        assert!(app.tasks[1].has_open_interval(now));
+    }
+
+    #[test]
+    fn test_history_editor() {
+        let mut app = create_test_app();
+        app.file_date = app.file_date.pred_opt().unwrap();
+        app.open_history_editor();
+        assert_eq!(app.ui_mode, UiMode::EditingHistory);
+
+        app.start_history_input(HistoryInput::Add);
+        app.history_editor.as_mut().unwrap().input.as_mut().unwrap().1 = "09:00-10:30".to_string();
+        app.submit_history_input();
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 90);
+        assert!(app.needs_save);
+
+        // Overlaps the session just added
+        app.start_history_input(HistoryInput::Add);
+        app.history_editor.as_mut().unwrap().input.as_mut().unwrap().1 = "10:00-11:00".to_string();
+        app.submit_history_input();
+        assert!(app.history_editor.as_ref().unwrap().error.is_some());
+        assert_eq!(app.tasks[0].sessions().len(), 1);
+
+        app.start_history_input(HistoryInput::Split);
+        app.history_editor.as_mut().unwrap().input.as_mut().unwrap().1 = "09:30-10:00".to_string();
+        app.submit_history_input();
+        assert_eq!(app.tasks[0].sessions().len(), 2);
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 60);
+
+        app.shift_history_session(false);
+        assert_eq!(app.tasks[0].sessions()[1].start.format("%H:%M").to_string(), "09:55");
+
+        app.delete_history_session();
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 30);
+
+        app.close_history_editor();
+        assert_eq!(app.ui_mode, UiMode::Normal);
+    }
+
+    #[test]
+    fn test_merge_journal() {
+        assert_eq!(merge_journal("a\nb\n", "a\nb\n"), "a\nb\n");
+        assert_eq!(merge_journal("a\nmine\n", "a\ntheirs\n"), "a\nmine\ntheirs\n");
+        assert_eq!(merge_journal("", "theirs\n"), "theirs\n");
+    }
+
+    #[test]
+    fn test_resolve_external_change_reload_keeps_selection() {
+        use crate::persistence::serializer::serialize_daily_file_with_date;
+
+        let mut app = create_test_app();
+        let task2 = app.tasks[1].clone();
+        app.select_by_id(task2.id);
+
+        // On disk, a new task was added in front of Task 2 and Task 1 was removed
+        let added = Item::new("Added elsewhere".to_string(), Duration::hours(1), ScheduleDay::Today);
+        let disk = serialize_daily_file_with_date(&[added, task2.clone()], &[], &[], app.file_date);
+
+        app.external_change = Some(ExternalChange::Tasks(Some(disk.clone())));
+        app.ui_mode = UiMode::ExternalChange;
+        app.needs_save = true;
+        app.resolve_external_change(ExternalChangeChoice::Reload).unwrap();
+
+        assert_eq!(app.tasks.len(), 2);
+        assert_eq!(app.tasks[0].title, "Added elsewhere");
+        assert_eq!(app.selected_path(), Some(vec![1]));
+        assert_eq!(app.daily_base, Some(disk));
+        assert!(!app.needs_save);
+        assert_eq!(app.ui_mode, UiMode::Normal);
+    }
+
+    #[test]
+    fn test_partial_external_edit_is_not_reloaded() {
+        use crate::persistence::serializer::serialize_daily_file_with_date;
+
+        let app = create_test_app();
+        let disk = serialize_daily_file_with_date(&app.tasks, &[], &[], app.file_date);
+        assert_eq!(AppState::parse_daily_content_cleanly(Some(&disk)).unwrap().0.len(), 2);
+        assert!(AppState::parse_daily_content_cleanly(None).is_some());
+
+        // Half-way through an edit, Task 2's status line is broken
+        let half_written = disk.replace("- [IDLE] Task 2", "- [ID");
+        assert!(AppState::parse_daily_content_cleanly(Some(&half_written)).is_none());
+    }
+
+    #[test]
+    fn test_resolve_external_change_merge() {
+        use crate::persistence::serializer::serialize_daily_file_with_date;
+
+        let mut app = create_test_app();
+        app.daily_base = Some(serialize_daily_file_with_date(&app.tasks, &[], &[], app.file_date));
+
+        // Locally: a new task. On disk: Task 1 was marked done.
+        app.add_task("Local".to_string(), Duration::hours(1));
+        let mut done = app.tasks[0].clone();
+        done.mark_done();
+        let disk = serialize_daily_file_with_date(&[app.tasks[1].clone()], &[done], &[], app.file_date);
+
+        app.external_change = Some(ExternalChange::Tasks(Some(disk)));
+        app.resolve_external_change(ExternalChangeChoice::Merge).unwrap();
+
+        let titles: Vec<&str> = app.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Task 2", "Local"]);
+        assert_eq!(app.done_today.len(), 1);
+        assert!(app.needs_save);
+    }
+
     #[test]
     fn test_add_task() {
         let mut app = create_test_app();
@@ -1756,6 +3003,181 @@ mod tests {
         assert!(app.needs_save);
     }
 
+    #[test]
+    fn test_promote_demote_and_move_between_parents() {
+        let mut app = create_test_app();
+        let task2_id = app.tasks[1].id;
+        app.selected_index = 1;
+        app.toggle_run_pause();
+
+        // Task 2 goes under Task 1, still running, and takes Task 1's timer along
+        app.demote_item();
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].subtasks[0].id, task2_id);
+        assert_eq!(app.tasks[0].subtasks[0].status, RunStatus::Running);
+        assert_eq!(app.tasks[0].status, RunStatus::Running);
+        assert_eq!(app.selected_path(), Some(vec![0, 0]));
+
+        // Shift+↓ at the bottom of Task 1's subtasks moves it on to Task 3
+        let task3 = Item::new("Task 3".to_string(), Duration::hours(1), ScheduleDay::Today);
+        app.tasks.push(task3);
+        app.move_item_down();
+        assert!(app.tasks[0].subtasks.is_empty());
+        assert_eq!(app.tasks[0].status, RunStatus::Paused);
+        assert_eq!(app.tasks[1].subtasks[0].id, task2_id);
+        assert_eq!(app.tasks[1].status, RunStatus::Running);
+        assert_eq!(app.selected_path(), Some(vec![1, 0]));
+
+        // Back to the top level, right after its parent
+        app.promote_item();
+        assert_eq!(app.tasks.len(), 3);
+        assert_eq!(app.tasks[2].id, task2_id);
+        assert_eq!(app.selected_path(), Some(vec![2]));
+        assert!(app.tasks[2].state_history.len() > 1);
+
+        // A top-level task can't be promoted further
+        app.promote_item();
+        assert_eq!(app.undo_stack.len(), 4);
+
+        app.undo().unwrap();
+        app.undo().unwrap();
+        app.undo().unwrap();
+        assert_eq!(app.tasks[1].id, task2_id);
+        assert!(app.tasks[0].subtasks.is_empty());
+    }
+
+    #[test]
+    fn test_failed_rollover_is_not_retried_at_once() {
+        let mut app = create_test_app();
+
+        // The old day was closed out before the rest of the rollover failed,
+        // so autosave leaves it alone
+        app.closed_day = Some(app.file_date);
+        app.needs_save = true;
+        app.save().unwrap();
+        assert!(app.needs_save);
+
+        app.rollover_retry_at = Some(Instant::now() + std::time::Duration::from_secs(60));
+        assert!(!app.try_rollover_day());
+        assert_eq!(app.closed_day, Some(app.file_date));
+    }
+
+    #[test]
+    fn test_history_times_follow_the_day_start() {
+        let mut app = create_test_app();
+        app.file_date = chrono::NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+
+        let (_, end) = app.parse_history_times("00:30-01:30").unwrap();
+        assert_eq!(end.date_naive(), app.file_date);
+
+        // With days starting at 03:00, 01:30 is after midnight at the end of the day
+        app.config.day.starts_at = "03:00".to_string();
+        let (start, end) = app.parse_history_times("23:30-01:30").unwrap();
+        assert_eq!(start.date_naive(), app.file_date);
+        assert_eq!(end.date_naive(), app.file_date.succ_opt().unwrap());
+    }
+
+    #[test]
+    fn test_reload_error_is_reported_once() {
+        let mut app = create_test_app();
+
+        app.report_reload_error(&anyhow::anyhow!("permission denied"));
+        assert_eq!(app.status_message.as_deref(), Some("Couldn't reload today's files: permission denied"));
+
+        // Dismissed with a key press, the same failure stays quiet
+        app.status_message = None;
+        app.report_reload_error(&anyhow::anyhow!("permission denied"));
+        assert_eq!(app.status_message, None);
+
+        // A different failure is shown again
+        app.report_reload_error(&anyhow::anyhow!("not valid UTF-8"));
+        assert_eq!(app.status_message.as_deref(), Some("Couldn't reload today's files: not valid UTF-8"));
+    }
+
+    #[test]
+    fn test_blocked_task_waits_for_its_blocker() {
+        let mut app = create_test_app();
+        let task1_id = app.tasks[0].id;
+
+        // Task 2 waits on Task 1
+        app.selected_index = 1;
+        app.open_blocker_picker();
+        assert_eq!(app.ui_mode, UiMode::PickingBlockers);
+        let (_, candidates) = app.blocker_candidates().unwrap();
+        assert_eq!(candidates.len(), 1);
+        app.toggle_selected_blocker();
+        app.close_blocker_picker();
+        assert_eq!(app.tasks[1].blocked_by, vec![task1_id]);
+
+        // ...so it can't start, and says why
+        app.toggle_run_pause();
+        assert_eq!(app.tasks[1].status, RunStatus::Idle);
+        assert_eq!(app.status_message.as_deref(), Some("Can't start \"Task 2\": blocked by \"Task 1\""));
+
+        // Task 1 can't wait on Task 2 in turn
+        app.selected_index = 0;
+        app.open_blocker_picker();
+        app.toggle_selected_blocker();
+        app.close_blocker_picker();
+        assert!(app.tasks[0].blocked_by.is_empty());
+
+        // Finishing Task 1 unblocks Task 2
+        app.mark_done().unwrap();
+        assert!(app.tasks[0].blocked_by.is_empty());
+        app.toggle_run_pause();
+        assert_eq!(app.tasks[0].status, RunStatus::Running);
+    }
+
+    #[test]
+    fn test_waiting_task_and_follow_up() {
+        let mut app = create_test_app();
+
+        // Task 1 waits on a review until tomorrow
+        app.toggle_waiting();
+        assert_eq!(app.ui_mode, UiMode::SettingWaiting);
+        let form = app.waiting_form.as_mut().unwrap();
+        form.reason = "Review from Sam".to_string();
+        form.follow_up = "+1".to_string();
+        app.submit_waiting_form();
+        assert_eq!(app.ui_mode, UiMode::Normal);
+        assert_eq!(app.tasks[0].status, RunStatus::Waiting);
+        assert_eq!(app.tasks[0].waiting_reason, "Review from Sam");
+        assert_eq!(app.tasks[0].follow_up, Some(app.file_date + Duration::days(1)));
+
+        // Waiting tasks are listed last
+        let rows = flatten_tasks(&app.tasks);
+        assert_eq!(rows[0].path[0], 1);
+        assert_eq!(rows.last().unwrap().path[0], 0);
+
+        // ...and Shift+↑/↓ moves through the list as shown, not past them
+        app.tasks.push(Item::new("Task 3".to_string(), Duration::hours(1), ScheduleDay::Today));
+        app.selected_index = 0;
+        app.move_item_down();
+        let titles: Vec<&str> = flatten_tasks(&app.tasks).iter().map(|row| app.tasks[row.path[0]].title.as_str()).collect();
+        assert_eq!(titles, ["Task 3", "Task 2", "Task 1"]);
+        assert_eq!(app.selected_index, 1);
+        app.move_item_down();
+        assert_eq!(app.tasks[2].title, "Task 2");
+        assert_eq!(app.selected_index, 1);
+
+        // Nothing resurfaces before the follow-up date...
+        app.resurface_waiting();
+        assert_eq!(app.tasks[0].status, RunStatus::Waiting);
+
+        // ...and it comes back on it
+        app.file_date += Duration::days(1);
+        app.resurface_waiting();
+        assert_eq!(app.tasks[0].status, RunStatus::Idle);
+        assert!(app.tasks[0].follow_up.is_none());
+
+        // A bad date keeps the form open
+        app.toggle_waiting();
+        app.waiting_form.as_mut().unwrap().follow_up = "someday".to_string();
+        app.submit_waiting_form();
+        assert!(app.waiting_form.as_ref().unwrap().error.is_some());
+        assert_eq!(app.tasks[0].status, RunStatus::Idle);
+    }
+
     #[test]
     fn test_archive_task() {
         let mut app = create_test_app();
@@ -1956,20 +3378,117 @@ mod tests {
         assert_eq!(app.tasks[0].status, RunStatus::Paused);
 
         // Switch to Lunch mode
-        app.set_global_mode(GlobalMode::Lunch);
+        app.set_global_mode(GlobalMode::new("Lunch"));
 
         // Try to start task in Lunch mode - should NOT work
         app.toggle_run_pause();
         assert_eq!(app.tasks[0].status, RunStatus::Paused); // Should remain paused
 
         // Switch back to Working mode
-        app.set_global_mode(GlobalMode::Working);
+        app.set_global_mode(GlobalMode::new("Working"));
 
         // Now it should work
         app.toggle_run_pause();
         assert_eq!(app.tasks[0].status, RunStatus::Running);
     }
 
+    #[test]
+    fn test_idle_timeout_trims_to_last_input() {
+        let mut app = create_test_app();
+        let now = chrono::Local::now();
+        app.tasks[0].state_history[0].timestamp = now - Duration::hours(2);
+        app.tasks[0].start();
+        app.tasks[0].state_history[1].timestamp = now - Duration::hours(1);
+        // Task 2 was started (without a keypress) after the last one
+        app.tasks[1].state_history[0].timestamp = now - Duration::hours(2);
+        app.tasks[1].start();
+        app.tasks[1].state_history[1].timestamp = now - Duration::minutes(10);
+        app.last_input = now - Duration::minutes(40);
+
+        // The idle check went unanswered
+        app.ui_mode = UiMode::IdleCheck;
+        app.idle_check_deadline = Some(Instant::now());
+        app.check_idle_time();
+        assert_eq!(app.ui_mode, UiMode::IdleTrimmed);
+        assert_eq!(app.tasks[0].status, RunStatus::Paused);
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 20);
+        assert_eq!(app.tasks[1].status, RunStatus::Paused);
+        assert_eq!(app.tasks[1].track.elapsed.num_minutes(), 0);
+        let trim = app.idle_trim.clone().unwrap();
+        assert_eq!(trim.trimmed_since(trim.items[0].1).num_minutes(), 40);
+        assert_eq!(trim.trimmed_since(trim.items[1].1).num_minutes(), 10);
+
+        // "I was working": each task gets back what it lost, and stays paused
+        app.resolve_idle_trim(true);
+        assert_eq!(app.ui_mode, UiMode::Normal);
+        assert_eq!(app.tasks[0].status, RunStatus::Paused);
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 60);
+        assert_eq!(app.tasks[1].status, RunStatus::Paused);
+        assert_eq!(app.tasks[1].track.elapsed.num_minutes(), 10);
+    }
+
+    #[test]
+    fn test_backdated_mode_switch() {
+        let mut app = create_test_app();
+        let now = chrono::Local::now();
+        app.mode_timeline = ModeTimeline::default();
+        app.mode_timeline.begin(GlobalMode::new("Working"), now - Duration::hours(2));
+        app.tasks[0].state_history[0].timestamp = now - Duration::hours(3);
+        app.tasks[0].start();
+        app.tasks[0].state_history[1].timestamp = now - Duration::hours(2);
+
+        // Went to lunch an hour ago but forgot to say so
+        app.set_global_mode_since(GlobalMode::new("Lunch"), Some(now - Duration::hours(1)));
+        assert_eq!(app.tasks[0].status, RunStatus::Paused);
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 60);
+        let lunch = app.get_mode_times().into_iter().find(|(mode, _)| mode.name() == "Lunch").unwrap();
+        assert_eq!(lunch.1.num_minutes(), 60);
+
+        // ...and came back half an hour ago
+        app.set_global_mode_since(GlobalMode::new("Working"), Some(now - Duration::minutes(30)));
+        assert_eq!(app.tasks[0].status, RunStatus::Running);
+        assert_eq!(app.tasks[0].track.elapsed.num_minutes(), 90);
+        let times = app.get_mode_times();
+        assert_eq!(times.iter().find(|(mode, _)| mode.name() == "Lunch").unwrap().1.num_minutes(), 30);
+        assert_eq!(times.iter().find(|(mode, _)| mode.name() == "Working").unwrap().1.num_minutes(), 90);
+    }
+
+    #[test]
+    fn test_undo_redo_any_change() {
+        let mut app = create_test_app();
+        let estimate = app.tasks[0].track.estimate;
+
+        app.increase_estimate();
+        app.move_item_down();
+        app.toggle_run_pause();
+        assert_eq!(app.tasks[1].status, RunStatus::Running);
+        assert_eq!(app.undo_stack.len(), 3);
+
+        // Nothing changed, nothing recorded
+        app.undo_log_dirty = false;
+        app.move_item_down();
+        assert_eq!(app.undo_stack.len(), 3);
+        assert!(!app.undo_log_dirty);
+
+        app.undo().unwrap();
+        assert_eq!(app.tasks[1].status, RunStatus::Idle);
+        app.undo().unwrap();
+        assert_eq!(app.tasks[0].title, "Task 1");
+        assert_eq!(app.tasks[0].track.estimate, estimate + app.estimate_step);
+        app.undo().unwrap();
+        assert_eq!(app.tasks[0].track.estimate, estimate);
+        assert_eq!(app.redo_stack.len(), 3);
+
+        app.redo().unwrap();
+        assert_eq!(app.tasks[0].track.estimate, estimate + app.estimate_step);
+
+        // A new change drops what's left to redo
+        app.set_global_mode(GlobalMode::new("Lunch"));
+        assert!(app.redo_stack.is_empty());
+        app.undo().unwrap();
+        assert_eq!(app.global_mode, GlobalMode::new("Working"));
+    }
+
     #[test]
     fn test_undo_delete() {
         let mut app = create_test_app();
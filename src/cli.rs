@@ -0,0 +1,313 @@
+use crate::app::AppState;
+use crate::config::load_config;
+use crate::day::parse_follow_up;
+use crate::domain::{active_blockers, find_item_mut, parse_duration, walk_items, Item, RunStatus, ScheduleDay};
+use crate::persistence::{ensure_centre_dir, load_and_migrate_with};
+use crate::report::export::TaskRow;
+use anyhow::{anyhow, bail, Result};
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// Load today's tasks for a CLI command (running timers keep running)
+fn load_app() -> Result<AppState> {
+    ensure_centre_dir()?;
+    let config = load_config()?;
+    let (tasks, done_today, archived_today) = load_and_migrate_with(&config, true)?;
+    Ok(AppState::new(tasks, done_today, archived_today, String::new(), config))
+}
+
+/// Resolve a full ID or unique ID prefix among active tasks and subtasks (at any depth)
+fn resolve_id(app: &AppState, id: &str) -> Result<Uuid> {
+    let needle = id.trim().to_lowercase();
+    if needle.is_empty() {
+        bail!("Empty task ID");
+    }
+
+    let matches: Vec<Uuid> = walk_items(&app.tasks)
+        .map(|item| item.id)
+        .filter(|item_id| item_id.to_string().starts_with(&needle))
+        .collect();
+
+    match matches.as_slice() {
+        [id] => Ok(*id),
+        [] => Err(anyhow!("No active task matches ID '{}'", id)),
+        _ => Err(anyhow!("ID '{}' is ambiguous ({} matches)", id, matches.len())),
+    }
+}
+
+/// Select the item with the given ID, failing if it isn't active
+fn select(app: &mut AppState, id: &str) -> Result<Uuid> {
+    let id = resolve_id(app, id)?;
+    if !app.select_by_id(id) {
+        bail!("Task {} is not in today's active list", id);
+    }
+    Ok(id)
+}
+
+fn selected_title(app: &AppState) -> String {
+    app.selected_item().map(|item| item.title.clone()).unwrap_or_default()
+}
+
+fn short_id(id: Uuid) -> String {
+    id.to_string()[..8].to_string()
+}
+
+/// `centre add "title" [--est 1.5h] [--tags a,b] [--parent <id>]`
+pub fn add(
+    title: String,
+    est: Option<String>,
+    tags: Option<String>,
+    parent: Option<String>,
+    blocked_by: Option<String>,
+) -> Result<()> {
+    let mut app = load_app()?;
+
+    if title.trim().is_empty() {
+        bail!("Task title cannot be empty");
+    }
+
+    let estimate = match est {
+        Some(est) => parse_duration(&est)?,
+        None => app.config.default_estimate(),
+    };
+
+    let mut item = Item::new(title.trim().to_string(), estimate, ScheduleDay::Today);
+    item.tags = tags
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    for blocker in blocked_by.unwrap_or_default().split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        item.blocked_by.push(resolve_id(&app, blocker)?);
+    }
+    let new_id = item.id;
+
+    if let Some(parent) = parent {
+        let parent_id = resolve_id(&app, &parent)?;
+        let parent_task = find_item_mut(&mut app.tasks, parent_id)
+            .ok_or_else(|| anyhow!("Task {} is not in today's active list", parent_id))?;
+        parent_task.add_subtask(item);
+    } else {
+        app.tasks.push(item);
+    }
+
+    app.save_tasks()?;
+    println!("{}", new_id);
+    Ok(())
+}
+
+/// `centre start <id>`
+pub fn start(id: String) -> Result<()> {
+    let mut app = load_app()?;
+    select(&mut app, &id)?;
+
+    let status = app.get_selected_item_mut().map(|item| item.status);
+    if status == Some(RunStatus::Running) {
+        println!("Already running: {}", selected_title(&app));
+        return Ok(());
+    }
+
+    let path = app.selected_path().unwrap_or_default();
+    let blockers: Vec<String> = active_blockers(&app.tasks, &path)
+        .iter()
+        .map(|blocker| format!("{} ({})", blocker.title, short_id(blocker.id)))
+        .collect();
+    if !blockers.is_empty() {
+        bail!("{} is blocked by: {}", selected_title(&app), blockers.join(", "));
+    }
+
+    app.toggle_run_pause();
+    if app.get_selected_item_mut().map(|item| item.status) != Some(RunStatus::Running) {
+        bail!(
+            "Cannot start tasks in {} mode; switch back to Working first",
+            app.global_mode.name()
+        );
+    }
+
+    app.save_tasks()?;
+    println!("Started: {}", selected_title(&app));
+    Ok(())
+}
+
+/// `centre pause [<id>]` - pause one task, or everything that is running
+pub fn pause(id: Option<String>) -> Result<()> {
+    let mut app = load_app()?;
+
+    match id {
+        Some(id) => {
+            select(&mut app, &id)?;
+            if app.get_selected_item_mut().map(|item| item.status) != Some(RunStatus::Running) {
+                println!("Not running: {}", selected_title(&app));
+                return Ok(());
+            }
+            app.toggle_run_pause();
+            println!("Paused: {}", selected_title(&app));
+        }
+        None => {
+            let running = walk_items(&app.tasks)
+                .filter(|item| item.status == RunStatus::Running)
+                .count();
+            app.auto_pause_all();
+            println!("Paused {} running task(s)", running);
+        }
+    }
+
+    app.save_tasks()?;
+    Ok(())
+}
+
+/// `centre done <id>`
+pub fn done(id: String) -> Result<()> {
+    let mut app = load_app()?;
+    select(&mut app, &id)?;
+    let title = selected_title(&app);
+
+    app.mark_done()?;
+    app.save_tasks()?;
+    println!("Done: {}", title);
+    Ok(())
+}
+
+/// `centre postpone <id>`
+pub fn postpone(id: String) -> Result<()> {
+    let mut app = load_app()?;
+    select(&mut app, &id)?;
+    let title = selected_title(&app);
+
+    app.postpone_to_tomorrow()?;
+    app.save_tasks()?;
+    println!("Postponed to tomorrow: {}", title);
+    Ok(())
+}
+
+/// `centre wait <id> [--reason <text>] [--follow-up <date>]`
+pub fn wait(id: String, reason: Option<String>, follow_up: Option<String>) -> Result<()> {
+    let mut app = load_app()?;
+    let item_id = select(&mut app, &id)?;
+    let follow_up = parse_follow_up(follow_up.as_deref().unwrap_or_default(), app.file_date)?;
+
+    app.set_waiting(item_id, reason.unwrap_or_default().trim().to_string(), follow_up);
+    app.save_tasks()?;
+    match follow_up {
+        Some(date) => println!("Waiting: {} (follow up {})", selected_title(&app), date),
+        None => println!("Waiting: {}", selected_title(&app)),
+    }
+    Ok(())
+}
+
+/// `centre rebuild [--date YYYY-MM-DD]`: regenerate a daily file from its event log
+pub fn rebuild(date: NaiveDate) -> Result<()> {
+    use crate::persistence::files::backup_file;
+    use crate::persistence::{
+        atomic_write, daily_file, events_file_for_date, load_events, lock_centre_dir, replay,
+        serialize_daily_file_with_date,
+    };
+
+    ensure_centre_dir()?;
+    let _lock = lock_centre_dir()?;
+
+    let events = load_events(events_file_for_date(date)?)?;
+    if events.is_empty() {
+        bail!("No events logged for {}", date);
+    }
+    let day = replay(&events)?;
+    let (active, done, archived) = &day.items;
+
+    let path = daily_file(date)?;
+    if path.exists() {
+        let backup = backup_file(&path)?;
+        println!("Previous file saved as {}", backup.display());
+    }
+    atomic_write(&path, &serialize_daily_file_with_date(active, done, archived, date))?;
+
+    println!(
+        "Rebuilt {} from {} events ({} active, {} done, {} archived)",
+        path.display(),
+        events.len(),
+        active.len(),
+        done.len(),
+        archived.len()
+    );
+    Ok(())
+}
+
+/// `centre list [--json] [--all]`
+pub fn list(json: bool, all: bool) -> Result<()> {
+    let app = load_app()?;
+
+    let mut sections: Vec<(&'static str, &[Item])> = vec![("active", &app.tasks)];
+    if all {
+        sections.push(("done", &app.done_today));
+        sections.push(("archived", &app.archived_today));
+    }
+
+    if json {
+        let mut rows = Vec::new();
+        for (section, items) in &sections {
+            for item in items.iter() {
+                rows.extend(TaskRow::tree_rows(item, None, section));
+            }
+        }
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    for (section, items) in &sections {
+        if all {
+            println!("## {}", section.to_uppercase());
+        }
+        for item in items.iter() {
+            print_tree(item, 0);
+        }
+    }
+    Ok(())
+}
+
+fn print_tree(item: &Item, depth: usize) {
+    println!("{}", format_list_line(item, depth));
+    for subtask in &item.subtasks {
+        print_tree(subtask, depth + 1);
+    }
+}
+
+fn format_list_line(item: &Item, depth: usize) -> String {
+    let tags = if item.tags.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " {}",
+            item.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
+        )
+    };
+    format!(
+        "{}{}  [{}] {} ({} / {}){}",
+        "  ".repeat(depth),
+        short_id(item.id),
+        item.status.to_tag(),
+        item.title,
+        item.track.elapsed_formatted(),
+        item.track.estimate_formatted(),
+        tags
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use chrono::Duration;
+
+    #[test]
+    fn test_resolve_id_prefix() {
+        let mut app = AppState::new(Vec::new(), Vec::new(), Vec::new(), String::new(), Config::default());
+        app.add_task("One".to_string(), Duration::hours(1));
+        app.add_task("Two".to_string(), Duration::hours(1));
+        let id = app.tasks[1].id;
+
+        assert_eq!(resolve_id(&app, &id.to_string()).unwrap(), id);
+        assert_eq!(resolve_id(&app, &id.to_string()[..8].to_uppercase()).unwrap(), id);
+        assert!(resolve_id(&app, "").is_err());
+        assert!(resolve_id(&app, "zzzz").is_err());
+    }
+}
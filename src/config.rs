@@ -0,0 +1,365 @@
+//! User configuration (`config.toml`)
+//!
+//! The global `~/.centre/config.toml` is read first, then a local
+//! `.centre/config.toml` (if one is in use) overrides it key by key.
+//! Every key is optional; missing keys use the defaults below.
+
+use crate::domain::ModeSet;
+use crate::notifications::NotifierBackend;
+use crate::persistence::files::{global_centre_dir, local_centre_dir};
+use anyhow::{bail, Context, Result};
+use chrono::{NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the configuration file inside a centre directory
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Typed configuration, loaded once at startup
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub timer: TimerConfig,
+    pub idle: IdleConfig,
+    pub estimates: EstimateConfig,
+    pub planner: PlannerConfig,
+    pub notifications: NotificationConfig,
+    pub day: DayConfig,
+    /// `[[modes]]` - context modes (replaces the built-in list when given)
+    pub modes: ModeSet,
+}
+
+/// `[timer]` - main loop timing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimerConfig {
+    /// Tick interval of the TUI loop in milliseconds
+    pub tick_ms: u64,
+}
+
+/// `[idle]` - "are you still working?" check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdleConfig {
+    /// Minutes of running tasks before asking (0 disables the check)
+    pub check_minutes: u64,
+    /// Minutes to answer before all running tasks are paused
+    pub auto_pause_minutes: u64,
+    /// Minutes of system suspend (or wall-clock jump) before asking what to
+    /// do with the time (0 disables the check)
+    pub away_minutes: u64,
+}
+
+/// `[estimates]` - estimate defaults and adjustments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EstimateConfig {
+    /// Estimate for new tasks, in hours
+    pub default_hours: f64,
+    /// Minutes added/removed by `+` / `-`
+    pub step_minutes: i64,
+    /// Minutes added by "Extend" when an estimate is reached
+    pub extend_minutes: i64,
+}
+
+/// `[planner]` - daily planner layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlannerConfig {
+    /// First hour shown in the planner
+    pub start_hour: u32,
+    /// Hour the planner ends at (24 = midnight)
+    pub end_hour: u32,
+}
+
+/// `[notifications]` - how notifications are delivered
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationConfig {
+    /// "desktop", "command", "bell" or "none"
+    pub backend: NotifierBackend,
+    /// Shell command run by the "command" backend
+    pub command: Option<String>,
+}
+
+/// `[day]` - when a new day starts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DayConfig {
+    /// Time the day starts at ("HH:MM"); earlier work counts toward the previous day
+    pub starts_at: String,
+}
+
+impl Default for TimerConfig {
+    fn default() -> Self {
+        Self {
+            tick_ms: crate::ticker::DEFAULT_TICK_MS,
+        }
+    }
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            check_minutes: 30,
+            auto_pause_minutes: 30,
+            away_minutes: 5,
+        }
+    }
+}
+
+impl Default for EstimateConfig {
+    fn default() -> Self {
+        Self {
+            default_hours: 1.0,
+            step_minutes: 15,
+            extend_minutes: 30,
+        }
+    }
+}
+
+impl Default for DayConfig {
+    fn default() -> Self {
+        Self {
+            starts_at: "00:00".to_string(),
+        }
+    }
+}
+
+impl Default for PlannerConfig {
+    fn default() -> Self {
+        Self {
+            start_hour: 9,
+            end_hour: 24,
+        }
+    }
+}
+
+impl Config {
+    /// Tick interval of the TUI loop
+    pub fn tick_duration(&self) -> std::time::Duration {
+        crate::ticker::tick_duration(self.timer.tick_ms)
+    }
+
+    /// Time between idle checks, or None if the check is disabled
+    pub fn idle_check_interval(&self) -> Option<std::time::Duration> {
+        match self.idle.check_minutes {
+            0 => None,
+            minutes => Some(std::time::Duration::from_secs(minutes * 60)),
+        }
+    }
+
+    /// Time the idle check waits for an answer before auto-pausing
+    pub fn idle_auto_pause(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.idle.auto_pause_minutes * 60)
+    }
+
+    /// Time lost to suspend or a clock jump before asking about it, or None
+    /// if the check is disabled
+    pub fn away_threshold(&self) -> Option<chrono::Duration> {
+        match self.idle.away_minutes {
+            0 => None,
+            minutes => Some(chrono::Duration::minutes(minutes as i64)),
+        }
+    }
+
+    /// Estimate for new tasks
+    pub fn default_estimate(&self) -> chrono::Duration {
+        chrono::Duration::seconds((self.estimates.default_hours * 3600.0) as i64)
+    }
+
+    /// Step for increasing/decreasing estimates
+    pub fn estimate_step(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.estimates.step_minutes)
+    }
+
+    /// Extra time given by "Extend" in the estimate-reached prompt
+    pub fn extend_step(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.estimates.extend_minutes)
+    }
+
+    /// Time at which a new day starts (midnight if `day.starts_at` is invalid)
+    pub fn day_start(&self) -> NaiveTime {
+        NaiveTime::parse_from_str(&self.day.starts_at, "%H:%M").unwrap_or(NaiveTime::MIN)
+    }
+
+    /// Reject values the rest of the app can't work with
+    fn validate(&self) -> Result<()> {
+        if self.timer.tick_ms == 0 {
+            bail!("timer.tick_ms must be greater than 0");
+        }
+        if self.idle.auto_pause_minutes == 0 {
+            bail!("idle.auto_pause_minutes must be greater than 0");
+        }
+        if self.estimates.default_hours.is_nan() || self.estimates.default_hours <= 0.0 {
+            bail!("estimates.default_hours must be greater than 0");
+        }
+        if self.estimates.step_minutes <= 0 {
+            bail!("estimates.step_minutes must be greater than 0");
+        }
+        if self.estimates.extend_minutes <= 0 {
+            bail!("estimates.extend_minutes must be greater than 0");
+        }
+        if self.notifications.backend == NotifierBackend::Command
+            && self.notifications.command.as_deref().unwrap_or("").trim().is_empty()
+        {
+            bail!("notifications.command is required when notifications.backend = \"command\"");
+        }
+        let day_start = NaiveTime::parse_from_str(&self.day.starts_at, "%H:%M")
+            .with_context(|| format!("day.starts_at must be a time like \"03:00\", not \"{}\"", self.day.starts_at))?;
+        if self.planner.start_hour >= self.planner.end_hour {
+            bail!("planner.start_hour must be before planner.end_hour");
+        }
+        // The planner covers one day, which may run past midnight (e.g. 9 to 26 for a 02:00 start)
+        let day_start_minutes = day_start.hour() * 60 + day_start.minute();
+        if self.planner.start_hour * 60 < day_start_minutes || self.planner.end_hour * 60 > 24 * 60 + day_start_minutes {
+            bail!("planner hours must lie within one day (from day.starts_at to 24 hours later)");
+        }
+        Ok(())
+    }
+}
+
+/// Paths of the config files that apply, lowest priority first
+pub fn config_paths() -> Result<Vec<PathBuf>> {
+    let mut paths = vec![global_centre_dir()?.join(CONFIG_FILE)];
+    if let Some(local_dir) = local_centre_dir()? {
+        let local = local_dir.join(CONFIG_FILE);
+        if !paths.contains(&local) {
+            paths.push(local);
+        }
+    }
+    Ok(paths)
+}
+
+/// Load the global config with the local override applied
+pub fn load_config() -> Result<Config> {
+    load_config_from(&config_paths()?)
+}
+
+/// Load and merge config files in order; missing files are skipped
+pub fn load_config_from(paths: &[PathBuf]) -> Result<Config> {
+    let mut merged = toml::Table::new();
+    let mut loaded = Vec::new();
+    for path in paths {
+        if path.exists() {
+            merge_tables(&mut merged, read_table(path)?);
+            loaded.push(path.display().to_string());
+        }
+    }
+
+    let invalid = || format!("Invalid configuration in {}", loaded.join(", "));
+    let config: Config = toml::Value::Table(merged).try_into().with_context(invalid)?;
+    config.validate().with_context(invalid)?;
+    Ok(config)
+}
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    content
+        .parse::<toml::Table>()
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Overlay `overrides` onto `base`, merging nested tables key by key
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(override_table)) => {
+                merge_tables(base_table, override_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_missing_files_give_defaults() {
+        let temp_dir = tempdir().unwrap();
+        let config = load_config_from(&[temp_dir.path().join(CONFIG_FILE)]).unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.default_estimate(), chrono::Duration::hours(1));
+        assert_eq!(config.tick_duration(), std::time::Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_local_overrides_global_per_key() {
+        let temp_dir = tempdir().unwrap();
+        let global = temp_dir.path().join("global.toml");
+        let local = temp_dir.path().join("local.toml");
+        std::fs::write(&global, "[estimates]\nstep_minutes = 10\nextend_minutes = 45\n\n[planner]\nstart_hour = 7\n").unwrap();
+        std::fs::write(&local, "[estimates]\nstep_minutes = 5\n").unwrap();
+
+        let config = load_config_from(&[global, local]).unwrap();
+        assert_eq!(config.estimates.step_minutes, 5);
+        assert_eq!(config.estimates.extend_minutes, 45);
+        assert_eq!(config.estimates.default_hours, 1.0);
+        assert_eq!(config.planner.start_hour, 7);
+        assert_eq!(config.planner.end_hour, 24);
+    }
+
+    #[test]
+    fn test_modes_from_config() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        std::fs::write(
+            &path,
+            r#"
+[[modes]]
+name = "Working"
+symbol = "💼"
+pause_timers = false
+
+[[modes]]
+name = "Meeting"
+symbol = "📅"
+phrase = "Listen first"
+key = "m"
+"#,
+        )
+        .unwrap();
+
+        let config = load_config_from(&[path]).unwrap();
+        let meeting = crate::domain::GlobalMode::new("Meeting");
+        assert_eq!(config.modes.all().len(), 2);
+        assert_eq!(config.modes.by_key('m'), Some(meeting.clone()));
+        assert!(config.modes.should_pause_timers(&meeting));
+        assert_eq!(config.modes.contextual_phrase(&meeting), "Listen first");
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+
+        std::fs::write(&path, "[planner]\nstart_hour = 20\nend_hour = 18\n").unwrap();
+        assert!(load_config_from(std::slice::from_ref(&path)).is_err());
+
+        std::fs::write(&path, "[idle]\ncheck_minute = 10\n").unwrap();
+        assert!(load_config_from(std::slice::from_ref(&path)).is_err());
+
+        std::fs::write(&path, "[notifications]\nbackend = \"command\"\n").unwrap();
+        assert!(load_config_from(std::slice::from_ref(&path)).is_err());
+
+        std::fs::write(&path, "[day]\nstarts_at = \"3am\"\n").unwrap();
+        assert!(load_config_from(std::slice::from_ref(&path)).is_err());
+
+        // Planner hours past midnight need a later day start
+        std::fs::write(&path, "[planner]\nend_hour = 26\n").unwrap();
+        assert!(load_config_from(std::slice::from_ref(&path)).is_err());
+        std::fs::write(&path, "[planner]\nend_hour = 26\n\n[day]\nstarts_at = \"03:00\"\n").unwrap();
+        let config = load_config_from(std::slice::from_ref(&path)).unwrap();
+        assert_eq!(config.day_start(), NaiveTime::from_hms_opt(3, 0, 0).unwrap());
+
+        std::fs::write(&path, "[idle]\ncheck_minutes = 0\n").unwrap();
+        let config = load_config_from(&[path]).unwrap();
+        assert_eq!(config.idle_check_interval(), None);
+    }
+}
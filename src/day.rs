@@ -0,0 +1,117 @@
+//! Day boundary
+//!
+//! Daily files, journals, mode timelines and reports are keyed by day. A day
+//! starts at midnight unless `[day] starts_at` in config.toml moves the
+//! boundary, e.g. to 03:00 so work until 3am still counts toward the
+//! previous day.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// The day a local date and time belongs to, for days starting at `start`
+pub fn date_for(at: NaiveDateTime, start: NaiveTime) -> NaiveDate {
+    (at - Duration::seconds(start.num_seconds_from_midnight() as i64)).date()
+}
+
+/// The day a point in time belongs to, for days starting at `start`
+pub fn day_of(at: DateTime<Local>, start: NaiveTime) -> NaiveDate {
+    date_for(at.naive_local(), start)
+}
+
+/// The current day, for days starting at `start` (see `Config::day_start`)
+pub fn today(start: NaiveTime) -> NaiveDate {
+    day_of(Local::now(), start)
+}
+
+/// Local time at which a day starts, for days starting at `start`
+pub fn start_of(date: NaiveDate, start: NaiveTime) -> DateTime<Local> {
+    date.and_time(start)
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(Local::now)
+}
+
+/// Local date and time of a time of day within a day starting at `start`
+/// (times before the day start fall on the next calendar date)
+pub fn time_on(date: NaiveDate, time: NaiveTime, start: NaiveTime) -> Option<DateTime<Local>> {
+    let date = if time < start { date.succ_opt()? } else { date };
+    date.and_time(time).and_local_timezone(Local).earliest()
+}
+
+/// Minutes since the start of the day for a time of day
+///
+/// Times before the day start belong to the end of the day, so with days
+/// starting at 03:00, 01:30 is 25:30.
+pub fn minutes_into_day(time: NaiveTime, start: NaiveTime) -> i64 {
+    let minutes = (time.hour() * 60 + time.minute()) as i64;
+    let start_minutes = (start.hour() * 60 + start.minute()) as i64;
+    if minutes < start_minutes {
+        minutes + 24 * 60
+    } else {
+        minutes
+    }
+}
+
+/// Parse a follow-up date typed relative to `today`: "YYYY-MM-DD",
+/// "tomorrow", or "+N" (N days from today); empty means no date
+pub fn parse_follow_up(text: &str, today: NaiveDate) -> Result<Option<NaiveDate>> {
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    if text == "tomorrow" {
+        return Ok(today.succ_opt());
+    }
+    if let Some(days) = text.strip_prefix('+') {
+        match days.trim_end_matches('d').parse::<i64>() {
+            Ok(days) if days >= 0 => return Ok(Some(today + Duration::days(days))),
+            _ => bail!("Invalid follow-up \"{}\": use +N for N days from today", text),
+        }
+    }
+    match NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+        Ok(date) => Ok(Some(date)),
+        Err(_) => bail!("Invalid follow-up \"{}\": use YYYY-MM-DD, +N or tomorrow", text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_date_for_day_boundary() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let midnight = NaiveTime::MIN;
+        let three_am = NaiveTime::from_hms_opt(3, 0, 0).unwrap();
+
+        assert_eq!(date_for(at("2025-03-15", "01:30"), midnight), day.succ_opt().unwrap());
+        assert_eq!(date_for(at("2025-03-15", "01:30"), three_am), day);
+        assert_eq!(date_for(at("2025-03-15", "03:00"), three_am), day.succ_opt().unwrap());
+        assert_eq!(date_for(at("2025-03-14", "23:59"), three_am), day);
+    }
+
+    #[test]
+    fn test_minutes_into_day() {
+        let three_am = NaiveTime::from_hms_opt(3, 0, 0).unwrap();
+        let half_past_one = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
+
+        assert_eq!(minutes_into_day(half_past_one, NaiveTime::MIN), 90);
+        assert_eq!(minutes_into_day(half_past_one, three_am), 25 * 60 + 30);
+        assert_eq!(minutes_into_day(three_am, three_am), 180);
+    }
+
+    #[test]
+    fn test_parse_follow_up() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+
+        assert_eq!(parse_follow_up("", day).unwrap(), None);
+        assert_eq!(parse_follow_up("+3", day).unwrap(), NaiveDate::from_ymd_opt(2025, 3, 17));
+        assert_eq!(parse_follow_up("tomorrow", day).unwrap(), NaiveDate::from_ymd_opt(2025, 3, 15));
+        assert_eq!(parse_follow_up("2025-04-01", day).unwrap(), NaiveDate::from_ymd_opt(2025, 4, 1));
+        assert!(parse_follow_up("next week", day).is_err());
+    }
+}
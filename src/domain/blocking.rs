@@ -0,0 +1,117 @@
+use super::enums::RunStatus;
+use super::item::Item;
+use super::tree::{item_at, walk_items, walk_items_mut};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Items the item at `path` (or one of its parents) is still waiting on.
+/// Blockers that are done, or no longer in the list (archived, postponed),
+/// don't count.
+pub fn active_blockers<'a>(items: &'a [Item], path: &[usize]) -> Vec<&'a Item> {
+    let waiting_on: HashSet<Uuid> = (1..=path.len())
+        .filter_map(|depth| item_at(items, &path[..depth]))
+        .flat_map(|item| item.blocked_by.iter().copied())
+        .collect();
+    walk_items(items)
+        .filter(|item| waiting_on.contains(&item.id) && item.status != RunStatus::Done)
+        .collect()
+}
+
+/// Whether `blocker` is waiting on `item`, directly or through other blockers
+/// (so making `item` wait on `blocker` would be a cycle)
+pub fn waits_on(items: &[Item], blocker: Uuid, item: Uuid) -> bool {
+    let mut seen = HashSet::new();
+    let mut pending = vec![blocker];
+    while let Some(id) = pending.pop() {
+        if id == item {
+            return true;
+        }
+        if !seen.insert(id) {
+            continue;
+        }
+        if let Some(waiting) = walk_items(items).find(|other| other.id == id) {
+            pending.extend(waiting.blocked_by.iter().copied());
+        }
+    }
+    false
+}
+
+/// Unblock everything that was waiting on `done` (or one of its subtasks)
+pub fn unblock(items: &mut [Item], done: &Item) {
+    let done_ids: HashSet<Uuid> = done.walk().iter().map(|item| item.id).collect();
+    walk_items_mut(items, &mut |item| item.blocked_by.retain(|id| !done_ids.contains(id)));
+}
+
+/// Order to work through a list in: list order, except that an item waiting
+/// on a sibling (or on something under it) comes after that sibling.
+/// Items caught in a cycle keep their list order.
+pub fn schedule_order(items: &[Item]) -> Vec<usize> {
+    let subtree_ids: Vec<HashSet<Uuid>> = items
+        .iter()
+        .map(|item| item.walk().iter().map(|inner| inner.id).collect())
+        .collect();
+    let needs: Vec<Vec<usize>> = items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let waiting_on: HashSet<Uuid> = item.walk().iter().flat_map(|inner| inner.blocked_by.iter().copied()).collect();
+            (0..items.len())
+                .filter(|other| *other != idx && subtree_ids[*other].iter().any(|id| waiting_on.contains(id)))
+                .collect()
+        })
+        .collect();
+
+    let mut order = Vec::with_capacity(items.len());
+    let mut scheduled = vec![false; items.len()];
+    while order.len() < items.len() {
+        let next = (0..items.len())
+            .find(|idx| !scheduled[*idx] && needs[*idx].iter().all(|other| scheduled[*other]))
+            .or_else(|| (0..items.len()).find(|idx| !scheduled[*idx]));
+        let Some(next) = next else {
+            break;
+        };
+        scheduled[next] = true;
+        order.push(next);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScheduleDay;
+    use chrono::Duration;
+
+    fn item(title: &str) -> Item {
+        Item::new(title.to_string(), Duration::hours(1), ScheduleDay::Today)
+    }
+
+    #[test]
+    fn test_blockers_and_schedule_order() {
+        // Deploy waits on Review, which waits on a step of Write
+        let (mut write, mut review, mut deploy, other) = (item("Write"), item("Review"), item("Deploy"), item("Other"));
+        let step = item("Step");
+        review.blocked_by = vec![step.id];
+        deploy.blocked_by = vec![review.id];
+        write.add_subtask(step.clone());
+        let mut items = vec![deploy.clone(), other.clone(), review.clone(), write.clone()];
+
+        assert_eq!(schedule_order(&items), vec![1, 3, 2, 0]);
+        let blockers: Vec<Uuid> = active_blockers(&items, &[0]).iter().map(|item| item.id).collect();
+        assert_eq!(blockers, vec![review.id]);
+        assert!(active_blockers(&items, &[1]).is_empty());
+
+        // Deploy waits on Step through Review, so Step can't wait on Deploy
+        assert!(waits_on(&items, deploy.id, step.id));
+        assert!(!waits_on(&items, step.id, deploy.id));
+
+        // Finishing Write (and its step) unblocks Review
+        unblock(&mut items, &write);
+        assert!(items[2].blocked_by.is_empty());
+        assert_eq!(items[0].blocked_by, vec![review.id]);
+
+        // A cycle falls back to list order
+        items[2].blocked_by = vec![deploy.id];
+        assert_eq!(schedule_order(&items), vec![1, 3, 0, 2]);
+    }
+}
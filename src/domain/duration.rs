@@ -0,0 +1,52 @@
+use anyhow::{bail, Context, Result};
+use chrono::Duration;
+
+/// Parse a duration like "1.5h", "90m", "1h30m" or "2" (hours), as typed for estimates
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim().to_lowercase();
+    if s.is_empty() {
+        bail!("Empty duration");
+    }
+
+    // Bare number means hours
+    if let Ok(hours) = s.parse::<f64>() {
+        return Ok(Duration::seconds((hours * 3600.0) as i64));
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in s.chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'h' | 'm' => {
+                let value: f64 = number
+                    .parse()
+                    .with_context(|| format!("Invalid duration: {}", s))?;
+                let secs = if c == 'h' { value * 3600.0 } else { value * 60.0 };
+                total += Duration::seconds(secs as i64);
+                number.clear();
+            }
+            _ => bail!("Invalid duration: {} (use e.g. 1.5h, 90m or 1h30m)", s),
+        }
+    }
+    if !number.is_empty() {
+        bail!("Invalid duration: {} (missing unit after {})", s, number);
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1.5h").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("90m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("2").unwrap(), Duration::hours(2));
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("1h30").is_err());
+    }
+}
@@ -15,6 +15,8 @@ pub enum RunStatus {
     Paused,
     Done,
     Postponed,
+    /// On hold, waiting on someone or something else
+    Waiting,
 }
 
 impl RunStatus {
@@ -26,6 +28,7 @@ impl RunStatus {
             "PAUSED" => Some(Self::Paused),
             "DONE" => Some(Self::Done),
             "POSTPONED" => Some(Self::Postponed),
+            "WAITING" => Some(Self::Waiting),
             _ => None,
         }
     }
@@ -38,12 +41,13 @@ impl RunStatus {
             Self::Paused => "PAUSED",
             Self::Done => "DONE",
             Self::Postponed => "POSTPONED",
+            Self::Waiting => "WAITING",
         }
     }
 
     /// Check if status is valid for today.md/tomorrow.md (excludes DONE/POSTPONED)
     pub fn is_active(&self) -> bool {
-        matches!(self, Self::Idle | Self::Running | Self::Paused)
+        matches!(self, Self::Idle | Self::Running | Self::Paused | Self::Waiting)
     }
 }
 
@@ -58,9 +62,15 @@ pub enum UiMode {
     AddingSubtask,
     EditingTask, // Editing an existing task/subtask
     IdleCheck,
+    IdleTrimmed, // The idle check timed out; shows the running time that was trimmed
     EditingJournal,
-    DayChanged, // Shown when midnight has passed, forces restart
     ModeSelector, // Shown when user presses 'm' to select global mode
+    ExternalChange, // A data file was changed by another session or editor
+    AwayCheck, // The computer was suspended (or the clock jumped) while running
+    CrashRecovery, // Startup prompt for tasks left running by a crashed session
+    EditingHistory, // Listing and editing the running sessions of the selected item
+    PickingBlockers, // Choosing the tasks the selected item waits on
+    SettingWaiting, // Typing what the selected item is waiting on and when to follow up
 }
 
 /// Global activity state for the application
@@ -74,84 +84,6 @@ pub enum GlobalState {
     Idle,
 }
 
-/// Global context mode representing the user's current life state
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum GlobalMode {
-    /// Active focus time. Default mode. Timers run normally.
-    Working,
-    /// Short break. All tasks paused.
-    Break,
-    /// Lunch break. All tasks paused.
-    Lunch,
-    /// Gym/exercise time. All tasks paused.
-    Gym,
-    /// Dinner time. All tasks paused.
-    Dinner,
-    /// Personal errands. All tasks paused.
-    Personal,
-    /// Sleep/night mode. All tasks paused.
-    Sleep,
-}
-
-impl GlobalMode {
-    /// Get the emoji symbol for this mode
-    pub fn symbol(&self) -> &'static str {
-        match self {
-            GlobalMode::Working => "💼",
-            GlobalMode::Break => "☁️",
-            GlobalMode::Lunch => "🍽",
-            GlobalMode::Gym => "🏋️",
-            GlobalMode::Dinner => "🍲",
-            GlobalMode::Personal => "🏡",
-            GlobalMode::Sleep => "🌙",
-        }
-    }
-
-    /// Get the display name for this mode
-    pub fn name(&self) -> &'static str {
-        match self {
-            GlobalMode::Working => "Working",
-            GlobalMode::Break => "Break",
-            GlobalMode::Lunch => "Lunch",
-            GlobalMode::Gym => "Gym",
-            GlobalMode::Dinner => "Dinner",
-            GlobalMode::Personal => "Personal",
-            GlobalMode::Sleep => "Sleep",
-        }
-    }
-
-    /// Get the contextual phrase for this mode (for Focus Garden)
-    pub fn contextual_phrase(&self) -> &'static str {
-        match self {
-            GlobalMode::Working => "",
-            GlobalMode::Break => "Breathe and reset ☁️",
-            GlobalMode::Lunch => "Nourish before you bloom again 🍽",
-            GlobalMode::Gym => "Strength feeds focus 🏋️",
-            GlobalMode::Dinner => "Evening nourishment 🍲",
-            GlobalMode::Personal => "Tending your own garden 🏡",
-            GlobalMode::Sleep => "Rest — tomorrow's seeds await 🌙",
-        }
-    }
-
-    /// Check if this mode should pause timers
-    pub fn should_pause_timers(&self) -> bool {
-        !matches!(self, GlobalMode::Working)
-    }
-
-    /// Get all modes as a list
-    pub fn all() -> &'static [GlobalMode] {
-        &[
-            GlobalMode::Working,
-            GlobalMode::Break,
-            GlobalMode::Lunch,
-            GlobalMode::Gym,
-            GlobalMode::Dinner,
-            GlobalMode::Personal,
-            GlobalMode::Sleep,
-        ]
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,5 +111,6 @@ mod tests {
         assert!(RunStatus::Paused.is_active());
         assert!(!RunStatus::Done.is_active());
         assert!(!RunStatus::Postponed.is_active());
+        assert!(RunStatus::Waiting.is_active());
     }
 }
@@ -0,0 +1,334 @@
+use super::enums::RunStatus;
+use super::item::{Item, StateEvent};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Local};
+
+/// A span of time an item spent RUNNING, derived from its state history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Session {
+    pub start: DateTime<Local>,
+    /// None while the item is still running
+    pub end: Option<DateTime<Local>>,
+}
+
+impl Session {
+    pub fn duration(&self) -> Duration {
+        self.end.unwrap_or_else(Local::now).signed_duration_since(self.start)
+    }
+
+    fn overlaps(&self, start: DateTime<Local>, end: Option<DateTime<Local>>) -> bool {
+        let now = Local::now();
+        self.start < end.unwrap_or(now) && start < self.end.unwrap_or(now)
+    }
+}
+
+/// Editing history as running sessions
+///
+/// Every edit rewrites `state_history` so that it stays a valid sequence of
+/// transitions, then recomputes `track.elapsed` from it.
+impl Item {
+    /// Running sessions, oldest first
+    pub fn sessions(&self) -> Vec<Session> {
+        self.state_history
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.to_status == RunStatus::Running)
+            .map(|(idx, event)| Session {
+                start: event.timestamp,
+                end: self.state_history.get(idx + 1).map(|next| next.timestamp),
+            })
+            .collect()
+    }
+
+    /// Record a session the timer missed (e.g. "I started this 20 minutes ago")
+    pub fn add_session(&mut self, start: DateTime<Local>, end: DateTime<Local>) -> Result<()> {
+        self.check_session(start, Some(end), None)?;
+        self.set_status_between(start, Some(end), RunStatus::Running);
+        self.history_edited();
+        Ok(())
+    }
+
+    /// Move or resize a session (the end of a running session stays open)
+    pub fn move_session(&mut self, index: usize, start: DateTime<Local>, end: DateTime<Local>) -> Result<()> {
+        let session = self.session(index)?;
+        let end = session.end.map(|_| end);
+        self.check_session(start, end, Some(index))?;
+
+        let gap = self.status_before_session(index);
+        match session.end {
+            Some(old_end) => self.set_status_between(session.start, Some(old_end), gap),
+            None => self.set_status_between(session.start, None, RunStatus::Running),
+        }
+        self.set_status_between(start, end, RunStatus::Running);
+        self.history_edited();
+        Ok(())
+    }
+
+    /// Pause a session between `from` and `to` (e.g. a meeting the timer
+    /// kept running through); with `from == to` the session is just split in two
+    pub fn split_session(&mut self, index: usize, from: DateTime<Local>, to: DateTime<Local>) -> Result<()> {
+        let session = self.session(index)?;
+        if from > to {
+            bail!("The break must end after it starts");
+        }
+        if from <= session.start || to >= session.end.unwrap_or_else(Local::now) {
+            bail!("The break must fall inside the session");
+        }
+
+        self.set_status_between(from, Some(to), RunStatus::Paused);
+        self.history_edited();
+        Ok(())
+    }
+
+    /// Remove a session (running sessions have to be paused first)
+    pub fn delete_session(&mut self, index: usize) -> Result<()> {
+        let session = self.session(index)?;
+        let Some(end) = session.end else {
+            bail!("Pause the task before deleting its running session");
+        };
+
+        let gap = self.status_before_session(index);
+        self.set_status_between(session.start, Some(end), gap);
+        self.history_edited();
+        Ok(())
+    }
+
+    /// Pause a running item as of an earlier time (e.g. a mode switch the
+    /// user forgot to make), dropping the running time since then
+    pub fn pause_since(&mut self, at: DateTime<Local>) {
+        if self.status != RunStatus::Running {
+            return;
+        }
+        let created = self.state_history.first().map(|event| event.timestamp).unwrap_or(at);
+        let at = std::cmp::max(at, created);
+        self.status = RunStatus::Paused;
+        self.track.started_at = None;
+        self.set_status_between(at, None, RunStatus::Paused);
+        self.sync_elapsed_from_history();
+    }
+
+    /// Resume a paused item as of an earlier time, counting the time since
+    /// then (never before it was paused)
+    pub fn resume_since(&mut self, at: DateTime<Local>) {
+        if self.status != RunStatus::Paused {
+            return;
+        }
+        let paused_at = self.state_history.last().map(|event| event.timestamp).unwrap_or(at);
+        self.status = RunStatus::Running;
+        self.set_status_between(std::cmp::max(at, paused_at), None, RunStatus::Running);
+        self.history_edited();
+    }
+
+    fn session(&self, index: usize) -> Result<Session> {
+        match self.sessions().get(index) {
+            Some(session) => Ok(*session),
+            None => bail!("No such session"),
+        }
+    }
+
+    /// Check that a session fits: it ends after it starts, isn't in the
+    /// future, doesn't run past completion and doesn't overlap another one
+    fn check_session(&self, start: DateTime<Local>, end: Option<DateTime<Local>>, ignore: Option<usize>) -> Result<()> {
+        let now = Local::now();
+        if end.is_some_and(|end| end <= start) {
+            bail!("A session must end after it starts");
+        }
+        if end.unwrap_or(start) > now {
+            bail!("Sessions can't be in the future");
+        }
+        if let (Some(completed), Some(end)) = (self.completed_at, end) {
+            if end > completed {
+                bail!("Sessions can't end after the task was completed");
+            }
+        }
+        let overlapping = self
+            .sessions()
+            .iter()
+            .enumerate()
+            .any(|(idx, session)| Some(idx) != ignore && session.overlaps(start, end));
+        if overlapping {
+            bail!("Sessions can't overlap");
+        }
+        Ok(())
+    }
+
+    /// Status the item was in before a session started
+    fn status_before_session(&self, index: usize) -> RunStatus {
+        self.state_history
+            .iter()
+            .filter(|event| event.to_status == RunStatus::Running)
+            .nth(index)
+            .and_then(|event| event.from_status)
+            .filter(|status| *status != RunStatus::Running)
+            .unwrap_or(RunStatus::Paused)
+    }
+
+    /// Status in effect at a point in time
+    fn status_at(&self, at: DateTime<Local>) -> RunStatus {
+        self.state_history
+            .iter()
+            .take_while(|event| event.timestamp <= at)
+            .last()
+            .map(|event| event.to_status)
+            .unwrap_or(RunStatus::Idle)
+    }
+
+    /// Make the item have `status` from `from` until `to` (or from then on,
+    /// with None), keeping the history before and after that span
+    fn set_status_between(&mut self, from: DateTime<Local>, to: Option<DateTime<Local>>, status: RunStatus) {
+        let after = to.map(|to| self.status_at(to));
+
+        // The creation event stays first, moving back if needed
+        let mut creation = self.state_history.remove(0);
+        if from < creation.timestamp {
+            creation.timestamp = from;
+            self.created_at = std::cmp::min(self.created_at, from);
+        }
+
+        self.state_history
+            .retain(|event| event.timestamp < from || to.is_some_and(|to| event.timestamp > to));
+        let idx = self.state_history.partition_point(|event| event.timestamp < from);
+        let mut inserted = vec![StateEvent {
+            timestamp: from,
+            from_status: None,
+            to_status: status,
+        }];
+        if let (Some(to), Some(after)) = (to, after) {
+            inserted.push(StateEvent {
+                timestamp: to,
+                from_status: None,
+                to_status: after,
+            });
+        }
+        self.state_history.splice(idx..idx, inserted);
+        self.state_history.insert(0, creation);
+
+        // Chain the transitions again, dropping ones that change nothing
+        let mut normalized: Vec<StateEvent> = Vec::with_capacity(self.state_history.len());
+        for mut event in self.state_history.drain(..) {
+            if let Some(previous) = normalized.last() {
+                if previous.to_status == event.to_status {
+                    continue;
+                }
+                event.from_status = Some(previous.to_status);
+            }
+            normalized.push(event);
+        }
+        self.state_history = normalized;
+    }
+
+    /// Recompute elapsed time (and restart a running timer) after an edit
+    fn history_edited(&mut self) {
+        self.sync_elapsed_from_history();
+        self.resume_running_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScheduleDay;
+
+    /// An item created 4 hours ago that ran from -3h to -2h and was then paused
+    fn item_with_session() -> (Item, DateTime<Local>) {
+        let now = Local::now();
+        let mut item = Item::new("Task".to_string(), Duration::hours(2), ScheduleDay::Today);
+        item.state_history[0].timestamp = now - Duration::hours(4);
+        item.state_history.push(StateEvent {
+            timestamp: now - Duration::hours(3),
+            from_status: Some(RunStatus::Idle),
+            to_status: RunStatus::Running,
+        });
+        item.state_history.push(StateEvent {
+            timestamp: now - Duration::hours(2),
+            from_status: Some(RunStatus::Running),
+            to_status: RunStatus::Paused,
+        });
+        item.status = RunStatus::Paused;
+        item.sync_elapsed_from_history();
+        (item, now)
+    }
+
+    fn is_chained(item: &Item) -> bool {
+        item.state_history
+            .windows(2)
+            .all(|pair| pair[1].from_status == Some(pair[0].to_status) && pair[0].timestamp <= pair[1].timestamp)
+    }
+
+    #[test]
+    fn test_add_and_delete_session() {
+        let (mut item, now) = item_with_session();
+
+        item.add_session(now - Duration::minutes(90), now - Duration::minutes(60)).unwrap();
+        assert_eq!(item.sessions().len(), 2);
+        assert_eq!(item.track.elapsed.num_minutes(), 90);
+        assert!(is_chained(&item));
+
+        // Overlapping and future sessions are rejected
+        assert!(item.add_session(now - Duration::minutes(150), now - Duration::minutes(80)).is_err());
+        assert!(item.add_session(now - Duration::minutes(10), now + Duration::minutes(10)).is_err());
+
+        item.delete_session(0).unwrap();
+        assert_eq!(item.sessions().len(), 1);
+        assert_eq!(item.track.elapsed.num_minutes(), 30);
+        assert_eq!(item.state_history.last().unwrap().to_status, RunStatus::Paused);
+        assert!(is_chained(&item));
+    }
+
+    #[test]
+    fn test_move_and_split_session() {
+        let (mut item, now) = item_with_session();
+
+        // Started earlier than the task was created
+        item.move_session(0, now - Duration::hours(5), now - Duration::hours(2)).unwrap();
+        assert_eq!(item.track.elapsed.num_minutes(), 180);
+        assert_eq!(item.state_history[0].timestamp, now - Duration::hours(5));
+        assert!(is_chained(&item));
+
+        // A meeting in the middle
+        item.split_session(0, now - Duration::hours(4), now - Duration::hours(3)).unwrap();
+        let sessions = item.sessions();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].end, Some(now - Duration::hours(4)));
+        assert_eq!(sessions[1].start, now - Duration::hours(3));
+        assert_eq!(item.track.elapsed.num_minutes(), 120);
+        assert!(is_chained(&item));
+
+        assert!(item.split_session(1, now - Duration::hours(4), now - Duration::hours(3)).is_err());
+    }
+
+    #[test]
+    fn test_move_running_session_start() {
+        let now = Local::now();
+        let mut item = Item::new("Task".to_string(), Duration::hours(1), ScheduleDay::Today);
+        item.start();
+
+        // "I actually started this 20 minutes ago"
+        item.move_session(0, now - Duration::minutes(20), now).unwrap();
+        assert_eq!(item.status, RunStatus::Running);
+        assert_eq!(item.sessions()[0].end, None);
+        assert!(item.track.elapsed.num_minutes() >= 20);
+        assert!(item.delete_session(0).is_err());
+        assert!(is_chained(&item));
+    }
+
+    #[test]
+    fn test_pause_and_resume_since() {
+        let (mut item, now) = item_with_session();
+        item.resume_since(now - Duration::minutes(90));
+        assert_eq!(item.status, RunStatus::Running);
+        assert_eq!(item.track.elapsed.num_minutes(), 150);
+
+        item.pause_since(now - Duration::minutes(30));
+        assert_eq!(item.status, RunStatus::Paused);
+        assert_eq!(item.track.elapsed.num_minutes(), 120);
+        assert_eq!(item.state_history.last().unwrap().timestamp, now - Duration::minutes(30));
+        assert!(is_chained(&item));
+
+        // Resuming before the pause picks up where it was paused
+        item.resume_since(now - Duration::hours(3));
+        assert_eq!(item.sessions().len(), 2);
+        assert_eq!(item.track.elapsed.num_minutes(), 150);
+        assert!(is_chained(&item));
+    }
+}
@@ -1,5 +1,5 @@
 use super::enums::{RunStatus, ScheduleDay};
-use chrono::{DateTime, Duration, Local};
+use chrono::{DateTime, Duration, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 use uuid::Uuid;
@@ -134,7 +134,7 @@ fn format_duration(duration: Duration) -> String {
 /// A task or subtask item
 #[derive(Debug, Clone)]
 pub struct Item {
-    /// Unique ID for internal references (not persisted in markdown)
+    /// Stable ID, persisted in the daily file so a task keeps one identity across days
     pub id: Uuid,
     /// Task title
     pub title: String,
@@ -148,10 +148,18 @@ pub struct Item {
     pub schedule: ScheduleDay,
     /// Whether subtasks are expanded (for parent tasks)
     pub expanded: bool,
-    /// Subtasks (one level deep)
+    /// Subtasks (which can have subtasks of their own)
     pub subtasks: Vec<Item>,
     /// Tags for categorization
     pub tags: Vec<String>,
+    /// IDs of the tasks that must be done before this one can start
+    pub blocked_by: Vec<Uuid>,
+    /// What the item is waiting on (while WAITING)
+    pub waiting_reason: String,
+    /// When to pick a WAITING item up again
+    pub follow_up: Option<NaiveDate>,
+    /// Recurring rule the task was generated from (see recurring.md)
+    pub recurring_id: Option<Uuid>,
     /// When the task was created
     pub created_at: DateTime<Local>,
     /// When the task was completed (if done)
@@ -175,6 +183,10 @@ impl Item {
             expanded: true,
             subtasks: Vec::new(),
             tags: Vec::new(),
+            blocked_by: Vec::new(),
+            waiting_reason: String::new(),
+            follow_up: None,
+            recurring_id: None,
             created_at,
             completed_at: None,
             state_history: vec![initial_event],
@@ -213,9 +225,42 @@ impl Item {
         }
     }
 
-    /// Toggle between running and paused
+    /// Put this item on hold, waiting on someone else (stops the timer if running)
+    pub fn set_waiting(&mut self, reason: String, follow_up: Option<NaiveDate>) {
+        if self.status == RunStatus::Running {
+            self.track.pause();
+        }
+        if self.status != RunStatus::Waiting {
+            let prev_status = self.status;
+            self.status = RunStatus::Waiting;
+            self.state_history.push(StateEvent::new(Some(prev_status), RunStatus::Waiting));
+        }
+        self.waiting_reason = reason;
+        self.follow_up = follow_up;
+    }
+
+    /// Take this item off hold, back to idle
+    pub fn stop_waiting(&mut self) {
+        if self.status == RunStatus::Waiting {
+            self.status = RunStatus::Idle;
+            self.state_history.push(StateEvent::new(Some(RunStatus::Waiting), RunStatus::Idle));
+        }
+        self.waiting_reason.clear();
+        self.follow_up = None;
+    }
+
+    /// Whether this item is waiting and its follow-up date has come
+    pub fn follow_up_due(&self, today: NaiveDate) -> bool {
+        self.status == RunStatus::Waiting && self.follow_up.is_some_and(|date| date <= today)
+    }
+
+    /// Toggle between running and paused (a waiting item stops waiting and starts)
     pub fn toggle_run_pause(&mut self) {
         match self.status {
+            RunStatus::Waiting => {
+                self.stop_waiting();
+                self.start();
+            }
             RunStatus::Idle | RunStatus::Paused => self.start(),
             RunStatus::Running => self.pause(),
             _ => {}
@@ -281,20 +326,47 @@ impl Item {
         self.subtasks.iter().any(|st| st.status == RunStatus::Running)
     }
 
-    /// Get total estimate from all subtasks
+    /// Get total estimate from all subtasks (rolled up from the leaves below them)
     pub fn subtask_total_estimate(&self) -> Duration {
         self.subtasks
             .iter()
-            .map(|st| st.track.estimate)
+            .map(|st| st.rollup_estimate())
             .fold(Duration::zero(), |acc, est| acc + est)
     }
 
-    /// Get total elapsed time from all subtasks
+    /// Get total elapsed time from all subtasks (summed over the leaves below them)
     pub fn subtask_total_elapsed(&self) -> Duration {
         self.subtasks
             .iter()
-            .map(|st| st.track.elapsed)
-            .fold(Duration::zero(), |acc, elapsed| acc + elapsed)
+            .flat_map(|st| st.walk())
+            .filter(|item| item.subtasks.is_empty())
+            .fold(Duration::zero(), |acc, item| acc + item.track.elapsed)
+    }
+
+    /// Estimate of a leaf, or the sum of the leaves below an item with subtasks
+    pub fn rollup_estimate(&self) -> Duration {
+        if self.subtasks.is_empty() {
+            self.track.estimate
+        } else {
+            self.subtask_total_estimate()
+        }
+    }
+
+    /// The item and everything below it, parents before their subtasks
+    pub fn walk(&self) -> Vec<&Item> {
+        let mut items = vec![self];
+        for subtask in &self.subtasks {
+            items.extend(subtask.walk());
+        }
+        items
+    }
+
+    /// Apply `f` to the item and everything below it, parents first
+    pub fn walk_mut(&mut self, f: &mut dyn FnMut(&mut Item)) {
+        f(self);
+        for subtask in &mut self.subtasks {
+            subtask.walk_mut(f);
+        }
     }
 
     /// Coerce all running items to paused (for startup)
@@ -307,6 +379,89 @@ impl Item {
         }
     }
 
+    /// End a running interval at a given time (e.g. midnight, when closing out
+    /// a day), leaving the item and its subtasks paused from then on
+    pub fn close_running_at(&mut self, at: DateTime<Local>) {
+        if self.status == RunStatus::Running {
+            self.status = RunStatus::Paused;
+            self.track.started_at = None;
+            self.state_history.push(StateEvent {
+                timestamp: at,
+                from_status: Some(RunStatus::Running),
+                to_status: RunStatus::Paused,
+            });
+        }
+        for subtask in &mut self.subtasks {
+            subtask.close_running_at(at);
+        }
+    }
+
+    /// Whether the item (or a subtask) isn't running but its history still
+    /// ends in a RUNNING interval started at or before `at` - i.e. the app
+    /// stopped without closing it
+    pub fn has_open_interval(&self, at: DateTime<Local>) -> bool {
+        let open = self.status != RunStatus::Running
+            && self
+                .state_history
+                .last()
+                .is_some_and(|event| event.to_status == RunStatus::Running && event.timestamp <= at);
+        open || self.subtasks.iter().any(|subtask| subtask.has_open_interval(at))
+    }
+
+    /// Close RUNNING intervals left open since `started_by` (see
+    /// `has_open_interval`) at the given time, or when they started if later
+    pub fn close_open_interval(&mut self, started_by: DateTime<Local>, at: DateTime<Local>) {
+        if self.status != RunStatus::Running {
+            if let Some(last) = self.state_history.last() {
+                if last.to_status == RunStatus::Running && last.timestamp <= started_by {
+                    let end = std::cmp::max(at, last.timestamp);
+                    self.state_history.push(StateEvent {
+                        timestamp: end,
+                        from_status: Some(RunStatus::Running),
+                        to_status: self.status,
+                    });
+                }
+            }
+        }
+        for subtask in &mut self.subtasks {
+            subtask.close_open_interval(started_by, at);
+        }
+    }
+
+    /// Take a span of time out of a running interval (e.g. while the computer
+    /// was suspended): items running throughout it are recorded as paused
+    /// from `from` to `to`, then running again
+    pub fn exclude_interval(&mut self, from: DateTime<Local>, to: DateTime<Local>) {
+        let running_throughout = self.status == RunStatus::Running
+            && self.state_history.last().is_some_and(|event| event.timestamp <= from);
+        if running_throughout {
+            self.state_history.push(StateEvent {
+                timestamp: from,
+                from_status: Some(RunStatus::Running),
+                to_status: RunStatus::Paused,
+            });
+            self.state_history.push(StateEvent {
+                timestamp: to,
+                from_status: Some(RunStatus::Paused),
+                to_status: RunStatus::Running,
+            });
+        }
+        for subtask in &mut self.subtasks {
+            subtask.exclude_interval(from, to);
+        }
+    }
+
+    /// Restart the in-memory timer of items loaded as RUNNING
+    /// (call after `sync_elapsed_from_history`, which already counts time up to now)
+    pub fn resume_running_timer(&mut self) {
+        if self.status == RunStatus::Running {
+            self.track.start();
+        }
+        for subtask in &mut self.subtasks {
+            subtask.resume_running_timer();
+        }
+    }
+
     /// Sync elapsed time from state history
     /// This should be called after loading from disk to ensure elapsed matches actual history
     pub fn sync_elapsed_from_history(&mut self) {
@@ -322,14 +477,6 @@ impl Item {
         }
     }
 
-    /// Regenerate UUIDs (for items loaded from disk)
-    pub fn regenerate_ids(&mut self) {
-        self.id = Uuid::new_v4();
-        for subtask in &mut self.subtasks {
-            subtask.regenerate_ids();
-        }
-    }
-
     /// Calculate total calendar time (created to completed)
     pub fn calendar_time(&self) -> Option<Duration> {
         self.completed_at.map(|completed| completed.signed_duration_since(self.created_at))
@@ -367,8 +514,8 @@ impl Item {
                 RunStatus::Running => running_time = running_time + duration,
                 RunStatus::Paused => paused_time = paused_time + duration,
                 RunStatus::Idle => idle_time = idle_time + duration,
-                RunStatus::Done | RunStatus::Postponed => {
-                    // Don't count time in Done/Postponed states
+                RunStatus::Done | RunStatus::Postponed | RunStatus::Waiting => {
+                    // Don't count time in Done/Postponed/Waiting states
                 }
             }
         }
@@ -676,4 +823,71 @@ mod tests {
             item.subtasks[0].track.elapsed.num_minutes()
         );
     }
+
+    #[test]
+    fn test_close_running_at() {
+        let now = Local::now();
+        let mut item = Item::new("Late task".to_string(), Duration::hours(2), ScheduleDay::Today);
+        item.state_history[0].timestamp = now - Duration::hours(1);
+        item.state_history.push(StateEvent {
+            timestamp: now - Duration::minutes(30),
+            from_status: Some(RunStatus::Idle),
+            to_status: RunStatus::Running,
+        });
+        item.status = RunStatus::Running;
+        item.track.start();
+
+        item.close_running_at(now - Duration::minutes(10));
+        item.sync_elapsed_from_history();
+
+        assert_eq!(item.status, RunStatus::Paused);
+        assert!(item.track.started_at.is_none());
+        assert_eq!(item.track.elapsed.num_minutes(), 20);
+    }
+
+    #[test]
+    fn test_exclude_interval() {
+        let now = Local::now();
+        let mut item = Item::new("Suspended task".to_string(), Duration::hours(2), ScheduleDay::Today);
+        item.state_history[0].timestamp = now - Duration::hours(2);
+        item.state_history.push(StateEvent {
+            timestamp: now - Duration::minutes(90),
+            from_status: Some(RunStatus::Idle),
+            to_status: RunStatus::Running,
+        });
+        item.status = RunStatus::Running;
+
+        item.exclude_interval(now - Duration::minutes(60), now - Duration::minutes(10));
+        item.sync_elapsed_from_history();
+
+        assert_eq!(item.status, RunStatus::Running);
+        assert_eq!(item.track.elapsed.num_minutes(), 40);
+        assert_eq!(item.interruption_count(), 1);
+    }
+
+    #[test]
+    fn test_close_open_interval() {
+        let now = Local::now();
+        let heartbeat = now - Duration::hours(3);
+        let mut item = Item::new("Crashed task".to_string(), Duration::hours(2), ScheduleDay::Today);
+        item.state_history[0].timestamp = now - Duration::hours(5);
+        item.state_history.push(StateEvent {
+            timestamp: now - Duration::hours(4),
+            from_status: Some(RunStatus::Idle),
+            to_status: RunStatus::Running,
+        });
+        item.status = RunStatus::Running;
+        // Loaded after a crash: coerced to paused, interval still open
+        item.coerce_running_to_paused();
+
+        assert!(item.has_open_interval(heartbeat));
+        assert!(!item.has_open_interval(now - Duration::hours(4) - Duration::minutes(1)));
+
+        item.close_open_interval(heartbeat, heartbeat);
+        item.sync_elapsed_from_history();
+
+        assert!(!item.has_open_interval(now));
+        assert_eq!(item.track.elapsed.num_minutes(), 60);
+        assert_eq!(item.state_history.last().unwrap().to_status, RunStatus::Paused);
+    }
 }
@@ -1,10 +1,24 @@
+pub mod blocking;
+pub mod duration;
 pub mod enums;
+pub mod history;
 pub mod item;
+pub mod modes;
+pub mod recurrence;
+pub mod tree;
 pub mod views;
 
-pub use enums::{GlobalMode, GlobalState, RunStatus, ScheduleDay, UiMode};
+pub use blocking::{active_blockers, schedule_order, unblock, waits_on};
+pub use duration::parse_duration;
+pub use enums::{GlobalState, RunStatus, ScheduleDay, UiMode};
+pub use history::Session;
 pub use item::{Item, StateEvent, TimeTracking};
+pub use modes::{GlobalMode, ModeSet};
+pub use recurrence::{Recurrence, RecurringTask};
+pub use tree::{
+    find_item_mut, find_path, item_at, item_at_mut, remove_at, siblings_mut, walk_items, walk_items_mut, ItemPath,
+};
 pub use views::{
-    compute_totals, flatten_tasks, garden_plant_state, plant_glyph, status_badge, tree_connector,
+    compute_totals, flatten_all_tasks, flatten_tasks, garden_plant_state, plant_glyph, status_badge, tree_connector,
     FlatRow,
 };
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+
+/// Global context mode representing the user's current life state
+///
+/// Modes are defined in configuration (see `ModeSet`); a `GlobalMode` is
+/// just the mode's name, which is what gets stored in meta.json and the
+/// per-day mode timelines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GlobalMode(String);
+
+impl GlobalMode {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Get the display name for this mode
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for GlobalMode {
+    /// The built-in focus mode
+    fn default() -> Self {
+        Self::new("Working")
+    }
+}
+
+/// Definition of one context mode (`[[modes]]` in config.toml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModeDefinition {
+    pub name: String,
+    /// Emoji or short symbol shown next to the name
+    #[serde(default)]
+    pub symbol: String,
+    /// Phrase shown in the Focus Garden while this mode is active
+    #[serde(default)]
+    pub phrase: String,
+    /// Whether switching to this mode pauses running tasks
+    #[serde(default = "default_pause_timers")]
+    pub pause_timers: bool,
+    /// Key that selects this mode in the mode selector (defaults to its position, 1-9)
+    #[serde(default)]
+    pub key: Option<char>,
+}
+
+fn default_pause_timers() -> bool {
+    true
+}
+
+impl ModeDefinition {
+    fn new(name: &str, symbol: &str, phrase: &str, pause_timers: bool, key: char) -> Self {
+        Self {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            phrase: phrase.to_string(),
+            pause_timers,
+            key: Some(key),
+        }
+    }
+
+    pub fn mode(&self) -> GlobalMode {
+        GlobalMode::new(self.name.clone())
+    }
+}
+
+/// The configured context modes, in selector order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<ModeDefinition>", into = "Vec<ModeDefinition>")]
+pub struct ModeSet(Vec<ModeDefinition>);
+
+impl Default for ModeSet {
+    /// The built-in modes: Working plus six modes that pause timers
+    fn default() -> Self {
+        Self(vec![
+            ModeDefinition::new("Working", "💼", "", false, '1'),
+            ModeDefinition::new("Break", "☁️", "Breathe and reset ☁️", true, '2'),
+            ModeDefinition::new("Lunch", "🍽", "Nourish before you bloom again 🍽", true, '3'),
+            ModeDefinition::new("Gym", "🏋️", "Strength feeds focus 🏋️", true, '4'),
+            ModeDefinition::new("Dinner", "🍲", "Evening nourishment 🍲", true, '5'),
+            ModeDefinition::new("Personal", "🏡", "Tending your own garden 🏡", true, '6'),
+            ModeDefinition::new("Sleep", "🌙", "Rest — tomorrow's seeds await 🌙", true, '7'),
+        ])
+    }
+}
+
+impl TryFrom<Vec<ModeDefinition>> for ModeSet {
+    type Error = String;
+
+    /// Validate mode definitions and fill in default hotkeys
+    fn try_from(mut modes: Vec<ModeDefinition>) -> Result<Self, Self::Error> {
+        if modes.is_empty() {
+            return Err("at least one mode must be defined".to_string());
+        }
+
+        for (idx, mode) in modes.iter_mut().enumerate() {
+            mode.name = mode.name.trim().to_string();
+            if mode.name.is_empty() {
+                return Err("mode names cannot be empty".to_string());
+            }
+            if mode.key.is_none() && idx < 9 {
+                mode.key = char::from_digit(idx as u32 + 1, 10);
+            }
+        }
+
+        for (idx, mode) in modes.iter().enumerate() {
+            let earlier = &modes[..idx];
+            if earlier.iter().any(|m| m.name == mode.name) {
+                return Err(format!("mode '{}' is defined twice", mode.name));
+            }
+            if let Some(key) = mode.key {
+                if earlier.iter().any(|m| m.key == Some(key)) {
+                    return Err(format!("key '{}' is used by more than one mode", key));
+                }
+            }
+        }
+
+        Ok(Self(modes))
+    }
+}
+
+impl From<ModeSet> for Vec<ModeDefinition> {
+    fn from(modes: ModeSet) -> Self {
+        modes.0
+    }
+}
+
+impl ModeSet {
+    /// Get all modes in selector order
+    pub fn all(&self) -> &[ModeDefinition] {
+        &self.0
+    }
+
+    pub fn get(&self, mode: &GlobalMode) -> Option<&ModeDefinition> {
+        self.0.iter().find(|def| def.name == mode.name())
+    }
+
+    /// Find the mode selected by a key in the mode selector
+    pub fn by_key(&self, key: char) -> Option<GlobalMode> {
+        self.0.iter().find(|def| def.key == Some(key)).map(ModeDefinition::mode)
+    }
+
+    /// The mode to fall back to: the first one that keeps timers running
+    pub fn default_mode(&self) -> GlobalMode {
+        self.0
+            .iter()
+            .find(|def| !def.pause_timers)
+            .unwrap_or(&self.0[0])
+            .mode()
+    }
+
+    /// Get the symbol for a mode (empty for modes no longer configured)
+    pub fn symbol(&self, mode: &GlobalMode) -> &str {
+        self.get(mode).map(|def| def.symbol.as_str()).unwrap_or("")
+    }
+
+    /// Get the contextual phrase for a mode (for Focus Garden)
+    pub fn contextual_phrase(&self, mode: &GlobalMode) -> &str {
+        self.get(mode).map(|def| def.phrase.as_str()).unwrap_or("")
+    }
+
+    /// Check if a mode should pause timers (modes no longer configured do)
+    pub fn should_pause_timers(&self, mode: &GlobalMode) -> bool {
+        self.get(mode).map(|def| def.pause_timers).unwrap_or(true)
+    }
+
+    /// Format a mode as "symbol name"
+    pub fn label(&self, mode: &GlobalMode) -> String {
+        match self.symbol(mode) {
+            "" => mode.name().to_string(),
+            symbol => format!("{} {}", symbol, mode.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_modes() {
+        let modes = ModeSet::default();
+        assert_eq!(modes.all().len(), 7);
+        assert_eq!(modes.default_mode(), GlobalMode::default());
+        assert!(!modes.should_pause_timers(&GlobalMode::new("Working")));
+        assert!(modes.should_pause_timers(&GlobalMode::new("Lunch")));
+        assert_eq!(modes.by_key('3'), Some(GlobalMode::new("Lunch")));
+        assert_eq!(modes.label(&GlobalMode::new("Gym")), "🏋️ Gym");
+    }
+
+    #[test]
+    fn test_custom_modes_fill_keys_and_validate() {
+        let modes: ModeSet = serde_json::from_str(
+            r#"[{"name": "Focus", "pause_timers": false}, {"name": "Meeting", "symbol": "📅"}, {"name": "On-call", "key": "o"}]"#,
+        )
+        .unwrap();
+        assert_eq!(modes.by_key('1'), Some(GlobalMode::new("Focus")));
+        assert_eq!(modes.by_key('2'), Some(GlobalMode::new("Meeting")));
+        assert_eq!(modes.by_key('o'), Some(GlobalMode::new("On-call")));
+        assert!(modes.should_pause_timers(&GlobalMode::new("Meeting")));
+        assert_eq!(modes.default_mode(), GlobalMode::new("Focus"));
+
+        // Unknown modes (e.g. removed from config) still get a name
+        let gone = GlobalMode::new("Gym");
+        assert_eq!(modes.label(&gone), "Gym");
+        assert!(modes.should_pause_timers(&gone));
+
+        assert!(serde_json::from_str::<ModeSet>("[]").is_err());
+        assert!(serde_json::from_str::<ModeSet>(r#"[{"name": "A"}, {"name": "A"}]"#).is_err());
+        assert!(serde_json::from_str::<ModeSet>(r#"[{"name": "A"}, {"name": "B", "key": "1"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_global_mode_serializes_as_name() {
+        assert_eq!(serde_json::to_string(&GlobalMode::new("Lunch")).unwrap(), "\"Lunch\"");
+        let mode: GlobalMode = serde_json::from_str("\"Working\"").unwrap();
+        assert_eq!(mode, GlobalMode::default());
+    }
+}
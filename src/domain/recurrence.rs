@@ -0,0 +1,168 @@
+use super::enums::ScheduleDay;
+use super::item::Item;
+use anyhow::{anyhow, bail, Result};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::fmt;
+use uuid::Uuid;
+
+/// When a recurring task comes round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recurrence {
+    /// Every day
+    Daily,
+    /// Monday to Friday
+    Weekdays,
+    /// On the given days of the week
+    Weekly(Vec<Weekday>),
+    /// On a day of the month (the last day in months that are too short)
+    Monthly(u32),
+}
+
+impl Recurrence {
+    /// Parse "daily", "weekdays", "weekly mon, thu" or "monthly 15"
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim().to_lowercase();
+        let (kind, rest) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+        let rest = rest.trim();
+        match kind {
+            "daily" if rest.is_empty() => Ok(Self::Daily),
+            "weekdays" if rest.is_empty() => Ok(Self::Weekdays),
+            "weekly" => {
+                let mut days = Vec::new();
+                for name in rest.split([',', ' ']).filter(|name| !name.is_empty()) {
+                    let day: Weekday = name.parse().map_err(|_| anyhow!("Unknown day of the week: {}", name))?;
+                    if !days.contains(&day) {
+                        days.push(day);
+                    }
+                }
+                if days.is_empty() {
+                    bail!("Weekly recurrence needs at least one day, e.g. \"weekly mon, thu\"");
+                }
+                days.sort_by_key(|day| day.num_days_from_monday());
+                Ok(Self::Weekly(days))
+            }
+            "monthly" => match rest.parse::<u32>() {
+                Ok(day @ 1..=31) => Ok(Self::Monthly(day)),
+                _ => bail!("Monthly recurrence needs a day of the month (1-31), e.g. \"monthly 15\""),
+            },
+            _ => bail!("Invalid recurrence: {} (use daily, weekdays, weekly <days> or monthly <day>)", text),
+        }
+    }
+
+    /// Whether the task comes round on `date`
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        match self {
+            Self::Daily => true,
+            Self::Weekdays => date.weekday().num_days_from_monday() < 5,
+            Self::Weekly(days) => days.contains(&date.weekday()),
+            Self::Monthly(day) => {
+                let last_of_month = (date + Duration::days(1)).month() != date.month();
+                date.day() == *day || (last_of_month && date.day() < *day)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Daily => write!(f, "daily"),
+            Self::Weekdays => write!(f, "weekdays"),
+            Self::Weekly(days) => {
+                let names: Vec<String> = days.iter().map(|day| day.to_string().to_lowercase()).collect();
+                write!(f, "weekly {}", names.join(", "))
+            }
+            Self::Monthly(day) => write!(f, "monthly {}", day),
+        }
+    }
+}
+
+/// A task definition from recurring.md, added to the day's list whenever its
+/// recurrence comes round
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurringTask {
+    /// Stable ID, carried by every task generated from the rule
+    pub id: Uuid,
+    pub title: String,
+    pub every: Recurrence,
+    pub estimate: Duration,
+    pub tags: Vec<String>,
+    pub notes: String,
+}
+
+impl RecurringTask {
+    pub fn new(title: String, every: Recurrence) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            title,
+            every,
+            estimate: Duration::hours(1),
+            tags: Vec::new(),
+            notes: String::new(),
+        }
+    }
+
+    /// A fresh task for one occurrence, linked back to this rule
+    pub fn instantiate(&self) -> Item {
+        let mut item = Item::new(self.title.clone(), self.estimate, ScheduleDay::Today);
+        item.tags = self.tags.clone();
+        item.notes = self.notes.clone();
+        item.recurring_id = Some(self.id);
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_recurrence_parse_and_matches() {
+        // 2025-11-14 is a Friday
+        let friday = date(2025, 11, 14);
+        let saturday = date(2025, 11, 15);
+
+        assert!(Recurrence::parse("daily").unwrap().matches(saturday));
+        let weekdays = Recurrence::parse("Weekdays").unwrap();
+        assert!(weekdays.matches(friday));
+        assert!(!weekdays.matches(saturday));
+
+        let weekly = Recurrence::parse("weekly fri, Tuesday").unwrap();
+        assert_eq!(weekly, Recurrence::Weekly(vec![Weekday::Tue, Weekday::Fri]));
+        assert_eq!(weekly.to_string(), "weekly tue, fri");
+        assert_eq!(Recurrence::parse(&weekly.to_string()).unwrap(), weekly);
+        assert!(weekly.matches(friday));
+        assert!(!weekly.matches(saturday));
+
+        // Day 31 falls on the last day of shorter months
+        let monthly = Recurrence::parse("monthly 31").unwrap();
+        assert!(monthly.matches(date(2025, 10, 31)));
+        assert!(monthly.matches(date(2025, 11, 30)));
+        assert!(monthly.matches(date(2026, 2, 28)));
+        assert!(!monthly.matches(date(2026, 2, 27)));
+        assert!(Recurrence::parse("monthly 15").unwrap().matches(date(2025, 11, 15)));
+
+        assert!(Recurrence::parse("weekly").is_err());
+        assert!(Recurrence::parse("weekly someday").is_err());
+        assert!(Recurrence::parse("monthly 32").is_err());
+        assert!(Recurrence::parse("hourly").is_err());
+    }
+
+    #[test]
+    fn test_instantiate_links_back_to_rule() {
+        let mut rule = RecurringTask::new("Standup".to_string(), Recurrence::Weekdays);
+        rule.tags = vec!["meeting".to_string()];
+        rule.estimate = Duration::minutes(15);
+
+        let first = rule.instantiate();
+        let second = rule.instantiate();
+        assert_eq!(first.recurring_id, Some(rule.id));
+        assert_eq!(first.tags, rule.tags);
+        assert_eq!(first.track.estimate, Duration::minutes(15));
+        assert_ne!(first.id, second.id);
+    }
+}
@@ -0,0 +1,121 @@
+use super::item::Item;
+use uuid::Uuid;
+
+/// Position of an item in the task tree: its index in the top-level list,
+/// then its index among its parent's subtasks at each level below
+pub type ItemPath = Vec<usize>;
+
+/// The item at a path
+pub fn item_at<'a>(items: &'a [Item], path: &[usize]) -> Option<&'a Item> {
+    let (first, rest) = path.split_first()?;
+    let mut item = items.get(*first)?;
+    for idx in rest {
+        item = item.subtasks.get(*idx)?;
+    }
+    Some(item)
+}
+
+/// The item at a path, mutably
+pub fn item_at_mut<'a>(items: &'a mut [Item], path: &[usize]) -> Option<&'a mut Item> {
+    let (first, rest) = path.split_first()?;
+    let mut item = items.get_mut(*first)?;
+    for idx in rest {
+        item = item.subtasks.get_mut(*idx)?;
+    }
+    Some(item)
+}
+
+/// The list holding the item at a path (the top-level list, or its parent's subtasks)
+pub fn siblings_mut<'a>(items: &'a mut Vec<Item>, path: &[usize]) -> Option<&'a mut Vec<Item>> {
+    let (_, parent) = path.split_last()?;
+    if parent.is_empty() {
+        Some(items)
+    } else {
+        item_at_mut(items, parent).map(|parent| &mut parent.subtasks)
+    }
+}
+
+/// Take the item at a path out of the tree (with its subtasks)
+pub fn remove_at(items: &mut Vec<Item>, path: &[usize]) -> Option<Item> {
+    let idx = *path.last()?;
+    let siblings = siblings_mut(items, path)?;
+    (idx < siblings.len()).then(|| siblings.remove(idx))
+}
+
+/// Path of the item with the given ID, at any depth
+pub fn find_path(items: &[Item], id: Uuid) -> Option<ItemPath> {
+    for (idx, item) in items.iter().enumerate() {
+        if item.id == id {
+            return Some(vec![idx]);
+        }
+        if let Some(mut path) = find_path(&item.subtasks, id) {
+            path.insert(0, idx);
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// The item with the given ID, at any depth
+pub fn find_item_mut(items: &mut [Item], id: Uuid) -> Option<&mut Item> {
+    let path = find_path(items, id)?;
+    item_at_mut(items, &path)
+}
+
+/// Every item in a list and below it, parents before their subtasks
+pub fn walk_items(items: &[Item]) -> impl Iterator<Item = &Item> {
+    items.iter().flat_map(|item| item.walk())
+}
+
+/// Apply `f` to every item in a list and below it, parents first
+pub fn walk_items_mut(items: &mut [Item], f: &mut dyn FnMut(&mut Item)) {
+    for item in items {
+        item.walk_mut(f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScheduleDay;
+    use chrono::Duration;
+
+    fn item(title: &str) -> Item {
+        Item::new(title.to_string(), Duration::hours(1), ScheduleDay::Today)
+    }
+
+    /// Epic > (Task > Step, Other task), Second epic
+    fn tree() -> Vec<Item> {
+        let mut task = item("Task");
+        task.add_subtask(item("Step"));
+        let mut epic = item("Epic");
+        epic.add_subtask(task);
+        epic.add_subtask(item("Other task"));
+        vec![epic, item("Second epic")]
+    }
+
+    #[test]
+    fn test_paths() {
+        let mut items = tree();
+        let step_id = items[0].subtasks[0].subtasks[0].id;
+
+        assert_eq!(find_path(&items, step_id), Some(vec![0, 0, 0]));
+        assert_eq!(item_at(&items, &[0, 0, 0]).unwrap().title, "Step");
+        assert_eq!(item_at(&items, &[0, 1]).unwrap().title, "Other task");
+        assert!(item_at(&items, &[0, 2]).is_none());
+        assert!(item_at(&items, &[]).is_none());
+
+        let titles: Vec<&str> = walk_items(&items).map(|item| item.title.as_str()).collect();
+        assert_eq!(titles, ["Epic", "Task", "Step", "Other task", "Second epic"]);
+
+        find_item_mut(&mut items, step_id).unwrap().title = "First step".to_string();
+        let step = remove_at(&mut items, &[0, 0, 0]).unwrap();
+        assert_eq!(step.title, "First step");
+        assert!(items[0].subtasks[0].subtasks.is_empty());
+        assert!(find_path(&items, step_id).is_none());
+
+        let second = remove_at(&mut items, &[1]).unwrap();
+        assert_eq!(second.title, "Second epic");
+        assert_eq!(items.len(), 1);
+    }
+}
@@ -1,74 +1,76 @@
+use super::enums::RunStatus;
 use super::item::Item;
+use super::tree::ItemPath;
 use chrono::Duration;
 
 /// A flattened row for rendering the task list
 #[derive(Debug, Clone)]
 pub struct FlatRow {
-    /// Index in the flattened list
-    pub index: usize,
-    /// Depth in the tree (0 = parent, 1 = subtask)
+    /// Depth in the tree (0 = top-level task, 1 = subtask, 2 = its subtask, ...)
     pub depth: usize,
     /// Whether this is the last subtask of its parent
     pub is_last: bool,
-    /// Reference to the item (stored as index into tasks array)
-    pub task_index: usize,
-    /// Subtask index (None for parent tasks)
-    pub subtask_index: Option<usize>,
+    /// Path to the item (see `ItemPath`)
+    pub path: ItemPath,
 }
 
 /// Flatten a hierarchical task list into a linear list for rendering
+/// (subtasks of collapsed items are left out). Waiting tasks are grouped
+/// after the others.
 pub fn flatten_tasks(tasks: &[Item]) -> Vec<FlatRow> {
     let mut rows = Vec::new();
-    let mut flat_index = 0;
-
-    for (task_idx, task) in tasks.iter().enumerate() {
-        // Add parent task
-        rows.push(FlatRow {
-            index: flat_index,
-            depth: 0,
-            is_last: false,
-            task_index: task_idx,
-            subtask_index: None,
-        });
-        flat_index += 1;
-
-        // Add subtasks if expanded
-        if task.expanded && !task.subtasks.is_empty() {
-            let subtask_count = task.subtasks.len();
-            for (st_idx, _subtask) in task.subtasks.iter().enumerate() {
-                rows.push(FlatRow {
-                    index: flat_index,
-                    depth: 1,
-                    is_last: st_idx == subtask_count - 1,
-                    task_index: task_idx,
-                    subtask_index: Some(st_idx),
-                });
-                flat_index += 1;
-            }
-        }
-    }
+    flatten_top_level(tasks, true, &mut rows);
+    rows
+}
 
+/// Flatten a task list including the subtasks of collapsed items
+pub fn flatten_all_tasks(tasks: &[Item]) -> Vec<FlatRow> {
+    let mut rows = Vec::new();
+    flatten_top_level(tasks, false, &mut rows);
     rows
 }
 
+fn flatten_top_level(tasks: &[Item], only_expanded: bool, rows: &mut Vec<FlatRow>) {
+    let (waiting, rest): (Vec<usize>, Vec<usize>) =
+        (0..tasks.len()).partition(|idx| tasks[*idx].status == RunStatus::Waiting);
+    for idx in rest.into_iter().chain(waiting) {
+        push_row(tasks, &[], idx, only_expanded, rows);
+    }
+}
+
+fn flatten_into(items: &[Item], parent: &[usize], only_expanded: bool, rows: &mut Vec<FlatRow>) {
+    for idx in 0..items.len() {
+        push_row(items, parent, idx, only_expanded, rows);
+    }
+}
+
+/// Add the row for `items[idx]`, then the rows below it
+fn push_row(items: &[Item], parent: &[usize], idx: usize, only_expanded: bool, rows: &mut Vec<FlatRow>) {
+    let item = &items[idx];
+    let mut path = parent.to_vec();
+    path.push(idx);
+    rows.push(FlatRow {
+        depth: parent.len(),
+        is_last: !parent.is_empty() && idx == items.len() - 1,
+        path: path.clone(),
+    });
+
+    if item.expanded || !only_expanded {
+        flatten_into(&item.subtasks, &path, only_expanded, rows);
+    }
+}
+
 /// Compute total elapsed and estimate for today's tasks
-/// Only counts leaf items (tasks without subtasks + all subtasks) to avoid double-counting
+/// Only counts leaf items (items without subtasks, at any depth) to avoid double-counting
 pub fn compute_totals(tasks: &[Item]) -> (Duration, Duration) {
     let mut total_elapsed = Duration::zero();
     let mut total_estimate = Duration::zero();
 
     for task in tasks {
-        // Only count the parent task if it has no subtasks (it's a leaf)
-        if task.subtasks.is_empty() {
-            total_elapsed = total_elapsed + task.track.elapsed;
-            total_estimate = total_estimate + task.track.estimate;
-        }
-
-        // Always count subtasks (they're always leaves)
-        for subtask in &task.subtasks {
-            total_elapsed = total_elapsed + subtask.track.elapsed;
-            total_estimate = total_estimate + subtask.track.estimate;
+        for leaf in task.walk().into_iter().filter(|item| item.subtasks.is_empty()) {
+            total_elapsed = total_elapsed + leaf.track.elapsed;
         }
+        total_estimate = total_estimate + task.rollup_estimate();
     }
 
     (total_elapsed, total_estimate)
@@ -119,14 +121,13 @@ pub fn garden_plant_state(percent: f64, use_emoji: bool) -> &'static str {
 
 /// Get status badge text
 pub fn status_badge(item: &Item) -> &'static str {
-    use super::enums::RunStatus;
-
     match item.status {
         RunStatus::Running => "⏱ RUNNING",
         RunStatus::Paused => "⏸ PAUSED",
         RunStatus::Idle => "🌿 IDLE",
         RunStatus::Done => "✓ DONE",
         RunStatus::Postponed => "→ TOMORROW",
+        RunStatus::Waiting => "⌛ WAITING",
     }
 }
 
@@ -158,9 +159,9 @@ mod tests {
         let rows = flatten_tasks(&tasks);
         assert_eq!(rows.len(), 2);
         assert_eq!(rows[0].depth, 0);
-        assert_eq!(rows[0].task_index, 0);
+        assert_eq!(rows[0].path[0], 0);
         assert_eq!(rows[1].depth, 0);
-        assert_eq!(rows[1].task_index, 1);
+        assert_eq!(rows[1].path[0], 1);
     }
 
     #[test]
@@ -192,6 +193,36 @@ mod tests {
         assert_eq!(rows.len(), 1); // Only parent, subtasks hidden
     }
 
+    #[test]
+    fn test_flatten_nested_tasks() {
+        let mut task = create_test_item("Task");
+        task.add_subtask(create_test_item("Step 1"));
+        task.add_subtask(create_test_item("Step 2"));
+        let mut epic = create_test_item("Epic");
+        epic.add_subtask(task);
+        epic.add_subtask(create_test_item("Other task"));
+
+        let tasks = vec![epic, create_test_item("Second epic")];
+        let rows = flatten_tasks(&tasks);
+        let paths: Vec<Vec<usize>> = rows.iter().map(|row| row.path.clone()).collect();
+        assert_eq!(paths, [vec![0], vec![0, 0], vec![0, 0, 0], vec![0, 0, 1], vec![0, 1], vec![1]]);
+        assert_eq!(rows[2].depth, 2);
+        assert!(rows[3].is_last);
+        assert!(!rows[1].is_last);
+        assert!(rows[4].is_last);
+        assert_eq!(rows[3].path[0], 0);
+
+        // Collapsing the middle level hides its steps
+        let mut tasks = tasks;
+        tasks[0].subtasks[0].expanded = false;
+        assert_eq!(flatten_tasks(&tasks).len(), 4);
+        assert_eq!(flatten_all_tasks(&tasks).len(), 6);
+
+        // Totals only count the leaves: 2 steps, "Other task" and "Second epic"
+        let (_, estimate) = compute_totals(&tasks);
+        assert_eq!(estimate, Duration::hours(4));
+    }
+
     #[test]
     fn test_compute_totals() {
         let mut task1 = create_test_item("Task 1");
@@ -1,7 +1,6 @@
-use crate::app::AppState;
-use crate::domain::{GlobalMode, UiMode};
+use crate::app::{AppState, AwayChoice, CrashGapChoice, ExternalChangeChoice, HistoryInput};
+use crate::domain::UiMode;
 use anyhow::Result;
-use chrono::Duration;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::env;
 use std::fs;
@@ -10,13 +9,21 @@ use tempfile::NamedTempFile;
 
 /// Handle keyboard input events
 pub fn handle_key(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    app.status_message = None;
     match app.ui_mode {
         UiMode::Normal => handle_normal_mode(app, key),
         UiMode::Modal => handle_modal_mode(app, key),
         UiMode::AddingTask | UiMode::AddingSubtask | UiMode::EditingTask => handle_input_form_mode(app, key),
         UiMode::IdleCheck => handle_idle_check_mode(app, key),
+        UiMode::IdleTrimmed => handle_idle_trimmed_mode(app, key),
         UiMode::EditingJournal => handle_journal_editing_mode(app, key),
         UiMode::ModeSelector => handle_mode_selector_mode(app, key),
+        UiMode::ExternalChange => handle_external_change_mode(app, key),
+        UiMode::AwayCheck => handle_away_check_mode(app, key),
+        UiMode::CrashRecovery => handle_crash_recovery_mode(app, key),
+        UiMode::EditingHistory => handle_history_editor_mode(app, key),
+        UiMode::PickingBlockers => handle_blocker_picker_mode(app, key),
+        UiMode::SettingWaiting => handle_waiting_form_mode(app, key),
         _ => Ok(false),
     }
 }
@@ -42,6 +49,24 @@ fn handle_normal_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
             Ok(false)
         }
 
+        // Promote/demote in the hierarchy (Shift+←/→ or Shift+Tab/Tab)
+        KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.promote_item();
+            Ok(false)
+        }
+        KeyCode::BackTab => {
+            app.promote_item();
+            Ok(false)
+        }
+        KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.demote_item();
+            Ok(false)
+        }
+        KeyCode::Tab => {
+            app.demote_item();
+            Ok(false)
+        }
+
         // Scroll done pane (when visible) using [ and ]
         KeyCode::Char('[') => {
             if app.show_done {
@@ -127,6 +152,12 @@ fn handle_normal_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
             Ok(false)
         }
 
+        // Redo the last undone action
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.redo()?;
+            Ok(false)
+        }
+
         // Postpone to tomorrow
         KeyCode::Char('p') | KeyCode::Char('P') => {
             app.postpone_to_tomorrow()?;
@@ -163,6 +194,24 @@ fn handle_normal_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
             Ok(false)
         }
 
+        // Edit the time history of the selected task/subtask
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            app.open_history_editor();
+            Ok(false)
+        }
+
+        // Put the selected task/subtask on hold (or take it off hold)
+        KeyCode::Char('w') | KeyCode::Char('W') => {
+            app.toggle_waiting();
+            Ok(false)
+        }
+
+        // Choose the tasks the selected task/subtask waits on
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            app.open_blocker_picker();
+            Ok(false)
+        }
+
         // Toggle expand/collapse
         KeyCode::Char(' ') => {
             app.toggle_expand();
@@ -220,8 +269,8 @@ fn handle_modal_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
 
         // Extend - show quick options
         KeyCode::Char('e') | KeyCode::Char('E') => {
-            // Quick extend by 30 minutes
-            app.modal_extend(Duration::minutes(30));
+            // Quick extend by the configured step
+            app.modal_extend(app.config.extend_step());
             Ok(false)
         }
 
@@ -270,6 +319,157 @@ fn handle_idle_check_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
     }
 }
 
+/// Handle keys in the prompt shown after the idle check timed out
+fn handle_idle_trimmed_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Char('k') | KeyCode::Char('K') => app.resolve_idle_trim(true),
+        KeyCode::Enter | KeyCode::Esc => app.resolve_idle_trim(false),
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keys in the away prompt (after suspend or a clock jump)
+fn handle_away_check_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    // Count and discard aren't letters, so they can't clash with a mode's hotkey
+    let choice = match key.code {
+        KeyCode::Enter => AwayChoice::Count,
+        KeyCode::Esc => AwayChoice::Discard,
+        // Any mode's hotkey assigns the time to that mode
+        KeyCode::Char(c) => match app.config.modes.by_key(c) {
+            Some(mode) => AwayChoice::Mode(mode),
+            None => return Ok(false),
+        },
+        _ => return Ok(false),
+    };
+    app.resolve_away(choice);
+    Ok(false)
+}
+
+/// Handle keys in the startup prompt for tasks left running by a crash
+fn handle_crash_recovery_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    let Some(gap) = app.crash_gap.as_mut() else {
+        return Ok(false);
+    };
+
+    // Typing the end time
+    if let Some(input) = gap.time_input.as_mut() {
+        match key.code {
+            KeyCode::Char(c) if (c.is_ascii_digit() || c == ':') && input.len() < 5 => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => gap.time_input = None,
+            KeyCode::Enter => {
+                if let Some(at) = app.crash_gap_input_time() {
+                    app.resolve_crash_gap(CrashGapChoice::At(at));
+                }
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    match key.code {
+        KeyCode::Char('h') | KeyCode::Char('H') => app.resolve_crash_gap(CrashGapChoice::Heartbeat),
+        KeyCode::Char('t') | KeyCode::Char('T') => gap.time_input = Some(String::new()),
+        KeyCode::Char('k') | KeyCode::Char('K') => app.resolve_crash_gap(CrashGapChoice::Keep),
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keys in the history editor
+fn handle_history_editor_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    let Some(editor) = app.history_editor.as_mut() else {
+        app.ui_mode = UiMode::Normal;
+        return Ok(false);
+    };
+
+    // Typing times
+    if let Some((_, input)) = editor.input.as_mut() {
+        match key.code {
+            KeyCode::Char(c) if (c.is_ascii_digit() || c == ':' || c == '-') && input.len() < 11 => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => editor.input = None,
+            KeyCode::Enter => app.submit_history_input(),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    match key.code {
+        KeyCode::Up => app.move_history_selection(false),
+        KeyCode::Down => app.move_history_selection(true),
+        KeyCode::Left => app.shift_history_session(false),
+        KeyCode::Right => app.shift_history_session(true),
+        KeyCode::Char('a') | KeyCode::Char('A') => app.start_history_input(HistoryInput::Add),
+        KeyCode::Char('e') | KeyCode::Char('E') => app.start_history_input(HistoryInput::Edit),
+        KeyCode::Char('s') | KeyCode::Char('S') => app.start_history_input(HistoryInput::Split),
+        KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => app.delete_history_session(),
+        KeyCode::Esc | KeyCode::Char('h') | KeyCode::Char('H') | KeyCode::Char('q') => app.close_history_editor(),
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keys in the waiting form
+fn handle_waiting_form_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    let Some(form) = app.waiting_form.as_mut() else {
+        app.ui_mode = UiMode::Normal;
+        return Ok(false);
+    };
+
+    match key.code {
+        KeyCode::Enter => app.submit_waiting_form(),
+        KeyCode::Esc => app.close_waiting_form(),
+        KeyCode::Tab | KeyCode::BackTab => form.editing_field = (form.editing_field + 1) % 2,
+        KeyCode::Backspace => {
+            if form.editing_field == 0 {
+                form.reason.pop();
+            } else {
+                form.follow_up.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if form.editing_field == 0 {
+                form.reason.push(c);
+            } else {
+                form.follow_up.push(c);
+            }
+            form.error = None;
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keys in the blocker picker
+fn handle_blocker_picker_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Up => app.move_blocker_selection(false),
+        KeyCode::Down => app.move_blocker_selection(true),
+        KeyCode::Enter | KeyCode::Char(' ') => app.toggle_selected_blocker(),
+        KeyCode::Esc | KeyCode::Char('b') | KeyCode::Char('B') | KeyCode::Char('q') => app.close_blocker_picker(),
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keys in the external change prompt
+fn handle_external_change_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
+    let choice = match key.code {
+        KeyCode::Char('r') | KeyCode::Char('R') => ExternalChangeChoice::Reload,
+        KeyCode::Char('m') | KeyCode::Char('M') => ExternalChangeChoice::Merge,
+        KeyCode::Char('o') | KeyCode::Char('O') => ExternalChangeChoice::Overwrite,
+        _ => return Ok(false),
+    };
+    app.resolve_external_change(choice)?;
+    Ok(false)
+}
+
 /// Handle keys in input form mode (adding task/subtask)
 fn handle_input_form_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
     match key.code {
@@ -433,34 +633,36 @@ fn handle_journal_editing_mode(app: &mut AppState, key: KeyEvent) -> Result<bool
 
 /// Handle keys in mode selector mode
 fn handle_mode_selector_mode(app: &mut AppState, key: KeyEvent) -> Result<bool> {
-    match key.code {
-        // Number keys to select mode
-        KeyCode::Char('1') => {
-            app.set_global_mode(GlobalMode::Working);
-            Ok(false)
-        }
-        KeyCode::Char('2') => {
-            app.set_global_mode(GlobalMode::Break);
-            Ok(false)
-        }
-        KeyCode::Char('3') => {
-            app.set_global_mode(GlobalMode::Lunch);
-            Ok(false)
-        }
-        KeyCode::Char('4') => {
-            app.set_global_mode(GlobalMode::Gym);
-            Ok(false)
-        }
-        KeyCode::Char('5') => {
-            app.set_global_mode(GlobalMode::Dinner);
-            Ok(false)
+    // Typing the "since HH:MM" time for a backdated switch
+    if app.editing_mode_since {
+        let since = app.mode_since.get_or_insert_with(String::new);
+        match key.code {
+            KeyCode::Char(c) if (c.is_ascii_digit() || c == ':') && since.len() < 5 => since.push(c),
+            KeyCode::Backspace => {
+                since.pop();
+            }
+            KeyCode::Enter | KeyCode::Tab => app.editing_mode_since = false,
+            KeyCode::Esc => {
+                app.mode_since = None;
+                app.editing_mode_since = false;
+            }
+            _ => {}
         }
-        KeyCode::Char('6') => {
-            app.set_global_mode(GlobalMode::Personal);
+        return Ok(false);
+    }
+
+    match key.code {
+        // Each mode's hotkey (1-9 by default) selects it
+        KeyCode::Char(c) => {
+            if let Some(mode) = app.config.modes.by_key(c) {
+                app.select_mode(mode);
+            }
             Ok(false)
         }
-        KeyCode::Char('7') => {
-            app.set_global_mode(GlobalMode::Sleep);
+
+        // Backdate the switch ("I went to lunch at 12:30")
+        KeyCode::Tab => {
+            app.editing_mode_since = true;
             Ok(false)
         }
 
@@ -514,7 +716,9 @@ fn edit_notes_external(app: &mut AppState) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Config;
     use crate::domain::{Item, ScheduleDay};
+    use chrono::Duration;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
     fn create_test_app() -> AppState {
@@ -523,7 +727,10 @@ mod tests {
             Duration::hours(1),
             ScheduleDay::Today,
         );
-        AppState::new(vec![task], Vec::new(), Vec::new(), String::new())
+        let mut app = AppState::new(vec![task], Vec::new(), Vec::new(), String::new(), Config::default());
+        // Don't send real notifications from tests
+        app.notifier = Box::new(crate::notifications::NoopNotifier);
+        app
     }
 
     fn key(code: KeyCode) -> KeyEvent {
@@ -544,6 +751,36 @@ mod tests {
         assert_eq!(app.selected_index, 0);
     }
 
+    #[test]
+    fn test_away_check_mode_keys_win_over_letters() {
+        use crate::app::AwayGap;
+        use crate::domain::GlobalMode;
+
+        let mut app = create_test_app();
+        app.config.modes = serde_json::from_str(
+            r#"[{"name": "Coding", "key": "c", "pause_timers": false}, {"name": "Dinner", "key": "d"}]"#,
+        )
+        .unwrap();
+        let now = chrono::Local::now();
+        app.global_mode = GlobalMode::new("Coding");
+        app.mode_timeline.segments.clear();
+        app.mode_timeline.begin(GlobalMode::new("Coding"), now - Duration::hours(2));
+
+        // 'd' is Dinner's hotkey, not "discard"
+        app.away_gap = Some(AwayGap { from: now - Duration::hours(1), to: now });
+        app.ui_mode = UiMode::AwayCheck;
+        handle_key(&mut app, key(KeyCode::Char('d'))).unwrap();
+        assert_eq!(app.ui_mode, UiMode::Normal);
+        assert!(app.mode_timeline.totals().iter().any(|(mode, _)| *mode == GlobalMode::new("Dinner")));
+
+        // Enter counts the time for the current mode
+        app.away_gap = Some(AwayGap { from: now - Duration::hours(1), to: now });
+        app.ui_mode = UiMode::AwayCheck;
+        handle_key(&mut app, key(KeyCode::Enter)).unwrap();
+        assert_eq!(app.ui_mode, UiMode::Normal);
+        assert!(!app.mode_timeline.totals().iter().any(|(mode, _)| *mode == GlobalMode::new("Dinner")));
+    }
+
     #[test]
     fn test_handle_quit() {
         let mut app = create_test_app();
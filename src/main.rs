@@ -1,4 +1,7 @@
 mod app;
+mod cli;
+mod config;
+mod day;
 mod domain;
 mod input;
 mod notifications;
@@ -34,12 +37,91 @@ enum Commands {
     /// Generate a daily report with statistics
     Report {
         /// Date to generate report for (YYYY-MM-DD format). Defaults to today.
+        /// With --week or --month, selects the week or month containing this date.
         #[arg(short, long)]
         date: Option<String>,
-        /// Output file path. Defaults to ~/.centre/report-YYYY-MM-DD.md
+        /// Start of a multi-day report (YYYY-MM-DD format)
+        #[arg(long, requires = "to", conflicts_with_all = ["week", "month"])]
+        from: Option<String>,
+        /// End of a multi-day report (YYYY-MM-DD format, inclusive)
+        #[arg(long, requires = "from", conflicts_with_all = ["week", "month"])]
+        to: Option<String>,
+        /// Report on the whole week (Monday to Sunday)
+        #[arg(long, conflicts_with = "month")]
+        week: bool,
+        /// Report on the whole calendar month
+        #[arg(long)]
+        month: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = report::ReportFormat::Md)]
+        format: report::ReportFormat,
+        /// Output file path. Defaults to ~/.centre/report-YYYY-MM-DD.<format>
         #[arg(short, long)]
         output: Option<String>,
     },
+    /// Add a task (prints the new task's ID)
+    Add {
+        /// Task title
+        title: String,
+        /// Estimate, e.g. 1.5h, 90m or 1h30m. Defaults to 1h.
+        #[arg(long)]
+        est: Option<String>,
+        /// Comma-separated tags, e.g. work,urgent
+        #[arg(long)]
+        tags: Option<String>,
+        /// Add as a subtask of this task (ID or unique ID prefix)
+        #[arg(long)]
+        parent: Option<String>,
+        /// Comma-separated IDs (or unique ID prefixes) of tasks that must be done first
+        #[arg(long)]
+        blocked_by: Option<String>,
+    },
+    /// Start (or resume) a task
+    Start {
+        /// Task ID or unique ID prefix
+        id: String,
+    },
+    /// Pause a task, or all running tasks if no ID is given
+    Pause {
+        /// Task ID or unique ID prefix
+        id: Option<String>,
+    },
+    /// Mark a task as done
+    Done {
+        /// Task ID or unique ID prefix
+        id: String,
+    },
+    /// Move a task to tomorrow
+    Postpone {
+        /// Task ID or unique ID prefix
+        id: String,
+    },
+    /// Put a task on hold, waiting on someone or something else
+    Wait {
+        /// Task ID or unique ID prefix
+        id: String,
+        /// What the task is waiting on
+        #[arg(short, long)]
+        reason: Option<String>,
+        /// When to follow up (YYYY-MM-DD, "tomorrow" or "+N" days)
+        #[arg(short, long)]
+        follow_up: Option<String>,
+    },
+    /// Regenerate a day's Markdown file by replaying its event log
+    Rebuild {
+        /// Date to rebuild (YYYY-MM-DD format). Defaults to today.
+        #[arg(short, long)]
+        date: Option<String>,
+    },
+    /// List today's active tasks
+    List {
+        /// Print tasks as JSON
+        #[arg(long)]
+        json: bool,
+        /// Include done and archived tasks
+        #[arg(long)]
+        all: bool,
+    },
 }
 
 fn main() -> Result<()> {
@@ -55,22 +137,53 @@ fn main() -> Result<()> {
             println!("Run 'centre' to start tracking tasks.");
             Ok(())
         }
-        Some(Commands::Report { date, output }) => {
-            // Generate daily report
+        Some(Commands::Report { date, from, to, week, month, format, output }) => {
+            // The default date depends on `[day] starts_at`
+            let config = config::load_config()?;
+            let output_path = output.map(std::path::PathBuf::from);
+
             let report_date = if let Some(date_str) = date {
-                chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-                    .map_err(|e| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD: {}", e))?
+                parse_date(&date_str)?
             } else {
-                chrono::Local::now().date_naive()
+                day::today(config.day_start())
             };
 
-            let output_path = output.map(std::path::PathBuf::from);
+            let range = if let (Some(from), Some(to)) = (from, to) {
+                // Multi-day report
+                report::ReportRange::new(parse_date(&from)?, parse_date(&to)?)?
+            } else if week {
+                report::ReportRange::week_of(report_date)
+            } else if month {
+                report::ReportRange::month_of(report_date)
+            } else {
+                // Daily report
+                report::ReportRange::single(report_date)
+            };
 
-            println!("Generating report for {}...", report_date);
-            let report_path = report::generate_report(Some(report_date), output_path)?;
+            if range.from == range.to {
+                println!("Generating report for {}...", range.from);
+            } else {
+                println!("Generating report for {} to {}...", range.from, range.to);
+            }
+            let report_path = report::write_report(&range, format, output_path, &config.modes)?;
             println!("Report generated: {}", report_path.display());
             Ok(())
         }
+        Some(Commands::Add { title, est, tags, parent, blocked_by }) => cli::add(title, est, tags, parent, blocked_by),
+        Some(Commands::Start { id }) => cli::start(id),
+        Some(Commands::Pause { id }) => cli::pause(id),
+        Some(Commands::Done { id }) => cli::done(id),
+        Some(Commands::Postpone { id }) => cli::postpone(id),
+        Some(Commands::Wait { id, reason, follow_up }) => cli::wait(id, reason, follow_up),
+        Some(Commands::Rebuild { date }) => {
+            let date = match date {
+                Some(date_str) => parse_date(&date_str)?,
+                // The default date depends on `[day] starts_at`
+                None => day::today(config::load_config()?.day_start()),
+            };
+            cli::rebuild(date)
+        }
+        Some(Commands::List { json, all }) => cli::list(json, all),
         None => {
             // Run the normal TUI application
             run_tui()
@@ -78,6 +191,12 @@ fn main() -> Result<()> {
     }
 }
 
+/// Parse a YYYY-MM-DD command line date
+fn parse_date(date_str: &str) -> Result<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|e| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD: {}", e))
+}
+
 fn run_tui() -> Result<()> {
     // Ensure centre directory exists
     ensure_centre_dir()?;
@@ -86,17 +205,26 @@ fn run_tui() -> Result<()> {
     let centre_dir = get_centre_dir()?;
     eprintln!("Using centre directory: {}", centre_dir.display());
 
+    // Load configuration (global config.toml, overridden by a local one)
+    let config = config::load_config()?;
+
     // Load and migrate tasks (uses new daily file format)
-    let (tasks, done_today, archived_today) = load_and_migrate()?;
+    let (tasks, done_today, archived_today) = load_and_migrate(&config)?;
 
     // Load journal
-    let journal_content = match std::fs::read_to_string(journal_file()?) {
+    let journal_content = match std::fs::read_to_string(journal_file(config.day_start())?) {
         Ok(content) => content,
         Err(_) => String::new(), // Empty journal if file doesn't exist
     };
 
     // Create app state
-    let mut app = AppState::new(tasks, done_today, archived_today, journal_content);
+    let mut app = AppState::new(tasks, done_today, archived_today, journal_content, config);
+
+    // Bring back waiting tasks that are due for a follow-up
+    app.resurface_waiting();
+
+    // Ask about tasks left running if the last session crashed
+    app.detect_crash_gap();
 
     // Setup terminal
     enable_raw_mode()?;
@@ -116,11 +244,14 @@ fn run_tui() -> Result<()> {
     // Move all tasks to idle on exit (to track state properly)
     app.auto_idle_all();
 
-    // Save on exit
-    if let Err(e) = app.save() {
+    // Clean exit: nothing is left running, so drop the heartbeat
+    app.heartbeat = None;
+
+    // Save on exit (merging with any external changes, since we can't prompt anymore)
+    if let Err(e) = app.save_merging() {
         eprintln!("Error saving state: {}", e);
     }
-    if let Err(e) = app.save_journal() {
+    if let Err(e) = app.save_journal_merging() {
         eprintln!("Error saving journal: {}", e);
     }
 
@@ -133,21 +264,18 @@ fn run_tui() -> Result<()> {
 }
 
 fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut AppState) -> Result<()> {
-    let tick_rate = ticker::tick_duration();
+    let tick_rate = app.config.tick_duration();
 
     loop {
-        // Check for midnight crossing - force restart
-        if app.has_day_changed() {
-            // Generate report for the day that just passed
-            let yesterday = app.file_date; // The date we were tracking
-            if let Err(e) = report::generate_report(Some(yesterday), None) {
-                eprintln!("Warning: Failed to generate report for {}: {}", yesterday, e);
-            } else {
-                eprintln!("Generated report for {}", yesterday);
-            }
-
-            // Show modal forcing user to restart
-            app.ui_mode = domain::UiMode::DayChanged;
+        // Roll over to the new day after midnight (once any form or prompt is
+        // closed); a failure shows in the status bar and is retried later
+        if app.has_day_changed()
+            && app.ui_mode == domain::UiMode::Normal
+            && app.external_change.is_none()
+            && app.try_rollover_day()
+        {
+            // Full redraw, as report warnings may have been printed over the screen
+            terminal.clear()?;
         }
 
         // Render
@@ -158,13 +286,7 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut AppS
             if let Event::Key(key) = event::read()? {
                 // Only process key press events (ignore key release)
                 if key.kind == KeyEventKind::Press {
-                    // If day changed, only allow quit
-                    if app.ui_mode == domain::UiMode::DayChanged {
-                        if key.code == event::KeyCode::Char('q') || key.code == event::KeyCode::Esc {
-                            return Ok(());
-                        }
-                        continue; // Ignore all other keys
-                    }
+                    app.record_input();
 
                     // Handle notes editing specially - need to disable raw mode
                     if app.ui_mode == domain::UiMode::Normal
@@ -199,6 +321,14 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut AppS
             }
         }
 
+        // Pick up external edits to today's files (full redraw, as the parser
+        // may have printed warnings over the screen). A file that can't be
+        // read right now is tried again on the next pass; the failure shows in
+        // the status bar.
+        if app.try_reload_external_changes() {
+            terminal.clear()?;
+        }
+
         // Tick timers
         app.tick();
 
@@ -215,5 +345,8 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut AppS
         if app.journal_needs_save {
             app.save_journal()?;
         }
+
+        // Let the next start know whether this session ended cleanly
+        app.write_heartbeat()?;
     }
 }
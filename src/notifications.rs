@@ -1,49 +1,210 @@
-/// Cross-platform notification support
-/// Currently only implements macOS notifications
+//! Notification backends
+//!
+//! The backend is chosen with `[notifications] backend` in config.toml:
+//! desktop notifications (macOS Notification Center or the freedesktop
+//! `org.freedesktop.Notifications` D-Bus service on Linux), a user command,
+//! the terminal bell, or nothing.
 
-#[cfg(target_os = "macos")]
-use std::process::Command;
+use crate::config::NotificationConfig;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Command, Stdio};
 
-/// Send a notification when a task is completed
-pub fn notify_task_done(task_title: &str) {
-    #[cfg(target_os = "macos")]
-    {
-        let script = format!(
-            r#"display notification "{}" with title "Centre - Task Completed""#,
-            task_title.replace('"', "\\\"")
-        );
+/// Something worth telling the user about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notification {
+    TaskDone(String),
+    EstimateReached(String),
+    IdleCheck,
+    ModeChanged(String),
+    /// A waiting task's follow-up date has come
+    FollowUp(String),
+}
+
+impl Notification {
+    /// Short machine-readable event name (passed to command hooks)
+    pub fn event(&self) -> &'static str {
+        match self {
+            Self::TaskDone(_) => "task_done",
+            Self::EstimateReached(_) => "estimate_reached",
+            Self::IdleCheck => "idle_check",
+            Self::ModeChanged(_) => "mode_changed",
+            Self::FollowUp(_) => "follow_up",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::TaskDone(_) => "Centre - Task Completed",
+            Self::EstimateReached(_) => "Centre - Estimate Reached",
+            Self::IdleCheck => "Centre - Still working?",
+            Self::ModeChanged(_) => "Centre - Mode Changed",
+            Self::FollowUp(_) => "Centre - Time to Follow Up",
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            Self::TaskDone(title) => title.clone(),
+            Self::EstimateReached(title) => format!("⏰ {}", title),
+            Self::IdleCheck => "Confirm you're still working, or running tasks will be paused".to_string(),
+            Self::ModeChanged(mode) => mode.clone(),
+            Self::FollowUp(title) => format!("⌛ {}", title),
+        }
+    }
+}
+
+/// A way of delivering notifications
+pub trait Notifier {
+    /// Deliver a notification; failures are ignored so they never disturb the UI
+    fn notify(&self, notification: &Notification);
+}
+
+/// Notifier backend selected in config.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifierBackend {
+    /// macOS Notification Center, or freedesktop notifications on Linux
+    #[default]
+    Desktop,
+    /// Run `notifications.command` through the shell
+    Command,
+    /// Ring the terminal bell
+    Bell,
+    /// No notifications
+    None,
+}
 
-        let _ = Command::new("osascript")
-            .arg("-e")
-            .arg(&script)
-            .output();
+/// Build the notifier described by the configuration
+pub fn notifier_from_config(config: &NotificationConfig) -> Box<dyn Notifier> {
+    match config.backend {
+        NotifierBackend::Desktop => Box::new(DesktopNotifier),
+        NotifierBackend::Command => Box::new(CommandNotifier {
+            command: config.command.clone().unwrap_or_default(),
+        }),
+        NotifierBackend::Bell => Box::new(BellNotifier),
+        NotifierBackend::None => Box::new(NoopNotifier),
     }
+}
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        // No-op on other platforms
-        let _ = task_title;
+/// Start a command without blocking or touching the terminal
+///
+/// The child is reaped on a separate thread; it keeps running if Centre
+/// exits first (e.g. after a one-shot CLI command).
+fn spawn_detached(mut command: Command) {
+    command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    if let Ok(mut child) = command.spawn() {
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
     }
 }
 
-/// Send a notification when a task estimate is reached
-pub fn notify_estimate_reached(task_title: &str) {
+/// Native desktop notifications
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
     #[cfg(target_os = "macos")]
-    {
+    fn notify(&self, notification: &Notification) {
         let script = format!(
-            r#"display notification "⏰ {}" with title "Centre - Estimate Reached""#,
-            task_title.replace('"', "\\\"")
+            r#"display notification "{}" with title "{}""#,
+            notification.body().replace('"', "\\\""),
+            notification.title()
         );
 
-        let _ = Command::new("osascript")
-            .arg("-e")
-            .arg(&script)
-            .output();
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(script);
+        spawn_detached(command);
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        // No-op on other platforms
-        let _ = task_title;
+    #[cfg(target_os = "linux")]
+    fn notify(&self, notification: &Notification) {
+        // org.freedesktop.Notifications.Notify(app_name, replaces_id, app_icon,
+        // summary, body, actions, hints, expire_timeout), via gdbus
+        let mut command = Command::new("gdbus");
+        command.args([
+            "call",
+            "--session",
+            "--dest=org.freedesktop.Notifications",
+            "--object-path=/org/freedesktop/Notifications",
+            "--method=org.freedesktop.Notifications.Notify",
+            "Centre",
+            "0",
+            "",
+        ]);
+        command.arg(notification.title());
+        command.arg(notification.body());
+        command.args(["[]", "{}", "-1"]);
+        spawn_detached(command);
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    fn notify(&self, notification: &Notification) {
+        // No desktop notifications on other platforms
+        let _ = notification;
+    }
+}
+
+/// Runs a user command for each notification
+///
+/// The command is run with `sh -c` and gets the notification in the
+/// `CENTRE_EVENT`, `CENTRE_TITLE` and `CENTRE_BODY` environment variables.
+pub struct CommandNotifier {
+    pub command: String,
+}
+
+impl CommandNotifier {
+    fn command_for(&self, notification: &Notification) -> Command {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(&self.command)
+            .env("CENTRE_EVENT", notification.event())
+            .env("CENTRE_TITLE", notification.title())
+            .env("CENTRE_BODY", notification.body());
+        command
+    }
+}
+
+impl Notifier for CommandNotifier {
+    fn notify(&self, notification: &Notification) {
+        spawn_detached(self.command_for(notification));
+    }
+}
+
+/// Rings the terminal bell
+pub struct BellNotifier;
+
+impl Notifier for BellNotifier {
+    fn notify(&self, _notification: &Notification) {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x07");
+        let _ = stdout.flush();
+    }
+}
+
+/// Discards notifications
+pub struct NoopNotifier;
+
+impl Notifier for NoopNotifier {
+    fn notify(&self, _notification: &Notification) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_command_notifier_passes_notification_in_env() {
+        let notifier = CommandNotifier {
+            command: "notify-me".to_string(),
+        };
+        let command = notifier.command_for(&Notification::EstimateReached("Write docs".to_string()));
+
+        let envs: Vec<(&OsStr, Option<&OsStr>)> = command.get_envs().collect();
+        assert!(envs.contains(&(OsStr::new("CENTRE_EVENT"), Some(OsStr::new("estimate_reached")))));
+        assert!(envs.contains(&(OsStr::new("CENTRE_BODY"), Some(OsStr::new("⏰ Write docs")))));
+        assert_eq!(command.get_args().collect::<Vec<_>>(), vec!["-c", "notify-me"]);
     }
 }
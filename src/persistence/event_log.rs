@@ -0,0 +1,574 @@
+use super::files::atomic_write;
+use super::merge::DailyItems;
+use super::parser::parse_daily_file;
+use super::serializer::{item_fingerprint, serialize_daily_file_with_date};
+use crate::domain::{walk_items, GlobalMode, Item, RunStatus};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Section of the daily file an item lives in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    Active,
+    Done,
+    Archived,
+}
+
+impl Section {
+    const ALL: [Section; 3] = [Section::Active, Section::Done, Section::Archived];
+
+    fn header(self) -> &'static str {
+        match self {
+            Section::Active => "## ACTIVE",
+            Section::Done => "## DONE",
+            Section::Archived => "## ARCHIVED",
+        }
+    }
+
+    fn list(self, items: &DailyItems) -> &Vec<Item> {
+        match self {
+            Section::Active => &items.0,
+            Section::Done => &items.1,
+            Section::Archived => &items.2,
+        }
+    }
+
+    fn list_mut(self, items: &mut DailyItems) -> &mut Vec<Item> {
+        match self {
+            Section::Active => &mut items.0,
+            Section::Done => &mut items.1,
+            Section::Archived => &mut items.2,
+        }
+    }
+}
+
+/// What the user did, as far as it can be told from the change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventAction {
+    Create,
+    Start,
+    Pause,
+    Done,
+    Archive,
+    /// Moved back to ACTIVE from DONE or ARCHIVED
+    Restore,
+    Estimate,
+    /// Title, notes or tags changed
+    Edit,
+    /// Reordered
+    Move,
+    /// Deleted or postponed
+    Remove,
+    Mode,
+    /// Any other change (e.g. edited time history)
+    Update,
+}
+
+/// The state change an event applies when replayed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "lowercase")]
+pub enum Change {
+    /// A task (with its subtasks) as it is after the event, placed right
+    /// after `after` in its section (first if None)
+    Item {
+        section: Section,
+        after: Option<Uuid>,
+        item: String,
+    },
+    Removed { id: Uuid },
+    Mode { mode: GlobalMode },
+}
+
+/// One line of events-YYYY-MM-DD.jsonl
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub at: DateTime<Local>,
+    pub action: EventAction,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+/// A day's state as rebuilt from its event log
+#[derive(Debug, Clone, Default)]
+pub struct LoggedDay {
+    pub items: DailyItems,
+    pub mode: Option<GlobalMode>,
+}
+
+impl LoggedDay {
+    /// Apply one event
+    pub fn apply(&mut self, event: &Event) -> Result<()> {
+        match &event.change {
+            Change::Item { section, after, item } => {
+                let content = format!("{}\n\n{}", section.header(), item);
+                let parsed = parse_daily_file(&content)?;
+                let Some(mut item) = section.list(&parsed).first().cloned() else {
+                    bail!("Event at {} holds no task", event.at.to_rfc3339());
+                };
+                item.sync_elapsed_from_history();
+
+                self.remove(item.id);
+                let list = section.list_mut(&mut self.items);
+                let idx = match after {
+                    Some(after) => list
+                        .iter()
+                        .position(|other| other.id == *after)
+                        .map_or(list.len(), |idx| idx + 1),
+                    None => 0,
+                };
+                list.insert(idx, item);
+            }
+            Change::Removed { id } => self.remove(*id),
+            Change::Mode { mode } => self.mode = Some(mode.clone()),
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, id: Uuid) {
+        for section in Section::ALL {
+            section.list_mut(&mut self.items).retain(|item| item.id != id);
+        }
+    }
+}
+
+/// Rebuild a day by replaying its events in order
+pub fn replay(events: &[Event]) -> Result<LoggedDay> {
+    let mut day = LoggedDay::default();
+    for event in events {
+        day.apply(event)?;
+    }
+    Ok(day)
+}
+
+/// Load a day's events (none if the file doesn't exist)
+pub fn load_events<P: AsRef<Path>>(path: P) -> Result<Vec<Event>> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+    let mut events = Vec::with_capacity(lines.len());
+    for (idx, line) in lines.iter().enumerate() {
+        match serde_json::from_str(line) {
+            Ok(event) => events.push(event),
+            // A last line cut short by a crash mid-append is dropped
+            Err(_) if idx + 1 == lines.len() && !content.ends_with('\n') => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Invalid event on line {} of {}", idx + 1, path.display()))
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// Append events to a day's log
+///
+/// A last line cut short by a crash mid-append is cut off first, so the new
+/// events don't get glued onto it.
+pub fn append_events<P: AsRef<Path>>(path: P, events: &[Event]) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    if events.is_empty() {
+        return Ok(());
+    }
+    let path = path.as_ref();
+
+    let mut lines = String::new();
+    for event in events {
+        lines.push_str(&serde_json::to_string(event)?);
+        lines.push('\n');
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let len = file.seek(SeekFrom::End(0))?;
+    if len > 0 {
+        let mut last = [0u8];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            let mut content = Vec::new();
+            file.seek(SeekFrom::Start(0))?;
+            file.read_to_end(&mut content)?;
+            let complete = content.iter().rposition(|byte| *byte == b'\n').map_or(0, |idx| idx + 1);
+            file.set_len(complete as u64)
+                .with_context(|| format!("Failed to cut the partial last line of {}", path.display()))?;
+        }
+        file.seek(SeekFrom::End(0))?;
+    }
+    file.write_all(lines.as_bytes())
+        .with_context(|| format!("Failed to append to {}", path.display()))?;
+    Ok(())
+}
+
+/// Item text compared to tell whether it changed (elapsed time is left out,
+/// as it follows from the history and grows while a timer runs)
+fn comparable(item: &Item) -> String {
+    let mut item = item.clone();
+    item.walk_mut(&mut |item| item.track.elapsed = chrono::Duration::zero());
+    item_fingerprint(&item)
+}
+
+/// Whether two days hold the same items in the same places, elapsed times
+/// aside (see `comparable`)
+pub fn same_items(a: &DailyItems, b: &DailyItems) -> bool {
+    Section::ALL.iter().all(|section| {
+        let (a, b) = (section.list(a), section.list(b));
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| comparable(a) == comparable(b))
+    })
+}
+
+/// Tell what kind of change turned `before` into `after` (same task), given
+/// the IDs of every item (at any depth) before and after the change, so
+/// subtasks moved in from, or out to, another task count as moves
+fn classify(before: &Item, after: &Item, day_before: &HashSet<Uuid>, day_after: &HashSet<Uuid>) -> EventAction {
+    let before_items: HashMap<Uuid, &Item> = before.walk().into_iter().map(|item| (item.id, item)).collect();
+    let after_ids: HashSet<Uuid> = after.walk().iter().map(|item| item.id).collect();
+
+    for item in after.walk() {
+        let Some(old) = before_items.get(&item.id) else {
+            if day_before.contains(&item.id) {
+                return EventAction::Move;
+            }
+            return EventAction::Create;
+        };
+        if old.status != item.status {
+            return match item.status {
+                RunStatus::Running => EventAction::Start,
+                RunStatus::Done => EventAction::Done,
+                _ if old.status == RunStatus::Running => EventAction::Pause,
+                _ => EventAction::Update,
+            };
+        }
+        if old.track.estimate != item.track.estimate {
+            return EventAction::Estimate;
+        }
+        if old.title != item.title || old.notes != item.notes || old.tags != item.tags || old.blocked_by != item.blocked_by {
+            return EventAction::Edit;
+        }
+    }
+    if let Some(id) = before_items.keys().find(|id| !after_ids.contains(*id)) {
+        if day_after.contains(id) {
+            return EventAction::Move;
+        }
+        return EventAction::Remove;
+    }
+    EventAction::Update
+}
+
+/// Positions (in `after`) of the items that keep their relative order from
+/// `before`, i.e. a longest common subsequence of the two ID lists
+fn unmoved(before: &[Uuid], after: &[Uuid]) -> HashSet<usize> {
+    let (n, m) = (before.len(), after.len());
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if before[i] == after[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut kept = HashSet::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if before[i] == after[j] {
+            kept.insert(j);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    kept
+}
+
+/// IDs of every item in a day, at any depth
+fn all_ids(items: &DailyItems) -> HashSet<Uuid> {
+    Section::ALL
+        .iter()
+        .flat_map(|section| walk_items(section.list(items)).map(|item| item.id))
+        .collect()
+}
+
+/// Events that turn the logged state into `current`
+pub fn diff_events(logged: &LoggedDay, current: &DailyItems, mode: Option<&GlobalMode>, at: DateTime<Local>) -> Vec<Event> {
+    let mut before: HashMap<Uuid, (Section, &Item)> = HashMap::new();
+    for section in Section::ALL {
+        for item in section.list(&logged.items) {
+            before.insert(item.id, (section, item));
+        }
+    }
+    let current_ids: HashSet<Uuid> = Section::ALL
+        .iter()
+        .flat_map(|section| section.list(current).iter().map(|item| item.id))
+        .collect();
+    let day_before = all_ids(&logged.items);
+    let day_after = all_ids(current);
+
+    let mut events = Vec::new();
+    for section in Section::ALL {
+        let list = section.list(current);
+        let old_order: Vec<Uuid> = section
+            .list(&logged.items)
+            .iter()
+            .map(|item| item.id)
+            .filter(|id| list.iter().any(|item| item.id == *id))
+            .collect();
+        let new_order: Vec<Uuid> = list.iter().map(|item| item.id).collect();
+        let in_place = unmoved(&old_order, &new_order);
+
+        for (idx, item) in list.iter().enumerate() {
+            let action = match before.get(&item.id) {
+                None if day_before.contains(&item.id) => EventAction::Move,
+                None => EventAction::Create,
+                Some((old_section, _)) if *old_section != section => match section {
+                    Section::Active => EventAction::Restore,
+                    Section::Done => EventAction::Done,
+                    Section::Archived => EventAction::Archive,
+                },
+                Some((_, old)) if comparable(old) != comparable(item) => classify(old, item, &day_before, &day_after),
+                Some(_) if !in_place.contains(&idx) => EventAction::Move,
+                Some(_) => continue,
+            };
+            events.push(Event {
+                at,
+                action,
+                change: Change::Item {
+                    section,
+                    after: idx.checked_sub(1).map(|prev| list[prev].id),
+                    item: item_fingerprint(item),
+                },
+            });
+        }
+    }
+
+    for section in Section::ALL {
+        for item in section.list(&logged.items) {
+            if !current_ids.contains(&item.id) {
+                // A task made a subtask of another is still there
+                let action = if day_after.contains(&item.id) { EventAction::Move } else { EventAction::Remove };
+                events.push(Event {
+                    at,
+                    action,
+                    change: Change::Removed { id: item.id },
+                });
+            }
+        }
+    }
+
+    if let Some(mode) = mode.filter(|mode| logged.mode.as_ref() != Some(*mode)) {
+        events.push(Event {
+            at,
+            action: EventAction::Mode,
+            change: Change::Mode { mode: mode.clone() },
+        });
+    }
+
+    events
+}
+
+/// Log what changed since the day's last event and write the daily file
+/// regenerated from the log (caller holds the centre lock)
+///
+/// Changes made outside the app (hand edits, carried-over tasks) show up
+/// as events at the next write. Returns the daily file content.
+pub fn record_day(date: NaiveDate, current: &DailyItems, mode: Option<&GlobalMode>) -> Result<String> {
+    DayRecorder::default().record(date, current, mode)
+}
+
+/// A day's replayed event log kept between writes, so a long-running
+/// session only diffs against it instead of replaying the file every save
+#[derive(Debug, Clone, Default)]
+pub struct DayRecorder {
+    /// Day `day` was replayed for (None until the first write, or after a
+    /// failed one)
+    date: Option<NaiveDate>,
+    day: LoggedDay,
+    /// Size of the events file `day` covers; if it changed, someone else
+    /// (e.g. the CLI) appended events and the log is replayed again
+    len: u64,
+}
+
+impl DayRecorder {
+    /// Like `record_day`, reusing the replayed log from the last call
+    pub fn record(&mut self, date: NaiveDate, current: &DailyItems, mode: Option<&GlobalMode>) -> Result<String> {
+        use super::files::{daily_file, events_file_for_date};
+
+        let content = self.record_to(&events_file_for_date(date)?, date, current, mode)?;
+        atomic_write(daily_file(date)?, &content)?;
+        Ok(content)
+    }
+
+    /// Log the changes to `events_path`, returning the daily file content
+    fn record_to(&mut self, events_path: &Path, date: NaiveDate, current: &DailyItems, mode: Option<&GlobalMode>) -> Result<String> {
+        let file_len = || std::fs::metadata(events_path).map_or(0, |meta| meta.len());
+        if self.date.take() != Some(date) || self.len != file_len() {
+            self.day = replay(&load_events(events_path)?)?;
+        }
+
+        let events = diff_events(&self.day, current, mode, Local::now());
+        append_events(events_path, &events)?;
+        for event in &events {
+            self.day.apply(event)?;
+        }
+        self.date = Some(date);
+        self.len = file_len();
+
+        let (active, done, archived) = &self.day.items;
+        Ok(serialize_daily_file_with_date(active, done, archived, date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScheduleDay;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn task(title: &str) -> Item {
+        Item::new(title.to_string(), Duration::hours(1), ScheduleDay::Today)
+    }
+
+    fn ids(items: &[Item]) -> Vec<Uuid> {
+        items.iter().map(|item| item.id).collect()
+    }
+
+    /// Diff, append and replay, checking the log reproduces `current`
+    fn record(path: &Path, current: &DailyItems, mode: Option<&GlobalMode>) -> Vec<Event> {
+        let logged = replay(&load_events(path).unwrap()).unwrap();
+        let events = diff_events(&logged, current, mode, Local::now());
+        append_events(path, &events).unwrap();
+
+        let replayed = replay(&load_events(path).unwrap()).unwrap();
+        for section in Section::ALL {
+            let (a, b) = (section.list(&replayed.items), section.list(current));
+            assert_eq!(ids(a), ids(b));
+            for (a, b) in a.iter().zip(b) {
+                assert_eq!(comparable(a), comparable(b));
+            }
+        }
+        events
+    }
+
+    fn actions(events: &[Event]) -> Vec<EventAction> {
+        events.iter().map(|event| event.action).collect()
+    }
+
+    #[test]
+    fn test_events_replay_to_the_same_day() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events-2025-11-10.jsonl");
+
+        let (mut a, b, c) = (task("A"), task("B"), task("C"));
+        let mut items: DailyItems = (vec![a.clone(), b.clone(), c.clone()], Vec::new(), Vec::new());
+        let events = record(&path, &items, Some(&GlobalMode::new("Working")));
+        assert_eq!(actions(&events)[..3], [EventAction::Create; 3]);
+        assert_eq!(events[3].change, Change::Mode { mode: GlobalMode::new("Working") });
+
+        // Nothing changed, nothing logged
+        assert!(record(&path, &items, Some(&GlobalMode::new("Working"))).is_empty());
+
+        a.start();
+        items.0[0] = a.clone();
+        assert_eq!(actions(&record(&path, &items, None)), [EventAction::Start]);
+
+        items.0[2].track.estimate = Duration::hours(3);
+        assert_eq!(actions(&record(&path, &items, None)), [EventAction::Estimate]);
+
+        // C moves above B
+        items.0.swap(1, 2);
+        assert_eq!(actions(&record(&path, &items, None)), [EventAction::Move]);
+
+        // B is done, C is deleted
+        let mut done = items.0.remove(2);
+        done.mark_done();
+        items.1.push(done);
+        items.0.remove(1);
+        assert_eq!(actions(&record(&path, &items, None)), [EventAction::Done, EventAction::Remove]);
+
+        let day = replay(&load_events(&path).unwrap()).unwrap();
+        assert_eq!(ids(&day.items.0), vec![a.id]);
+        assert_eq!(ids(&day.items.1), vec![b.id]);
+        assert_eq!(day.mode, Some(GlobalMode::new("Working")));
+        assert!(!ids(&day.items.0).contains(&c.id));
+    }
+
+    #[test]
+    fn test_moves_between_parents_are_logged_as_moves() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let (a, b) = (task("A"), task("B"));
+        let mut items: DailyItems = (vec![a.clone(), b.clone()], Vec::new(), Vec::new());
+        record(&path, &items, None);
+
+        // B becomes a subtask of A
+        let moved = items.0.remove(1);
+        items.0[0].add_subtask(moved);
+        assert_eq!(actions(&record(&path, &items, None)), [EventAction::Move, EventAction::Move]);
+
+        // ...and back to the top level
+        let moved = items.0[0].subtasks.remove(0);
+        items.0.push(moved);
+        assert_eq!(actions(&record(&path, &items, None)), [EventAction::Move, EventAction::Move]);
+        assert_eq!(ids(&items.0), vec![a.id, b.id]);
+    }
+
+    #[test]
+    fn test_truncated_last_event_is_ignored() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let items: DailyItems = (vec![task("A")], Vec::new(), Vec::new());
+        record(&path, &items, None);
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"at\":\"2025-").unwrap();
+        assert_eq!(load_events(&path).unwrap().len(), 1);
+
+        // The next append replaces the fragment, so the log stays readable
+        let more: DailyItems = (vec![items.0[0].clone(), task("B")], Vec::new(), Vec::new());
+        assert_eq!(actions(&record(&path, &more, None)), [EventAction::Create]);
+        assert_eq!(load_events(&path).unwrap().len(), 2);
+        assert!(std::fs::read_to_string(&path).unwrap().lines().all(|line| line.starts_with("{\"at\":") && line.ends_with('}')));
+    }
+
+    #[test]
+    fn test_recorder_replays_again_after_outside_events() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events-2025-11-10.jsonl");
+        let date = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+        let (a, b) = (task("A"), task("B"));
+
+        let mut recorder = DayRecorder::default();
+        let items: DailyItems = (vec![a.clone()], Vec::new(), Vec::new());
+        recorder.record_to(&path, date, &items, None).unwrap();
+        assert_eq!(recorder.len, std::fs::metadata(&path).unwrap().len());
+
+        // Another process adds B; the next write sees it and logs its removal
+        // rather than losing track of it
+        record(&path, &(vec![a.clone(), b.clone()], Vec::new(), Vec::new()), None);
+        let content = recorder.record_to(&path, date, &items, None).unwrap();
+        assert!(!content.contains(&b.id.to_string()));
+        let events = load_events(&path).unwrap();
+        assert_eq!(events.last().unwrap().change, Change::Removed { id: b.id });
+    }
+}
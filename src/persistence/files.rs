@@ -8,18 +8,26 @@ use tempfile::NamedTempFile;
 /// Get the centre directory - checks for local .centre first, then falls back to global ~/.centre
 pub fn get_centre_dir() -> Result<PathBuf> {
     // Check for local .centre directory
-    let current_dir = env::current_dir().context("Could not determine current directory")?;
-    let local_centre = find_local_centre(&current_dir);
-
-    if let Some(local_dir) = local_centre {
+    if let Some(local_dir) = local_centre_dir()? {
         return Ok(local_dir);
     }
 
     // Fall back to global ~/.centre
+    global_centre_dir()
+}
+
+/// Get the global centre directory (~/.centre), whether or not it exists
+pub fn global_centre_dir() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Could not determine home directory")?;
     Ok(home.join(".centre"))
 }
 
+/// Get the nearest local .centre directory above the current directory, if any
+pub fn local_centre_dir() -> Result<Option<PathBuf>> {
+    let current_dir = env::current_dir().context("Could not determine current directory")?;
+    Ok(find_local_centre(&current_dir))
+}
+
 /// Find local .centre directory by walking up the directory tree
 fn find_local_centre(start_dir: &Path) -> Option<PathBuf> {
     let mut current = start_dir;
@@ -66,22 +74,17 @@ pub fn daily_file(date: chrono::NaiveDate) -> Result<PathBuf> {
     Ok(ensure_centre_dir()?.join(filename))
 }
 
-/// Get path to today's daily file
-pub fn today_file() -> Result<PathBuf> {
-    let today = chrono::Local::now().date_naive();
-    daily_file(today)
-}
-
-/// Get path to tomorrow's daily file
-pub fn tomorrow_file() -> Result<PathBuf> {
-    let tomorrow = chrono::Local::now().date_naive() + chrono::Duration::days(1);
+/// Get path to tomorrow's daily file, for days starting at `day_start`
+pub fn tomorrow_file(day_start: chrono::NaiveTime) -> Result<PathBuf> {
+    let tomorrow = crate::day::today(day_start) + chrono::Duration::days(1);
     daily_file(tomorrow)
 }
 
-/// Get path to previous day's file (for copying incomplete tasks)
-pub fn previous_day_file() -> Result<PathBuf> {
-    let yesterday = chrono::Local::now().date_naive() - chrono::Duration::days(1);
-    daily_file(yesterday)
+/// Get the date of a daily file from its name (None for other files)
+pub fn daily_file_date(path: &Path) -> Option<chrono::NaiveDate> {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
 }
 
 /// Get all daily files in the centre directory (for browsing history)
@@ -125,10 +128,31 @@ pub fn journal_file_for_date(date: chrono::NaiveDate) -> Result<PathBuf> {
     Ok(ensure_centre_dir()?.join(format!("journal-{}.md", date_str)))
 }
 
-/// Get path to journal file for today
-pub fn journal_file() -> Result<PathBuf> {
-    let today = chrono::Local::now().date_naive();
-    journal_file_for_date(today)
+/// Get path to journal file for today, for days starting at `day_start`
+pub fn journal_file(day_start: chrono::NaiveTime) -> Result<PathBuf> {
+    journal_file_for_date(crate::day::today(day_start))
+}
+
+/// Get path to the mode timeline file for a specific date (modes-YYYY-MM-DD.json)
+pub fn mode_file_for_date(date: chrono::NaiveDate) -> Result<PathBuf> {
+    let date_str = date.format("%Y-%m-%d").to_string();
+    Ok(ensure_centre_dir()?.join(format!("modes-{}.json", date_str)))
+}
+
+/// Get path to the event log for a specific date (events-YYYY-MM-DD.jsonl)
+pub fn events_file_for_date(date: chrono::NaiveDate) -> Result<PathBuf> {
+    let date_str = date.format("%Y-%m-%d").to_string();
+    Ok(ensure_centre_dir()?.join(format!("events-{}.jsonl", date_str)))
+}
+
+/// Get path to undo.json (undo/redo log for the current day)
+pub fn undo_file() -> Result<PathBuf> {
+    Ok(ensure_centre_dir()?.join("undo.json"))
+}
+
+/// Get path to recurring.md (recurring task definitions)
+pub fn recurring_file() -> Result<PathBuf> {
+    Ok(ensure_centre_dir()?.join("recurring.md"))
 }
 
 /// Get path to meta.json file (stores global mode and other app metadata)
@@ -0,0 +1,69 @@
+use super::files::ensure_centre_dir;
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+/// Advisory lock on the centre directory, released when dropped
+///
+/// Every read-modify-write of the data files (TUI saves, CLI commands,
+/// postponing into tomorrow's file) takes this lock so parallel sessions
+/// never interleave their writes.
+pub struct CentreLock {
+    _file: File,
+}
+
+/// Block until the centre directory lock (`centre.lock`) is acquired
+pub fn lock_centre_dir() -> Result<CentreLock> {
+    let path = ensure_centre_dir()?.join("centre.lock");
+    lock_file(&path)
+}
+
+fn lock_file(path: &Path) -> Result<CentreLock> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+    file.lock()
+        .with_context(|| format!("Failed to lock {}", path.display()))?;
+    Ok(CentreLock { _file: file })
+}
+
+/// Read a file's current content, or None if it doesn't exist
+pub fn read_if_exists<P: AsRef<Path>>(path: P) -> Result<Option<String>> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(std::fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_lock_is_exclusive_and_released_on_drop() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("centre.lock");
+
+        let lock = lock_file(&path).unwrap();
+        let other = OpenOptions::new().write(true).open(&path).unwrap();
+        assert!(other.try_lock().is_err());
+
+        drop(lock);
+        assert!(other.try_lock().is_ok());
+    }
+
+    #[test]
+    fn test_read_if_exists() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("2025-11-10.md");
+        assert_eq!(read_if_exists(&path).unwrap(), None);
+
+        std::fs::write(&path, "# 2025-11-10\n").unwrap();
+        assert_eq!(read_if_exists(&path).unwrap().as_deref(), Some("# 2025-11-10\n"));
+    }
+}
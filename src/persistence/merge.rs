@@ -0,0 +1,147 @@
+use super::serializer::item_fingerprint;
+use crate::domain::Item;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// ACTIVE, DONE and ARCHIVED items of a daily file
+pub type DailyItems = (Vec<Item>, Vec<Item>, Vec<Item>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Active,
+    Done,
+    Archived,
+}
+
+/// Index a daily file's items by ID, remembering their section and order
+fn index(items: &DailyItems) -> (Vec<Uuid>, HashMap<Uuid, (Section, &Item)>) {
+    let mut order = Vec::new();
+    let mut map = HashMap::new();
+    let (active, done, archived) = items;
+    for (section, list) in [
+        (Section::Active, active),
+        (Section::Done, done),
+        (Section::Archived, archived),
+    ] {
+        for item in list {
+            order.push(item.id);
+            map.insert(item.id, (section, item));
+        }
+    }
+    (order, map)
+}
+
+fn same(a: Option<&(Section, &Item)>, b: Option<&(Section, &Item)>) -> bool {
+    match (a, b) {
+        (Some((sa, ia)), Some((sb, ib))) => sa == sb && item_fingerprint(ia) == item_fingerprint(ib),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Three-way merge of a daily file by task ID
+///
+/// `base` is the file as this session last read or wrote it, `ours` is the
+/// in-memory state and `theirs` is what is on disk now. For each top-level
+/// task, whichever side changed it since `base` wins; if both changed it,
+/// ours wins. Additions on either side are kept, and a task deleted on one
+/// side stays deleted unless the other side modified it.
+pub fn merge_daily(base: &DailyItems, ours: &DailyItems, theirs: &DailyItems) -> DailyItems {
+    let (_, base_map) = index(base);
+    let (our_order, our_map) = index(ours);
+    let (their_order, their_map) = index(theirs);
+
+    let mut seen = HashSet::new();
+    let mut merged: DailyItems = (Vec::new(), Vec::new(), Vec::new());
+
+    // Our order first, then anything that only exists on disk
+    for id in our_order.iter().chain(their_order.iter()) {
+        if !seen.insert(*id) {
+            continue;
+        }
+
+        let base_entry = base_map.get(id);
+        let our_entry = our_map.get(id);
+        let their_entry = their_map.get(id);
+
+        let winner = if same(our_entry, base_entry) {
+            their_entry
+        } else if same(their_entry, base_entry) {
+            our_entry
+        } else {
+            // Both changed: prefer ours, but an edit beats a delete
+            our_entry.or(their_entry)
+        };
+
+        if let Some((section, item)) = winner {
+            let item = (*item).clone();
+            match section {
+                Section::Active => merged.0.push(item),
+                Section::Done => merged.1.push(item),
+                Section::Archived => merged.2.push(item),
+            }
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScheduleDay;
+    use chrono::Duration;
+
+    fn task(title: &str) -> Item {
+        Item::new(title.to_string(), Duration::hours(1), ScheduleDay::Today)
+    }
+
+    fn titles(items: &[Item]) -> Vec<&str> {
+        items.iter().map(|i| i.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_merge_keeps_additions_from_both_sides() {
+        let a = task("A");
+        let base = (vec![a.clone()], Vec::new(), Vec::new());
+        let ours = (vec![a.clone(), task("Ours")], Vec::new(), Vec::new());
+        let theirs = (vec![a, task("Theirs")], Vec::new(), Vec::new());
+
+        let merged = merge_daily(&base, &ours, &theirs);
+        assert_eq!(titles(&merged.0), vec!["A", "Ours", "Theirs"]);
+    }
+
+    #[test]
+    fn test_merge_takes_the_changed_side() {
+        let a = task("A");
+        let b = task("B");
+        let base = (vec![a.clone(), b.clone()], Vec::new(), Vec::new());
+
+        // We renamed A; they marked B done (moved to DONE section)
+        let mut our_a = a.clone();
+        our_a.title = "A renamed".to_string();
+        let mut their_b = b.clone();
+        their_b.mark_done();
+
+        let ours = (vec![our_a, b], Vec::new(), Vec::new());
+        let theirs = (vec![a], vec![their_b], Vec::new());
+
+        let merged = merge_daily(&base, &ours, &theirs);
+        assert_eq!(titles(&merged.0), vec!["A renamed"]);
+        assert_eq!(titles(&merged.1), vec!["B"]);
+    }
+
+    #[test]
+    fn test_merge_deletions() {
+        let a = task("A");
+        let b = task("B");
+        let base = (vec![a.clone(), b.clone()], Vec::new(), Vec::new());
+
+        // We deleted A, they deleted B
+        let ours = (vec![b], Vec::new(), Vec::new());
+        let theirs = (vec![a], Vec::new(), Vec::new());
+
+        let merged = merge_daily(&base, &ours, &theirs);
+        assert!(merged.0.is_empty());
+    }
+}
@@ -1,50 +1,25 @@
 use crate::domain::GlobalMode;
 use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// App metadata stored in meta.json
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AppMetadata {
     pub global_mode: GlobalMode,
     #[serde(default)]
     pub paused_by_mode_task_ids: Vec<String>, // UUIDs as strings
-
-    // Mode time tracking (in seconds for JSON serialization)
-    #[serde(default)]
-    pub mode_time_working_secs: i64,
-    #[serde(default)]
-    pub mode_time_break_secs: i64,
-    #[serde(default)]
-    pub mode_time_lunch_secs: i64,
+    /// Day unfinished tasks were last carried over into
     #[serde(default)]
-    pub mode_time_gym_secs: i64,
+    pub last_carry_over: Option<NaiveDate>,
+    /// Day recurring tasks were last added to the list
     #[serde(default)]
-    pub mode_time_dinner_secs: i64,
+    pub last_recurring: Option<NaiveDate>,
+    /// Written periodically while the TUI runs and cleared on a clean exit,
+    /// so a value found at startup means the last session was cut short
     #[serde(default)]
-    pub mode_time_personal_secs: i64,
-    #[serde(default)]
-    pub mode_time_sleep_secs: i64,
-
-    #[serde(default)]
-    pub last_mode_change_timestamp: Option<String>, // ISO8601 timestamp
-}
-
-impl Default for AppMetadata {
-    fn default() -> Self {
-        Self {
-            global_mode: GlobalMode::Working,
-            paused_by_mode_task_ids: Vec::new(),
-            mode_time_working_secs: 0,
-            mode_time_break_secs: 0,
-            mode_time_lunch_secs: 0,
-            mode_time_gym_secs: 0,
-            mode_time_dinner_secs: 0,
-            mode_time_personal_secs: 0,
-            mode_time_sleep_secs: 0,
-            last_mode_change_timestamp: None,
-        }
-    }
+    pub heartbeat: Option<DateTime<Local>>,
 }
 
 /// Load app metadata from meta.json file
@@ -68,6 +43,15 @@ pub fn save_metadata<P: AsRef<Path>>(path: P, metadata: &AppMetadata) -> Result<
     Ok(())
 }
 
+/// Update only the heartbeat in meta.json, leaving the mode and other fields
+/// as another session (or the CLI) may have just written them
+pub fn save_heartbeat<P: AsRef<Path>>(path: P, heartbeat: Option<DateTime<Local>>) -> Result<()> {
+    let path = path.as_ref();
+    let mut metadata = load_metadata(path).unwrap_or_default();
+    metadata.heartbeat = heartbeat;
+    save_metadata(path, &metadata)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,7 +63,7 @@ mod tests {
         let meta_path = temp_dir.path().join("meta.json");
 
         let metadata = load_metadata(&meta_path).unwrap();
-        assert_eq!(metadata.global_mode, GlobalMode::Working);
+        assert_eq!(metadata.global_mode, GlobalMode::default());
         assert!(metadata.paused_by_mode_task_ids.is_empty());
     }
 
@@ -89,17 +73,51 @@ mod tests {
         let meta_path = temp_dir.path().join("meta.json");
 
         let mut metadata = AppMetadata::default();
-        metadata.global_mode = GlobalMode::Lunch;
+        metadata.global_mode = GlobalMode::new("Lunch");
         metadata.paused_by_mode_task_ids = vec!["test-id".to_string()];
-        metadata.mode_time_working_secs = 3600; // 1 hour
-        metadata.mode_time_lunch_secs = 1800;   // 30 minutes
 
         save_metadata(&meta_path, &metadata).unwrap();
 
         let loaded = load_metadata(&meta_path).unwrap();
-        assert_eq!(loaded.global_mode, GlobalMode::Lunch);
+        assert_eq!(loaded.global_mode, GlobalMode::new("Lunch"));
         assert_eq!(loaded.paused_by_mode_task_ids.len(), 1);
-        assert_eq!(loaded.mode_time_working_secs, 3600);
-        assert_eq!(loaded.mode_time_lunch_secs, 1800);
+    }
+
+    #[test]
+    fn test_load_legacy_metadata_with_mode_counters() {
+        let temp_dir = tempdir().unwrap();
+        let meta_path = temp_dir.path().join("meta.json");
+
+        // Older versions kept rolling mode counters here; they are now ignored
+        std::fs::write(
+            &meta_path,
+            r#"{"global_mode":"Gym","paused_by_mode_task_ids":[],"mode_time_working_secs":3600,"last_mode_change_timestamp":null}"#,
+        )
+        .unwrap();
+
+        let loaded = load_metadata(&meta_path).unwrap();
+        assert_eq!(loaded.global_mode, GlobalMode::new("Gym"));
+    }
+
+    #[test]
+    fn test_save_heartbeat_keeps_other_fields() {
+        let temp_dir = tempdir().unwrap();
+        let meta_path = temp_dir.path().join("meta.json");
+
+        // Another session switched to Lunch after this one last saved
+        let metadata = AppMetadata {
+            global_mode: GlobalMode::new("Lunch"),
+            paused_by_mode_task_ids: vec!["test-id".to_string()],
+            ..Default::default()
+        };
+        save_metadata(&meta_path, &metadata).unwrap();
+
+        let now = Local::now();
+        save_heartbeat(&meta_path, Some(now)).unwrap();
+
+        let loaded = load_metadata(&meta_path).unwrap();
+        assert_eq!(loaded.heartbeat, Some(now));
+        assert_eq!(loaded.global_mode, GlobalMode::new("Lunch"));
+        assert_eq!(loaded.paused_by_mode_task_ids, vec!["test-id".to_string()]);
     }
 }
@@ -1,83 +1,200 @@
-use super::files::{daily_file, previous_day_file, read_file, today_file};
+use super::files::{atomic_write, daily_file, daily_file_date, list_daily_files, meta_file, read_file};
+use super::lock::lock_centre_dir;
+use super::merge::DailyItems;
+use super::metadata::{load_metadata, save_metadata};
 use super::parser::{parse_daily_file, parse_markdown};
-use crate::domain::{Item, ScheduleDay};
+use super::recurring::load_recurring;
+use super::serializer::serialize_daily_file_with_date;
+use crate::config::Config;
+use crate::domain::{walk_items, Item, RecurringTask, ScheduleDay};
 use anyhow::Result;
-use std::path::Path;
+use chrono::NaiveDate;
+use std::path::{Path, PathBuf};
 
 /// Load and migrate tasks on startup
 ///
 /// New behavior with daily files:
-/// 1. Check if today's file exists
-/// 2. If it exists, load ACTIVE, DONE, and ARCHIVED sections
-/// 3. If it doesn't exist, create new file by copying incomplete tasks from previous day
-/// 4. Coerce all RUNNING items to PAUSED (prevent orphaned timers)
+/// 1. Find the daily files since tasks were last carried over (or, the first
+///    time, the most recent file before today), however many days ago
+/// 2. Generate a catch-up report for each of those days
+/// 3. Carry their unfinished ACTIVE tasks into today's file, merged with
+///    anything already there (e.g. tasks postponed to today)
+/// 4. Add today's occurrences of the recurring tasks in recurring.md
+/// 5. Coerce all RUNNING items to PAUSED (prevent orphaned timers)
 ///
 /// Returns: (active_tasks, done_tasks, archived_tasks)
-pub fn load_and_migrate() -> Result<(Vec<Item>, Vec<Item>, Vec<Item>)> {
-    let today_path = today_file()?;
-
-    if today_path.exists() {
-        // Load today's file
-        let today_content = read_file(&today_path)?;
-        let (mut active_items, done_items, archived_items) = if !today_content.is_empty() {
-            parse_daily_file(&today_content)?
-        } else {
-            (Vec::new(), Vec::new(), Vec::new())
-        };
+pub fn load_and_migrate(config: &Config) -> Result<(Vec<Item>, Vec<Item>, Vec<Item>)> {
+    load_and_migrate_with(config, false)
+}
 
-        // Sync elapsed time from history and coerce running items to paused
-        for item in &mut active_items {
-            item.sync_elapsed_from_history();
+/// Same as `load_and_migrate`, but with `keep_running` today's RUNNING items
+/// stay running (their timers resume from the persisted history). Used by the
+/// non-interactive CLI, where each command is a short-lived process.
+pub fn load_and_migrate_with(config: &Config, keep_running: bool) -> Result<(Vec<Item>, Vec<Item>, Vec<Item>)> {
+    let ((mut active_items, done_items, archived_items), warnings) = roll_over_to_today(config)?;
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    // Sync elapsed time from history and coerce running items to paused
+    for item in &mut active_items {
+        item.sync_elapsed_from_history();
+        if keep_running {
+            item.resume_running_timer();
+        } else {
             item.coerce_running_to_paused();
         }
+    }
+
+    Ok((active_items, done_items, archived_items))
+}
+
+/// Set up today's file: carry over unfinished tasks from the days since the
+/// last carry-over, generate their reports and add the day's recurring tasks
+///
+/// Returns today's items as stored (RUNNING items are left as they are),
+/// along with warnings about what was skipped (a catch-up report or
+/// recurring.md that failed) for the caller to show. Also used by the TUI to
+/// roll over to a new day at midnight.
+pub fn roll_over_to_today(config: &Config) -> Result<(DailyItems, Vec<String>)> {
+    let _lock = lock_centre_dir()?;
+
+    let today = crate::day::today(config.day_start());
+    let today_path = daily_file(today)?;
+    let meta_path = meta_file()?;
+    let mut metadata = load_metadata(&meta_path).unwrap_or_default();
+    let mut warnings = Vec::new();
+
+    let mut previous_days: Vec<(NaiveDate, PathBuf)> = list_daily_files()?
+        .into_iter()
+        .filter_map(|path| daily_file_date(&path).map(|date| (date, path)))
+        .filter(|(date, _)| *date < today)
+        .collect();
+
+    // Days whose tasks still need carrying over
+    let sources = match metadata.last_carry_over {
+        Some(last) => previous_days.into_iter().filter(|(date, _)| *date >= last).collect(),
+        // Before carry-over was tracked, an existing file means today was already set up
+        None if today_path.exists() => Vec::new(),
+        None => previous_days.pop().into_iter().collect(),
+    };
+
+    let today_items = if today_path.exists() {
+        parse_daily_file(&read_file(&today_path)?)?
+    } else {
+        (Vec::new(), Vec::new(), Vec::new())
+    };
 
-        Ok((active_items, done_items, archived_items))
+    let mut items = if sources.is_empty() {
+        today_items
     } else {
-        // Today's file doesn't exist - check if previous day's file exists
-        let previous_path = previous_day_file()?;
-
-        if previous_path.exists() {
-            // Generate report for the previous day before migrating
-            let yesterday = chrono::Local::now().date_naive() - chrono::Duration::days(1);
-            if let Err(e) = crate::report::generate_report(Some(yesterday), None) {
-                eprintln!("Warning: Failed to generate report for {}: {}", yesterday, e);
+        let mut previous = Vec::new();
+        for (date, path) in &sources {
+            // Catch-up report for each day that was never rolled over
+            if let Err(e) = crate::report::generate_report(*date, None, &config.modes) {
+                warnings.push(format!("Failed to generate report for {}: {:#}", date, e));
             }
+            previous.push(parse_daily_file(&read_file(path)?)?);
+        }
 
-            // Load previous day's incomplete tasks
-            let previous_content = read_file(&previous_path)?;
-            let (mut previous_active, _, _) = if !previous_content.is_empty() {
-                parse_daily_file(&previous_content)?
-            } else {
-                (Vec::new(), Vec::new(), Vec::new())
-            };
-
-            // Copy incomplete tasks to today (sync elapsed and coerce to paused)
-            for item in &mut previous_active {
-                item.schedule = ScheduleDay::Today;
-                item.sync_elapsed_from_history();
-                item.coerce_running_to_paused();
+        carry_over(&previous, today_items)
+    };
+    let mut changed = !sources.is_empty();
+
+    // Recurring tasks are added once a day, so removing one doesn't bring it back
+    let mut recurring_added = metadata.last_recurring == Some(today);
+    if !recurring_added {
+        match load_recurring() {
+            Ok(rules) => {
+                changed |= add_recurring(&rules, today, &mut items) > 0;
+                recurring_added = true;
             }
+            Err(e) => warnings.push(format!("Failed to load recurring tasks: {:#}", e)),
+        }
+    }
 
-            // Return copied tasks, empty done and archived
-            Ok((previous_active, Vec::new(), Vec::new()))
-        } else {
-            // No previous file - start fresh
-            Ok((Vec::new(), Vec::new(), Vec::new()))
+    if changed {
+        let (active, done, archived) = &items;
+        atomic_write(&today_path, &serialize_daily_file_with_date(active, done, archived, today))?;
+    }
+
+    let last_recurring = if recurring_added { Some(today) } else { metadata.last_recurring };
+    if metadata.last_carry_over != Some(today) || metadata.last_recurring != last_recurring {
+        metadata.last_carry_over = Some(today);
+        metadata.last_recurring = last_recurring;
+        save_metadata(&meta_path, &metadata)?;
+    }
+
+    Ok((items, warnings))
+}
+
+/// Carry unfinished tasks from earlier days (oldest first) into today
+///
+/// A task's latest state wins: one that was later finished or archived is
+/// dropped, and tasks already in today's file are left as they are. Carried
+/// tasks come first, followed by today's own ACTIVE tasks.
+pub fn carry_over(previous_days: &[DailyItems], today: DailyItems) -> DailyItems {
+    let mut carried: Vec<Item> = Vec::new();
+
+    for (active, done, archived) in previous_days {
+        for item in active {
+            match carried.iter_mut().find(|c| c.id == item.id) {
+                Some(existing) => *existing = item.clone(),
+                None => carried.push(item.clone()),
+            }
+        }
+        carried.retain(|c| !done.iter().chain(archived).any(|i| i.id == c.id));
+    }
+
+    let (today_active, today_done, today_archived) = today;
+    carried.retain(|c| {
+        !today_active
+            .iter()
+            .chain(&today_done)
+            .chain(&today_archived)
+            .any(|i| i.id == c.id)
+    });
+
+    for item in &mut carried {
+        item.schedule = ScheduleDay::Today;
+        item.sync_elapsed_from_history();
+        item.coerce_running_to_paused();
+    }
+
+    carried.extend(today_active);
+    (carried, today_done, today_archived)
+}
+
+/// Add a task for each recurring rule that comes round on `date` to the end
+/// of the ACTIVE list
+///
+/// Rules that already have a task in the day's file (including an unfinished
+/// one carried over from an earlier day) are skipped. Returns how many tasks
+/// were added.
+pub fn add_recurring(rules: &[RecurringTask], date: NaiveDate, items: &mut DailyItems) -> usize {
+    let (active, done, archived) = items;
+    let mut added = 0;
+    for rule in rules.iter().filter(|rule| rule.every.matches(date)) {
+        let exists = [&*active, &*done, &*archived]
+            .into_iter()
+            .any(|section| walk_items(section).any(|item| item.recurring_id == Some(rule.id)));
+        if !exists {
+            active.push(rule.instantiate());
+            added += 1;
         }
     }
+    added
 }
 
 /// Legacy migration for old format (today.md, tomorrow.md, done.log.md)
 /// This can be used to migrate from the old format to the new daily file format
-pub fn migrate_legacy_format() -> Result<()> {
+pub fn migrate_legacy_format(day_start: chrono::NaiveTime) -> Result<()> {
     use super::files::{done_log_file, tomorrow_file, truncate_file};
     use super::parser::parse_done_log_today;
-    use super::serializer::serialize_daily_file;
-    use super::files::atomic_write;
 
     // Check if legacy files exist
     let old_today_path = Path::new("today.md");
-    let old_tomorrow_path = tomorrow_file()?;
+    let old_tomorrow_path = tomorrow_file(day_start)?;
     let old_done_log_path = done_log_file()?;
 
     if !old_today_path.exists() && !old_tomorrow_path.exists() && !old_done_log_path.exists() {
@@ -135,8 +252,9 @@ pub fn migrate_legacy_format() -> Result<()> {
     }
 
     // Save to new daily file format
-    let daily_content = serialize_daily_file(&today_items, &done_items, &Vec::new());
-    let today_path = today_file()?;
+    let today = crate::day::today(day_start);
+    let daily_content = serialize_daily_file_with_date(&today_items, &done_items, &Vec::new(), today);
+    let today_path = daily_file(today)?;
     atomic_write(today_path, &daily_content)?;
 
     // Clear legacy files
@@ -192,4 +310,85 @@ mod tests {
         item.schedule = ScheduleDay::Today;
         assert_eq!(item.schedule, ScheduleDay::Today);
     }
+
+    fn task(title: &str) -> Item {
+        Item::new(title.to_string(), Duration::hours(1), ScheduleDay::Today)
+    }
+
+    #[test]
+    fn test_carry_over_across_skipped_days() {
+        let friday_task = task("Friday task");
+        let finished_later = task("Finished on Saturday");
+        let postponed = task("Postponed to Monday");
+
+        let friday = (vec![friday_task.clone(), finished_later.clone()], Vec::new(), Vec::new());
+        let saturday = (Vec::new(), vec![finished_later.clone()], Vec::new());
+        let monday = (vec![postponed.clone()], Vec::new(), Vec::new());
+
+        let (active, done, archived) = carry_over(&[friday, saturday], monday);
+
+        let ids: Vec<_> = active.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![friday_task.id, postponed.id]);
+        assert!(done.is_empty());
+        assert!(archived.is_empty());
+    }
+
+    #[test]
+    fn test_add_recurring_once_per_rule() {
+        use crate::domain::Recurrence;
+
+        // 2025-11-14 is a Friday
+        let friday = NaiveDate::from_ymd_opt(2025, 11, 14).unwrap();
+        let standup = RecurringTask::new("Standup".to_string(), Recurrence::Weekdays);
+        let deploy = RecurringTask::new("Deploy".to_string(), Recurrence::parse("weekly fri").unwrap());
+        let invoices = RecurringTask::new("Invoices".to_string(), Recurrence::Monthly(1));
+        let rules = vec![standup.clone(), deploy.clone(), invoices];
+
+        // Yesterday's standup was never done and got carried over
+        let carried = standup.instantiate();
+        let mut items = (vec![task("Other"), carried.clone()], Vec::new(), Vec::new());
+
+        assert_eq!(add_recurring(&rules, friday, &mut items), 1);
+        let (active, _, _) = &items;
+        assert_eq!(active.len(), 3);
+        assert_eq!(active[1].id, carried.id);
+        assert_eq!(active[2].title, "Deploy");
+        assert_eq!(active[2].recurring_id, Some(deploy.id));
+
+        // Nothing new the second time round, even once it's done
+        let done_deploy = items.0.pop().unwrap();
+        items.1.push(done_deploy);
+        assert_eq!(add_recurring(&rules, friday, &mut items), 0);
+
+        // Only the standup comes round on Monday
+        let monday = NaiveDate::from_ymd_opt(2025, 11, 17).unwrap();
+        let mut items = (Vec::new(), Vec::new(), Vec::new());
+        assert_eq!(add_recurring(&rules, monday, &mut items), 1);
+        assert_eq!(items.0[0].recurring_id, Some(standup.id));
+    }
+
+    #[test]
+    fn test_carry_over_keeps_todays_copy_and_latest_state() {
+        let mut task_a = task("Task A");
+        let mut task_b = task("Task B");
+        let day1 = (vec![task_a.clone(), task_b.clone()], Vec::new(), Vec::new());
+
+        // Renamed on a later day that was never rolled over
+        task_a.title = "Task A (renamed)".to_string();
+        let mut running = task_a.clone();
+        running.status = RunStatus::Running;
+        running.track.start();
+        let day2 = (vec![running], Vec::new(), Vec::new());
+
+        // Already in today's file (e.g. carried by another process)
+        task_b.title = "Task B today".to_string();
+        let today = (vec![task_b.clone()], Vec::new(), Vec::new());
+
+        let (active, _, _) = carry_over(&[day1, day2], today);
+
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[0].title, "Task A (renamed)");
+        assert_eq!(active[0].status, RunStatus::Paused);
+        assert_eq!(active[1].title, "Task B today");
+    }
 }
@@ -1,15 +1,26 @@
+pub mod event_log;
 pub mod files;
+pub mod lock;
+pub mod merge;
 pub mod metadata;
 pub mod migration;
+pub mod mode_history;
 pub mod parser;
+pub mod recurring;
 pub mod serializer;
+pub mod undo_log;
 
+pub use event_log::{load_events, record_day, replay, DayRecorder};
 pub use files::{
-    append_to_file, archive_file, atomic_write, daily_file, done_log_file, ensure_centre_dir,
-    get_centre_dir, init_local_centre, journal_file, journal_file_for_date, list_daily_files, meta_file, previous_day_file,
-    read_file, today_file, tomorrow_file, truncate_file,
+    append_to_file, archive_file, atomic_write, daily_file, daily_file_date, done_log_file, ensure_centre_dir, events_file_for_date,
+    get_centre_dir, init_local_centre, journal_file, journal_file_for_date, list_daily_files, meta_file, mode_file_for_date,
+    read_file, tomorrow_file, truncate_file, undo_file,
 };
-pub use metadata::{load_metadata, save_metadata, AppMetadata};
-pub use migration::load_and_migrate;
-pub use parser::{parse_daily_file, parse_done_log_today, parse_markdown};
-pub use serializer::{serialize_archive_entry, serialize_daily_file, serialize_daily_file_with_date, serialize_done_entry, serialize_to_markdown};
+pub use lock::{lock_centre_dir, read_if_exists};
+pub use merge::{merge_daily, DailyItems};
+pub use metadata::{load_metadata, save_heartbeat, save_metadata, AppMetadata};
+pub use migration::{load_and_migrate, load_and_migrate_with, roll_over_to_today};
+pub use mode_history::{add_mode_time, load_mode_timeline, save_mode_timeline, ModeTimeline};
+pub use parser::{parse_daily_file, parse_daily_file_checked, parse_done_log_today, parse_markdown};
+pub use serializer::{serialize_archive_entry, serialize_daily_file_with_date, serialize_done_entry, serialize_to_markdown};
+pub use undo_log::{load_undo_log, save_undo_log, UndoEntry, UndoLog};
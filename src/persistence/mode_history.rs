@@ -0,0 +1,203 @@
+use crate::domain::GlobalMode;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A contiguous span of time spent in one context mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeSegment {
+    pub mode: GlobalMode,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl ModeSegment {
+    pub fn duration(&self) -> Duration {
+        std::cmp::max(Duration::zero(), self.end.signed_duration_since(self.start))
+    }
+}
+
+/// Add time to a mode's entry in a list of per-mode totals
+pub fn add_mode_time(totals: &mut Vec<(GlobalMode, Duration)>, mode: &GlobalMode, time: Duration) {
+    match totals.iter_mut().find(|(m, _)| m == mode) {
+        Some((_, total)) => *total += time,
+        None => totals.push((mode.clone(), time)),
+    }
+}
+
+/// Per-day timeline of context modes, stored in modes-YYYY-MM-DD.json
+///
+/// Segments are only recorded while Centre is running, so gaps between
+/// segments are time the app was closed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModeTimeline {
+    #[serde(default)]
+    pub segments: Vec<ModeSegment>,
+}
+
+impl ModeTimeline {
+    /// Open a new segment (e.g. at app startup)
+    pub fn begin(&mut self, mode: GlobalMode, at: DateTime<Local>) {
+        self.segments.push(ModeSegment {
+            mode,
+            start: at,
+            end: at,
+        });
+    }
+
+    /// Close the current segment and open one for the new mode
+    pub fn switch_to(&mut self, mode: GlobalMode, at: DateTime<Local>) {
+        self.extend_to(at);
+        self.begin(mode, at);
+    }
+
+    /// Extend the current (last) segment up to the given time
+    pub fn extend_to(&mut self, at: DateTime<Local>) {
+        if let Some(last) = self.segments.last_mut() {
+            if at > last.end {
+                last.end = at;
+            }
+        }
+    }
+
+    /// Record a span of time as another mode, or drop it from the timeline
+    /// with None (e.g. time the computer spent suspended)
+    pub fn reassign(&mut self, from: DateTime<Local>, to: DateTime<Local>, mode: Option<GlobalMode>) {
+        let mut segments = Vec::new();
+        for segment in self.segments.drain(..) {
+            if segment.end <= from || segment.start >= to {
+                segments.push(segment);
+                continue;
+            }
+            if segment.start < from {
+                segments.push(ModeSegment {
+                    mode: segment.mode.clone(),
+                    start: segment.start,
+                    end: from,
+                });
+            }
+            if segment.end > to {
+                segments.push(ModeSegment {
+                    mode: segment.mode,
+                    start: to,
+                    end: segment.end,
+                });
+            }
+        }
+        if let Some(mode) = mode {
+            segments.push(ModeSegment { mode, start: from, end: to });
+        }
+        segments.sort_by_key(|segment| segment.start);
+        self.segments = segments;
+    }
+
+    /// Total time per mode name, in order of first use
+    pub fn totals(&self) -> Vec<(GlobalMode, Duration)> {
+        let mut totals = Vec::new();
+        for segment in &self.segments {
+            add_mode_time(&mut totals, &segment.mode, segment.duration());
+        }
+        totals
+    }
+}
+
+/// Load a day's mode timeline (empty if the file doesn't exist)
+pub fn load_mode_timeline<P: AsRef<Path>>(path: P) -> Result<ModeTimeline> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        return Ok(ModeTimeline::default());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let timeline: ModeTimeline = serde_json::from_str(&content)?;
+    Ok(timeline)
+}
+
+/// Save a day's mode timeline
+pub fn save_mode_timeline<P: AsRef<Path>>(path: P, timeline: &ModeTimeline) -> Result<()> {
+    let json = serde_json::to_string_pretty(timeline)?;
+    crate::persistence::atomic_write(path, &json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 11, 10, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_timeline_reassign() {
+        let mut timeline = ModeTimeline::default();
+        timeline.begin(GlobalMode::new("Working"), at(9, 0));
+        timeline.extend_to(at(12, 0));
+
+        timeline.reassign(at(10, 0), at(11, 0), Some(GlobalMode::new("Break")));
+        let modes: Vec<&str> = timeline.segments.iter().map(|s| s.mode.name()).collect();
+        assert_eq!(modes, vec!["Working", "Break", "Working"]);
+        assert_eq!(timeline.segments[2].end, at(12, 0));
+
+        // Dropped time leaves a gap; the current segment keeps extending
+        timeline.reassign(at(11, 30), at(11, 45), None);
+        timeline.extend_to(at(13, 0));
+        let totals = timeline.totals();
+        assert_eq!(totals[0], (GlobalMode::new("Working"), Duration::minutes(60 + 30 + 75)));
+        assert_eq!(totals[1], (GlobalMode::new("Break"), Duration::minutes(60)));
+    }
+
+    #[test]
+    fn test_timeline_totals() {
+        let mut timeline = ModeTimeline::default();
+        timeline.begin(GlobalMode::new("Working"), at(9, 0));
+        timeline.switch_to(GlobalMode::new("Lunch"), at(12, 0));
+        timeline.switch_to(GlobalMode::new("Working"), at(12, 45));
+        timeline.extend_to(at(17, 0));
+
+        let totals = timeline.totals();
+        let working = totals.iter().find(|(m, _)| *m == GlobalMode::new("Working")).unwrap().1;
+        let lunch = totals.iter().find(|(m, _)| *m == GlobalMode::new("Lunch")).unwrap().1;
+
+        assert_eq!(working, Duration::minutes(3 * 60 + 4 * 60 + 15));
+        assert_eq!(lunch, Duration::minutes(45));
+        assert_eq!(totals.len(), 2);
+        assert_eq!(timeline.segments.len(), 3);
+    }
+
+    #[test]
+    fn test_extend_never_shrinks() {
+        let mut timeline = ModeTimeline::default();
+        timeline.begin(GlobalMode::new("Working"), at(9, 0));
+        timeline.extend_to(at(10, 0));
+        timeline.extend_to(at(9, 30));
+
+        assert_eq!(timeline.segments[0].end, at(10, 0));
+    }
+
+    #[test]
+    fn test_save_and_load_timeline() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("modes-2025-11-10.json");
+
+        let mut timeline = ModeTimeline::default();
+        timeline.begin(GlobalMode::new("Working"), at(9, 0));
+        timeline.switch_to(GlobalMode::new("Gym"), at(18, 0));
+        timeline.extend_to(at(19, 0));
+
+        save_mode_timeline(&path, &timeline).unwrap();
+        let loaded = load_mode_timeline(&path).unwrap();
+        assert_eq!(loaded, timeline);
+    }
+
+    #[test]
+    fn test_load_missing_timeline() {
+        let temp_dir = tempdir().unwrap();
+        let loaded = load_mode_timeline(temp_dir.path().join("missing.json")).unwrap();
+        assert!(loaded.segments.is_empty());
+    }
+}
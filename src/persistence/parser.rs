@@ -1,6 +1,9 @@
+use super::merge::DailyItems;
 use crate::domain::{Item, RunStatus, ScheduleDay, StateEvent};
 use anyhow::{Context, Result};
-use chrono::{Duration, DateTime, Local, TimeZone};
+use chrono::{Duration, DateTime, Local, NaiveDate, TimeZone};
+use std::collections::HashSet;
+use uuid::Uuid;
 
 /// Section type for daily files
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -11,12 +14,26 @@ pub enum Section {
 }
 
 /// Parse a daily markdown file into separate lists for ACTIVE, DONE, and ARCHIVED items
+///
+/// Items that can't be parsed are skipped with a warning on stderr.
 pub fn parse_daily_file(content: &str) -> Result<(Vec<Item>, Vec<Item>, Vec<Item>)> {
+    let (items, warnings) = parse_daily_file_checked(content)?;
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    Ok(items)
+}
+
+/// Same as `parse_daily_file`, but returns the warnings for the items that
+/// were skipped, so callers can tell a partial parse from a complete one
+pub fn parse_daily_file_checked(content: &str) -> Result<(DailyItems, Vec<String>)> {
+    let mut warnings = Vec::new();
     let lines: Vec<&str> = content.lines().collect();
     let mut active_items = Vec::new();
     let mut done_items = Vec::new();
     let mut archived_items = Vec::new();
     let mut current_section = Section::Active; // Default section
+    let mut seen_ids = HashSet::new();
     let mut i = 0;
 
     while i < lines.len() {
@@ -45,8 +62,9 @@ pub fn parse_daily_file(content: &str) -> Result<(Vec<Item>, Vec<Item>, Vec<Item
 
         // Parse task (starts with "- [STATUS]")
         if line.starts_with("- [") {
-            match parse_item(&lines, &mut i, ScheduleDay::Today, 0) {
-                Ok(item) => {
+            match parse_item(&lines, &mut i, ScheduleDay::Today, 0, &mut warnings) {
+                Ok(mut item) => {
+                    ensure_unique_ids(&mut item, &mut seen_ids);
                     match current_section {
                         Section::Active => active_items.push(item),
                         Section::Done => done_items.push(item),
@@ -54,7 +72,7 @@ pub fn parse_daily_file(content: &str) -> Result<(Vec<Item>, Vec<Item>, Vec<Item
                     }
                 }
                 Err(e) => {
-                    eprintln!("Warning: Failed to parse item at line {}: {}", i + 1, e);
+                    warnings.push(format!("Failed to parse item at line {}: {}", i + 1, e));
                     i += 1;
                 }
             }
@@ -63,13 +81,14 @@ pub fn parse_daily_file(content: &str) -> Result<(Vec<Item>, Vec<Item>, Vec<Item
         }
     }
 
-    Ok((active_items, done_items, archived_items))
+    Ok(((active_items, done_items, archived_items), warnings))
 }
 
 /// Parse a markdown file (legacy format for today.md or tomorrow.md) into a list of items
 pub fn parse_markdown(content: &str, schedule: ScheduleDay) -> Result<Vec<Item>> {
     let lines: Vec<&str> = content.lines().collect();
     let mut items = Vec::new();
+    let mut warnings = Vec::new();
     let mut i = 0;
 
     while i < lines.len() {
@@ -83,10 +102,10 @@ pub fn parse_markdown(content: &str, schedule: ScheduleDay) -> Result<Vec<Item>>
 
         // Parse task (starts with "- [STATUS]")
         if line.starts_with("- [") {
-            match parse_item(&lines, &mut i, schedule, 0) {
+            match parse_item(&lines, &mut i, schedule, 0, &mut warnings) {
                 Ok(item) => items.push(item),
                 Err(e) => {
-                    eprintln!("Warning: Failed to parse item at line {}: {}", i + 1, e);
+                    warnings.push(format!("Failed to parse item at line {}: {}", i + 1, e));
                     i += 1;
                 }
             }
@@ -95,15 +114,21 @@ pub fn parse_markdown(content: &str, schedule: ScheduleDay) -> Result<Vec<Item>>
         }
     }
 
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
     Ok(items)
 }
 
 /// Parse a single item (task or subtask) starting at the given line index
+/// (subtasks that can't be parsed are skipped, with a message in `warnings`)
 fn parse_item(
     lines: &[&str],
     index: &mut usize,
     schedule: ScheduleDay,
-    _depth: usize,
+    depth: usize,
+    warnings: &mut Vec<String>,
 ) -> Result<Item> {
     let line = lines[*index].trim();
 
@@ -112,11 +137,16 @@ fn parse_item(
 
     *index += 1;
 
-    // Parse fields: est, elapsed, notes, tags, created, completed, history, subtasks
+    // Parse fields: id, est, elapsed, notes, tags, created, completed, history, subtasks
+    let mut id: Option<Uuid> = None;
     let mut estimate = Duration::zero();
     let mut elapsed = Duration::zero();
     let mut notes = String::new();
     let mut tags = Vec::new();
+    let mut blocked_by = Vec::new();
+    let mut waiting_reason = String::new();
+    let mut follow_up: Option<NaiveDate> = None;
+    let mut recurring_id: Option<Uuid> = None;
     let mut created_at: Option<DateTime<Local>> = None;
     let mut completed_at: Option<DateTime<Local>> = None;
     let mut state_history = Vec::new();
@@ -131,12 +161,15 @@ fn parse_item(
             break;
         }
 
-        // Check if we've reached the next task
-        if trimmed.starts_with("- [") && !current_line.trim_start().starts_with(" ") {
+        // Check if we've reached the next task (a sibling or one further up the tree)
+        if trimmed.starts_with("- [") && indent_of(current_line) <= depth * 4 {
             break;
         }
 
-        if trimmed.starts_with("est:") {
+        if trimmed.starts_with("id:") {
+            id = Uuid::parse_str(trimmed.trim_start_matches("id:").trim()).ok();
+            *index += 1;
+        } else if trimmed.starts_with("est:") {
             estimate = parse_duration(trimmed.trim_start_matches("est:").trim())?;
             *index += 1;
         } else if trimmed.starts_with("elapsed:") {
@@ -149,6 +182,22 @@ fn parse_item(
             let tags_str = trimmed.trim_start_matches("tags:").trim();
             tags = parse_tags(tags_str);
             *index += 1;
+        } else if trimmed.starts_with("waiting:") {
+            waiting_reason = trimmed.trim_start_matches("waiting:").trim().to_string();
+            *index += 1;
+        } else if trimmed.starts_with("follow_up:") {
+            follow_up = NaiveDate::parse_from_str(trimmed.trim_start_matches("follow_up:").trim(), "%Y-%m-%d").ok();
+            *index += 1;
+        } else if trimmed.starts_with("blocked_by:") {
+            blocked_by = trimmed
+                .trim_start_matches("blocked_by:")
+                .split(',')
+                .filter_map(|id| Uuid::parse_str(id.trim()).ok())
+                .collect();
+            *index += 1;
+        } else if trimmed.starts_with("recurring:") {
+            recurring_id = Uuid::parse_str(trimmed.trim_start_matches("recurring:").trim()).ok();
+            *index += 1;
         } else if trimmed.starts_with("created:") {
             let timestamp_str = trimmed.trim_start_matches("created:").trim();
             created_at = DateTime::parse_from_rfc3339(timestamp_str)
@@ -166,7 +215,7 @@ fn parse_item(
             state_history = parse_state_history(lines, index)?;
         } else if trimmed.starts_with("subtasks:") {
             *index += 1;
-            subtasks = parse_subtasks(lines, index, schedule)?;
+            subtasks = parse_subtasks(lines, index, schedule, depth, warnings)?;
         } else if trimmed.is_empty() {
             *index += 1;
         } else {
@@ -176,10 +225,18 @@ fn parse_item(
     }
 
     let mut item = Item::new(title, estimate, schedule);
+    // Keep the persisted ID; items written before IDs existed get a fresh one
+    if let Some(id) = id {
+        item.id = id;
+    }
     item.status = status;
     item.track.elapsed = elapsed;
     item.notes = notes;
     item.tags = tags;
+    item.blocked_by = blocked_by;
+    item.waiting_reason = waiting_reason;
+    item.follow_up = follow_up;
+    item.recurring_id = recurring_id;
 
     // Override created_at if parsed, otherwise keep the one from new()
     if let Some(created) = created_at {
@@ -193,11 +250,22 @@ fn parse_item(
     }
 
     item.subtasks = subtasks;
-    item.regenerate_ids();
 
     Ok(item)
 }
 
+/// Give a fresh ID to any item whose ID was already seen in this file
+/// (e.g. a task block copied by hand), so IDs stay unique
+fn ensure_unique_ids(item: &mut Item, seen: &mut HashSet<Uuid>) {
+    if !seen.insert(item.id) {
+        item.id = Uuid::new_v4();
+        seen.insert(item.id);
+    }
+    for subtask in &mut item.subtasks {
+        ensure_unique_ids(subtask, seen);
+    }
+}
+
 /// Parse task line: "- [STATUS] Title" -> (status, title)
 fn parse_task_line(line: &str) -> Result<(RunStatus, String)> {
     let line = line.trim_start_matches('-').trim();
@@ -232,21 +300,22 @@ fn parse_duration(s: &str) -> Result<Duration> {
 /// Parse multi-line notes after "notes: |"
 fn parse_notes(lines: &[&str], index: &mut usize) -> String {
     let mut notes = Vec::new();
+    // Note lines indented further than "notes:" can look like fields
+    let field_indent = indent_of(lines[*index - 1]);
 
     while *index < lines.len() {
         let line = lines[*index];
 
         // Check if this line starts a new field or task
         let trimmed = line.trim();
-        if trimmed.starts_with("est:")
-            || trimmed.starts_with("elapsed:")
-            || trimmed.starts_with("tags:")
-            || trimmed.starts_with("created:")
-            || trimmed.starts_with("completed:")
-            || trimmed.starts_with("history:")
-            || trimmed.starts_with("subtasks:")
-            || trimmed.starts_with("- [")
-        {
+        let field = indent_of(line) <= field_indent
+            && [
+                "id:", "est:", "elapsed:", "tags:", "waiting:", "follow_up:", "blocked_by:", "recurring:", "created:",
+                "completed:", "history:", "subtasks:",
+            ]
+            .iter()
+            .any(|name| trimmed.starts_with(name));
+        if field || trimmed.starts_with("- [") {
             break;
         }
 
@@ -290,10 +359,15 @@ fn parse_state_history(lines: &[&str], index: &mut usize) -> Result<Vec<StateEve
         let trimmed = line.trim();
 
         // Check if this line starts a new field or task
-        if trimmed.starts_with("est:")
+        if trimmed.starts_with("id:")
+            || trimmed.starts_with("est:")
             || trimmed.starts_with("elapsed:")
             || trimmed.starts_with("notes:")
             || trimmed.starts_with("tags:")
+            || trimmed.starts_with("waiting:")
+            || trimmed.starts_with("follow_up:")
+            || trimmed.starts_with("blocked_by:")
+            || trimmed.starts_with("recurring:")
             || trimmed.starts_with("created:")
             || trimmed.starts_with("completed:")
             || trimmed.starts_with("subtasks:")
@@ -302,9 +376,10 @@ fn parse_state_history(lines: &[&str], index: &mut usize) -> Result<Vec<StateEve
             break;
         }
 
-        // Parse history entry (indented "- timestamp: status" or "- timestamp: from -> to")
-        if line.starts_with("    - ") {
-            let entry = line.trim_start_matches("    - ");
+        // Parse history entry (indented "- timestamp: status" or "- timestamp: from -> to";
+        // subtasks' entries are indented further)
+        if line.starts_with("    ") && trimmed.starts_with("- ") {
+            let entry = trimmed.trim_start_matches("- ");
             if let Some((timestamp_str, status_str)) = entry.split_once(": ") {
                 // Try parsing as RFC3339 first (for today.md/tomorrow.md format)
                 let timestamp_local = if let Ok(timestamp) = DateTime::parse_from_rfc3339(timestamp_str) {
@@ -355,33 +430,37 @@ fn parse_state_history(lines: &[&str], index: &mut usize) -> Result<Vec<StateEve
     Ok(history)
 }
 
-/// Parse subtasks list after "subtasks:"
+/// Parse subtasks list after "subtasks:" of an item at the given depth
+/// (subtasks are indented one level, 4 spaces, deeper than their parent)
 fn parse_subtasks(
     lines: &[&str],
     index: &mut usize,
     schedule: ScheduleDay,
+    depth: usize,
+    warnings: &mut Vec<String>,
 ) -> Result<Vec<Item>> {
     let mut subtasks = Vec::new();
 
     while *index < lines.len() {
         let line = lines[*index];
         let trimmed = line.trim();
+        let indent = indent_of(line);
 
         // Check if this is a subtask line (indented "- [STATUS] Title")
-        if line.starts_with("    - [") {
-            match parse_item(lines, index, schedule, 1) {
+        if trimmed.starts_with("- [") && indent > depth * 4 {
+            match parse_item(lines, index, schedule, depth + 1, warnings) {
                 Ok(subtask) => subtasks.push(subtask),
                 Err(e) => {
-                    eprintln!("Warning: Failed to parse subtask at line {}: {}", *index + 1, e);
+                    warnings.push(format!("Failed to parse subtask at line {}: {}", *index + 1, e));
                     *index += 1;
                 }
             }
         } else if trimmed.is_empty() {
             *index += 1;
         } else if trimmed.starts_with("- [") {
-            // Reached next parent task
+            // Reached the parent's next sibling (or a task further up)
             break;
-        } else if !line.starts_with("    ") && !trimmed.is_empty() {
+        } else if indent <= depth * 4 + 2 {
             // Reached next field or section
             break;
         } else {
@@ -392,6 +471,11 @@ fn parse_subtasks(
     Ok(subtasks)
 }
 
+/// Number of leading spaces on a line
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
 /// Parse done.log.md and return tasks completed today
 pub fn parse_done_log_today(content: &str) -> Result<Vec<Item>> {
     let lines: Vec<&str> = content.lines().collect();
@@ -668,4 +752,118 @@ mod tests {
         assert_eq!(done[0].status, RunStatus::Done);
         assert_eq!(archived[0].status, RunStatus::Idle);
     }
+
+    #[test]
+    fn test_parse_preserves_ids() {
+        use crate::persistence::serializer::serialize_daily_file_with_date;
+        let day = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+
+        let mut task = Item::new("Parent".to_string(), Duration::hours(1), ScheduleDay::Today);
+        task.add_subtask(Item::new("Child".to_string(), Duration::minutes(30), ScheduleDay::Today));
+        let done = Item::new("Finished".to_string(), Duration::hours(1), ScheduleDay::Today);
+        let other = Item::new("Other".to_string(), Duration::hours(1), ScheduleDay::Today);
+        task.subtasks[0].blocked_by = vec![done.id, other.id];
+        let rule_id = Uuid::new_v4();
+        task.recurring_id = Some(rule_id);
+
+        let content = serialize_daily_file_with_date(std::slice::from_ref(&task), std::slice::from_ref(&done), &[], day);
+        let (active, done_items, _) = parse_daily_file(&content).unwrap();
+
+        assert_eq!(active[0].id, task.id);
+        assert_eq!(active[0].recurring_id, Some(rule_id));
+        assert_eq!(active[0].subtasks[0].id, task.subtasks[0].id);
+        assert_eq!(active[0].subtasks[0].blocked_by, vec![done.id, other.id]);
+        assert_eq!(done_items[0].id, done.id);
+    }
+
+    #[test]
+    fn test_parse_waiting_task() {
+        use crate::persistence::serializer::serialize_daily_file_with_date;
+        let day = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+
+        let follow_up = NaiveDate::from_ymd_opt(2025, 11, 14);
+        let mut task = Item::new("Ship release".to_string(), Duration::hours(1), ScheduleDay::Today);
+        let mut review = Item::new("Get review".to_string(), Duration::minutes(30), ScheduleDay::Today);
+        // A note line that looks like a field stays part of the notes
+        review.notes = "ask in the team channel\nelapsed: two days so far".to_string();
+        review.set_waiting("review from the platform team".to_string(), follow_up);
+        task.add_subtask(review);
+
+        let content = serialize_daily_file_with_date(std::slice::from_ref(&task), &[], &[], day);
+        let (active, _, _) = parse_daily_file(&content).unwrap();
+
+        let parsed = &active[0].subtasks[0];
+        assert_eq!(parsed.status, RunStatus::Waiting);
+        assert_eq!(parsed.waiting_reason, "review from the platform team");
+        assert_eq!(parsed.follow_up, follow_up);
+        assert_eq!(parsed.notes, "ask in the team channel\nelapsed: two days so far");
+    }
+
+    #[test]
+    fn test_parse_nested_subtasks_roundtrip() {
+        use crate::persistence::serializer::serialize_daily_file_with_date;
+        let day = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+
+        let mut step = Item::new("Step".to_string(), Duration::minutes(20), ScheduleDay::Today);
+        step.start();
+        let mut task = Item::new("Task".to_string(), Duration::hours(1), ScheduleDay::Today);
+        task.add_subtask(step);
+        let mut epic = Item::new("Epic".to_string(), Duration::hours(2), ScheduleDay::Today);
+        epic.add_subtask(task);
+        epic.add_subtask(Item::new("Other task".to_string(), Duration::hours(1), ScheduleDay::Today));
+        let next = Item::new("Next epic".to_string(), Duration::hours(1), ScheduleDay::Today);
+
+        let content = serialize_daily_file_with_date(&[epic.clone(), next], &[], &[], day);
+        let (active, _, _) = parse_daily_file(&content).unwrap();
+
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[1].title, "Next epic");
+        let parsed = &active[0];
+        assert_eq!(parsed.subtasks.len(), 2);
+        assert_eq!(parsed.subtasks[1].title, "Other task");
+        let parsed_step = &parsed.subtasks[0].subtasks[0];
+        let step = &epic.subtasks[0].subtasks[0];
+        assert_eq!(parsed_step.id, step.id);
+        assert_eq!(parsed_step.status, RunStatus::Running);
+        assert_eq!(parsed_step.state_history.len(), step.state_history.len());
+        assert_eq!(
+            serialize_daily_file_with_date(&active[..1], &[], &[], day),
+            serialize_daily_file_with_date(&[epic], &[], &[], day)
+        );
+    }
+
+    #[test]
+    fn test_parse_without_id_generates_one() {
+        let content = r#"## ACTIVE
+
+- [IDLE] Legacy task
+  est: 1.00h
+  elapsed: 0.00h
+"#;
+
+        let (first, _, _) = parse_daily_file(content).unwrap();
+        let (second, _, _) = parse_daily_file(content).unwrap();
+        assert_eq!(first.len(), 1);
+        // No persisted ID, so each load mints a new one
+        assert_ne!(first[0].id, second[0].id);
+    }
+
+    #[test]
+    fn test_parse_duplicate_ids_are_made_unique() {
+        let content = r#"## ACTIVE
+
+- [IDLE] Original
+  id: 6f1c1f52-2f36-4a55-9a0e-0d6b0b4c2a11
+  est: 1.00h
+
+- [IDLE] Copied by hand
+  id: 6f1c1f52-2f36-4a55-9a0e-0d6b0b4c2a11
+  est: 1.00h
+"#;
+
+        let (active, _, _) = parse_daily_file(content).unwrap();
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[0].id.to_string(), "6f1c1f52-2f36-4a55-9a0e-0d6b0b4c2a11");
+        assert_ne!(active[0].id, active[1].id);
+    }
 }
@@ -0,0 +1,164 @@
+use super::files::{atomic_write, read_file, recurring_file};
+use crate::domain::{parse_duration, Recurrence, RecurringTask};
+use anyhow::{bail, Context, Result};
+use chrono::Duration;
+use uuid::Uuid;
+
+/// Index of a definition's title line, and the ID generated for it
+pub type MissingId = (usize, Uuid);
+
+/// Parse recurring.md into its task definitions
+///
+/// Expected format:
+/// ```text
+/// - Daily standup
+///   every: weekdays
+///   est: 15m
+///   tags: meeting
+/// ```
+/// `every:` is required; `est:` defaults to 1h. A leading tab counts as one
+/// level of indentation (two spaces). Definitions without an `id:` get a
+/// fresh one, returned with the index of their title line.
+pub fn parse_recurring(content: &str) -> Result<(Vec<RecurringTask>, Vec<MissingId>)> {
+    let lines: Vec<String> = content.lines().map(expand_tabs).collect();
+    let mut rules = Vec::new();
+    let mut missing_ids = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = &lines[i];
+        let Some(title) = line.strip_prefix("- ") else {
+            // Headers, blank lines and anything else between definitions
+            i += 1;
+            continue;
+        };
+        let start = i + 1;
+        i += 1;
+
+        let mut id = None;
+        let mut every = None;
+        let mut estimate = Duration::hours(1);
+        let mut tags = Vec::new();
+        let mut notes = Vec::new();
+        while i < lines.len() && (lines[i].starts_with(' ') || lines[i].trim().is_empty()) {
+            let trimmed = lines[i].trim();
+            i += 1;
+            if let Some(value) = trimmed.strip_prefix("id:") {
+                id = Uuid::parse_str(value.trim()).ok();
+            } else if let Some(value) = trimmed.strip_prefix("every:") {
+                every = Some(Recurrence::parse(value).with_context(|| format!("Line {}", i))?);
+            } else if let Some(value) = trimmed.strip_prefix("est:") {
+                estimate = parse_duration(value).with_context(|| format!("Line {}", i))?;
+            } else if let Some(value) = trimmed.strip_prefix("tags:") {
+                tags = value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect();
+            } else if trimmed.starts_with("notes:") {
+                while i < lines.len() && (lines[i].starts_with("    ") || lines[i].trim().is_empty()) {
+                    notes.push(lines[i].trim_start_matches("    "));
+                    i += 1;
+                }
+            }
+        }
+
+        let Some(every) = every else {
+            bail!("Recurring task \"{}\" (line {}) has no `every:` line", title.trim(), start);
+        };
+        let mut rule = RecurringTask::new(title.trim().to_string(), every);
+        match id {
+            Some(id) => rule.id = id,
+            None => missing_ids.push((start - 1, rule.id)),
+        }
+        rule.estimate = estimate;
+        rule.tags = tags;
+        rule.notes = notes.join("\n").trim().to_string();
+        rules.push(rule);
+    }
+
+    Ok((rules, missing_ids))
+}
+
+/// A line with its leading tabs turned into two spaces each
+fn expand_tabs(line: &str) -> String {
+    let text = line.trim_start_matches('\t');
+    format!("{}{}", "  ".repeat(line.len() - text.len()), text)
+}
+
+/// Add `id:` lines right below the given title lines, leaving the rest of
+/// the file as it was
+fn insert_ids(content: &str, ids: &[MissingId]) -> String {
+    let mut output = String::with_capacity(content.len() + ids.len() * 45);
+    for (idx, line) in content.lines().enumerate() {
+        output.push_str(line);
+        output.push('\n');
+        if let Some((_, id)) = ids.iter().find(|(title_idx, _)| *title_idx == idx) {
+            output.push_str(&format!("  id: {}\n", id));
+        }
+    }
+    if !content.ends_with('\n') {
+        output.pop();
+    }
+    output
+}
+
+/// Load the recurring task definitions (none if recurring.md doesn't exist;
+/// caller holds the centre lock)
+///
+/// Definitions written by hand without an `id:` get one, added to the file
+/// so every task generated from them links back to the same rule.
+pub fn load_recurring() -> Result<Vec<RecurringTask>> {
+    let path = recurring_file()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = read_file(&path)?;
+    let (rules, missing_ids) = parse_recurring(&content).with_context(|| format!("Invalid {}", path.display()))?;
+    if !missing_ids.is_empty() {
+        atomic_write(&path, &insert_ids(&content, &missing_ids))?;
+    }
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recurring_roundtrip() {
+        let content = "# Recurring\n\n\
+            - Daily standup\n  every: weekdays\n  est: 15m\n  tags: meeting, team\n\n\
+            - Deploy\n  id: 3f2b8c1e-5d4a-4f7e-9b1c-2a6d8e0f4b73\n  every: weekly fri\n  notes: |\n    check the dashboards\n    then ship\n\n\
+            - Invoices\n  every: monthly 1\n  est: 1.5h\n";
+
+        let (rules, missing_ids) = parse_recurring(content).unwrap();
+        assert_eq!(missing_ids, vec![(2, rules[0].id), (14, rules[2].id)]);
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].title, "Daily standup");
+        assert_eq!(rules[0].every, Recurrence::Weekdays);
+        assert_eq!(rules[0].estimate, Duration::minutes(15));
+        assert_eq!(rules[0].tags, vec!["meeting", "team"]);
+        assert_eq!(rules[1].id.to_string(), "3f2b8c1e-5d4a-4f7e-9b1c-2a6d8e0f4b73");
+        assert_eq!(rules[1].estimate, Duration::hours(1));
+        assert_eq!(rules[1].notes, "check the dashboards\nthen ship");
+        assert_eq!(rules[2].every, Recurrence::Monthly(1));
+
+        // With the IDs added, it reads the same and the rest is untouched
+        let with_ids = insert_ids(content, &missing_ids);
+        let (reparsed, still_missing) = parse_recurring(&with_ids).unwrap();
+        assert!(still_missing.is_empty());
+        assert_eq!(reparsed, rules);
+        let added: Vec<String> = missing_ids.iter().map(|(_, id)| format!("  id: {}", id)).collect();
+        let kept: Vec<&str> = with_ids.lines().filter(|line| !added.iter().any(|id| id == line)).collect();
+        assert_eq!(kept, content.lines().collect::<Vec<_>>());
+        assert!(with_ids.starts_with(&format!("# Recurring\n\n- Daily standup\n  id: {}\n  every:", rules[0].id)));
+
+        // Tabs indent like two spaces each
+        let tabbed = "- Deploy\n\tevery: weekly fri\n\tnotes: |\n\t\tcheck the dashboards\n\t\tthen ship\n\test: 30m\n";
+        let (tabbed_rules, _) = parse_recurring(tabbed).unwrap();
+        assert_eq!(tabbed_rules[0].every, Recurrence::Weekly(vec![chrono::Weekday::Fri]));
+        assert_eq!(tabbed_rules[0].notes, "check the dashboards\nthen ship");
+        assert_eq!(tabbed_rules[0].estimate, Duration::minutes(30));
+
+        assert!(parse_recurring("- No rule\n  est: 1h\n").is_err());
+        assert!(parse_recurring("- Bad rule\n  every: fortnightly\n").is_err());
+    }
+}
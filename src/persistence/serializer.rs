@@ -1,16 +1,8 @@
 use crate::domain::{Item, RunStatus, ScheduleDay};
 use chrono::Local;
 
-/// Serialize active, done, and archived items into daily file format (YYYY-MM-DD.md)
-pub fn serialize_daily_file(
-    active_items: &[Item],
-    done_items: &[Item],
-    archived_items: &[Item],
-) -> String {
-    serialize_daily_file_with_date(active_items, done_items, archived_items, Local::now().date_naive())
-}
-
-/// Serialize active, done, and archived items into daily file format with a specific date
+/// Serialize active, done, and archived items into the daily file for `date`
+/// (YYYY-MM-DD.md)
 pub fn serialize_daily_file_with_date(
     active_items: &[Item],
     done_items: &[Item],
@@ -76,6 +68,11 @@ pub fn serialize_to_markdown(items: &[Item], schedule: ScheduleDay) -> String {
     output
 }
 
+/// Canonical text form of an item, used to tell whether two copies differ
+pub fn item_fingerprint(item: &Item) -> String {
+    serialize_item(item, 0, false)
+}
+
 /// Serialize a single item (task or subtask)
 /// `include_analytics` adds analytics data for done items
 fn serialize_item(item: &Item, depth: usize, include_analytics: bool) -> String {
@@ -90,6 +87,9 @@ fn serialize_item(item: &Item, depth: usize, include_analytics: bool) -> String
         item.title
     ));
 
+    // Stable ID
+    output.push_str(&format!("{}  id: {}\n", indent, item.id));
+
     // Estimate
     output.push_str(&format!(
         "{}  est: {:.2}h\n",
@@ -114,6 +114,25 @@ fn serialize_item(item: &Item, depth: usize, include_analytics: bool) -> String
         output.push_str(&format!("{}  tags: {}\n", indent, item.tags.join(", ")));
     }
 
+    // What it's waiting on, and when to follow up (while WAITING)
+    if !item.waiting_reason.is_empty() {
+        output.push_str(&format!("{}  waiting: {}\n", indent, item.waiting_reason));
+    }
+    if let Some(follow_up) = item.follow_up {
+        output.push_str(&format!("{}  follow_up: {}\n", indent, follow_up.format("%Y-%m-%d")));
+    }
+
+    // Tasks this one waits on (if any)
+    if !item.blocked_by.is_empty() {
+        let ids: Vec<String> = item.blocked_by.iter().map(|id| id.to_string()).collect();
+        output.push_str(&format!("{}  blocked_by: {}\n", indent, ids.join(", ")));
+    }
+
+    // Recurring rule it was generated from
+    if let Some(recurring_id) = item.recurring_id {
+        output.push_str(&format!("{}  recurring: {}\n", indent, recurring_id));
+    }
+
     // Notes (if not empty)
     if !item.notes.trim().is_empty() {
         output.push_str(&format!("{}  notes: |\n", indent));
@@ -293,6 +312,7 @@ mod tests {
 
         assert!(output.contains("# Today"));
         assert!(output.contains("- [RUNNING] Write tests"));
+        assert!(output.contains(&format!("id: {}", items[0].id)));
         assert!(output.contains("est: 2.00h"));
         assert!(output.contains("elapsed: 1.30h"));
         assert!(output.contains("notes: |"));
@@ -369,7 +389,8 @@ mod tests {
         let done_items = vec![done_item];
         let archived_items = vec![archived_item];
 
-        let output = serialize_daily_file(&active_items, &done_items, &archived_items);
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let output = serialize_daily_file_with_date(&active_items, &done_items, &archived_items, date);
 
         println!("Output:\n{}", output);
 
@@ -0,0 +1,205 @@
+use super::event_log::same_items;
+use super::merge::DailyItems;
+use super::parser::parse_daily_file_checked;
+use super::serializer::serialize_daily_file_with_date;
+use crate::domain::{GlobalMode, RunStatus};
+use crate::persistence::ModeTimeline;
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use uuid::Uuid;
+
+/// The state of the day before a change (on the undo stack) or before an
+/// undo (on the redo stack)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndoEntry {
+    /// What the change was, e.g. "Mark done"
+    pub label: String,
+    /// Daily file content
+    pub daily: String,
+    pub global_mode: GlobalMode,
+    #[serde(default)]
+    pub paused_by_mode_task_ids: Vec<String>, // UUIDs as strings
+    #[serde(default)]
+    pub mode_timeline: ModeTimeline,
+    /// Tomorrow's file content, for changes that also write it (postponing);
+    /// empty if the file didn't exist
+    #[serde(default)]
+    pub tomorrow: Option<String>,
+    /// When the snapshot was taken
+    #[serde(default)]
+    pub at: Option<DateTime<Local>>,
+}
+
+impl UndoEntry {
+    /// Whether the entry holds the given state (labels aside)
+    ///
+    /// Entries are compared as they'd be restored: elapsed times follow from
+    /// task history and the current mode segment runs on to the present, so
+    /// neither counts as a change.
+    pub fn holds(
+        &self,
+        items: &DailyItems,
+        global_mode: &GlobalMode,
+        paused_by_mode_task_ids: &[Uuid],
+        mode_timeline: &ModeTimeline,
+    ) -> bool {
+        let (ours, theirs) = (&self.mode_timeline.segments, &mode_timeline.segments);
+        let same_timeline = ours.len() == theirs.len()
+            && ours.iter().zip(theirs).enumerate().all(|(i, (a, b))| {
+                a.mode == b.mode && a.start == b.start && (a.end == b.end || i + 1 == ours.len())
+            });
+
+        self.global_mode == *global_mode
+            && self.paused_by_mode_task_ids.len() == paused_by_mode_task_ids.len()
+            && self.paused_by_mode_task_ids.iter().zip(paused_by_mode_task_ids).all(|(a, b)| *a == b.to_string())
+            && same_timeline
+            && parse_daily_file_checked(&self.daily).is_ok_and(|(daily, _)| same_items(&daily, items))
+    }
+
+    /// Stop the timers running in the snapshot at the time it was taken
+    ///
+    /// For entries left by an earlier session: its timers stopped with it,
+    /// so undoing to one mustn't restart them and credit the time since.
+    /// Older entries without a time stop at their last recorded change.
+    pub fn close_running(&mut self, date: NaiveDate) {
+        let Ok(((mut active, done, archived), _)) = parse_daily_file_checked(&self.daily) else {
+            return;
+        };
+        if !active.iter().any(|item| item.walk().iter().any(|item| item.status == RunStatus::Running)) {
+            return;
+        }
+
+        let last_change = active
+            .iter()
+            .flat_map(|item| item.walk())
+            .flat_map(|item| item.state_history.iter().map(|event| event.timestamp))
+            .max();
+        let Some(at) = self.at.or(last_change) else {
+            return;
+        };
+        for item in &mut active {
+            item.close_running_at(at);
+            item.sync_elapsed_from_history();
+        }
+        self.daily = serialize_daily_file_with_date(&active, &done, &archived, date);
+    }
+}
+
+/// Undo and redo stacks, stored in undo.json so the last session's changes
+/// can still be undone after a restart
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UndoLog {
+    /// Day the entries belong to (they are dropped on other days)
+    pub date: Option<NaiveDate>,
+    #[serde(default)]
+    pub undo: Vec<UndoEntry>,
+    #[serde(default)]
+    pub redo: Vec<UndoEntry>,
+}
+
+/// Load the undo log (empty if the file doesn't exist)
+pub fn load_undo_log<P: AsRef<Path>>(path: P) -> Result<UndoLog> {
+    let path = path.as_ref();
+
+    if !path.exists() {
+        return Ok(UndoLog::default());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let log: UndoLog = serde_json::from_str(&content)?;
+    Ok(log)
+}
+
+/// Save the undo log
+pub fn save_undo_log<P: AsRef<Path>>(path: P, log: &UndoLog) -> Result<()> {
+    let json = serde_json::to_string(log)?;
+    crate::persistence::atomic_write(path, &json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Item, ScheduleDay};
+    use crate::persistence::serialize_daily_file_with_date;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_undo_log_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("undo.json");
+        assert_eq!(load_undo_log(&path).unwrap(), UndoLog::default());
+
+        let entry = UndoEntry {
+            label: "Mark done".to_string(),
+            daily: "# 2025-11-10\n".to_string(),
+            global_mode: GlobalMode::new("Working"),
+            paused_by_mode_task_ids: Vec::new(),
+            mode_timeline: ModeTimeline::default(),
+            tomorrow: None,
+            at: None,
+        };
+        let log = UndoLog {
+            date: NaiveDate::from_ymd_opt(2025, 11, 10),
+            undo: vec![entry.clone()],
+            redo: Vec::new(),
+        };
+        save_undo_log(&path, &log).unwrap();
+        assert_eq!(load_undo_log(&path).unwrap(), log);
+
+        // A running timer or the clock moving on isn't a change...
+        let date = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+        let start = chrono::Local::now();
+        let mut item = Item::new("Write".to_string(), Duration::hours(1), ScheduleDay::Today);
+        item.notes = "elapsed: longer than planned".to_string();
+        item.start();
+        let mut timeline = ModeTimeline::default();
+        timeline.begin(GlobalMode::new("Working"), start);
+        let entry = UndoEntry {
+            daily: serialize_daily_file_with_date(&[item.clone()], &[], &[], date),
+            mode_timeline: timeline.clone(),
+            ..entry
+        };
+        item.track.elapsed = Duration::minutes(30);
+        timeline.extend_to(start + Duration::minutes(5));
+        let working = GlobalMode::new("Working");
+        assert!(entry.holds(&(vec![item.clone()], Vec::new(), Vec::new()), &working, &[], &timeline));
+
+        // ...but notes are, even ones that look like a field
+        item.notes = "elapsed: shorter than planned".to_string();
+        assert!(!entry.holds(&(vec![item.clone()], Vec::new(), Vec::new()), &working, &[], &timeline));
+        item.notes = "elapsed: longer than planned".to_string();
+
+        timeline.switch_to(GlobalMode::new("Lunch"), start + Duration::minutes(5));
+        assert!(!entry.holds(&(vec![item], Vec::new(), Vec::new()), &working, &[], &timeline));
+    }
+
+    #[test]
+    fn test_close_running_stops_at_snapshot_time() {
+        let date = NaiveDate::from_ymd_opt(2025, 11, 10).unwrap();
+        let now = Local::now();
+        let mut item = Item::new("Write".to_string(), Duration::hours(3), ScheduleDay::Today);
+        item.state_history[0].timestamp = now - Duration::hours(3);
+        item.start();
+        item.state_history[1].timestamp = now - Duration::hours(3);
+
+        // Taken an hour before the session ended, two hours into the run
+        let mut entry = UndoEntry {
+            label: "Estimate".to_string(),
+            daily: serialize_daily_file_with_date(&[item], &[], &[], date),
+            global_mode: GlobalMode::new("Working"),
+            paused_by_mode_task_ids: Vec::new(),
+            mode_timeline: ModeTimeline::default(),
+            tomorrow: None,
+            at: Some(now - Duration::hours(1)),
+        };
+        entry.close_running(date);
+
+        let ((active, _, _), _) = parse_daily_file_checked(&entry.daily).unwrap();
+        assert_eq!(active[0].status, RunStatus::Paused);
+        assert_eq!(active[0].running_time().num_minutes(), 120);
+    }
+}
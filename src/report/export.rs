@@ -0,0 +1,361 @@
+use crate::domain::{GlobalMode, Item, ModeSet, RunStatus};
+use crate::report::range::{
+    dedupe_tasks, load_range, sum_mode_times, summarize_days, DayData, DaySummary, ReportRange,
+};
+use crate::report::stats::{
+    calculate_completion_stats, calculate_estimation_stats, calculate_global_stats, calculate_recurring_stats,
+    calculate_tag_stats, serialize_secs, CompletionStats, EstimationStats, GlobalStats, RecurringStats, TagStats,
+};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Version of the JSON report schema, bumped on incompatible changes
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Output format for `centre report`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown report
+    #[default]
+    Md,
+    /// Structured JSON with summary, modes, estimation, completion, tags, recurring tasks and tasks
+    Json,
+    /// One CSV row per task and subtask
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Md => "md",
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Complete report contents in a serializable form
+///
+/// All durations are whole seconds.
+#[derive(Debug, Serialize)]
+pub struct ReportData {
+    pub schema_version: u32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub summary: GlobalStats,
+    pub modes: Vec<ModeTime>,
+    pub estimation: EstimationStats,
+    pub completion: CompletionStats,
+    pub tags: Vec<TagRow>,
+    pub recurring: Vec<RecurringRow>,
+    pub days: Vec<DaySummary>,
+    pub tasks: Vec<TaskRow>,
+}
+
+/// Time spent in one context mode
+#[derive(Debug, Serialize)]
+pub struct ModeTime {
+    pub mode: GlobalMode,
+    #[serde(serialize_with = "serialize_secs")]
+    pub time: Duration,
+}
+
+/// Statistics for one tag
+#[derive(Debug, Serialize)]
+pub struct TagRow {
+    pub tag: String,
+    #[serde(flatten)]
+    pub stats: TagStats,
+}
+
+/// Statistics for one recurring task (all its occurrences in the range)
+#[derive(Debug, Serialize)]
+pub struct RecurringRow {
+    pub rule_id: Uuid,
+    #[serde(flatten)]
+    pub stats: RecurringStats,
+}
+
+/// One task or subtask (subtasks carry their direct parent's ID)
+#[derive(Debug, Serialize)]
+pub struct TaskRow {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub section: &'static str,
+    pub title: String,
+    pub status: RunStatus,
+    pub tags: Vec<String>,
+    #[serde(serialize_with = "serialize_secs")]
+    pub estimate: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub elapsed: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub running_time: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub paused_time: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub idle_time: Duration,
+    pub sessions: usize,
+    pub interruptions: usize,
+    pub created_at: DateTime<Local>,
+    pub completed_at: Option<DateTime<Local>>,
+    /// Recurring rule the task was generated from
+    pub recurring_id: Option<Uuid>,
+    pub history: Vec<HistoryRow>,
+}
+
+/// One state transition of a task
+#[derive(Debug, Serialize)]
+pub struct HistoryRow {
+    pub timestamp: DateTime<Local>,
+    pub from: Option<RunStatus>,
+    pub to: RunStatus,
+}
+
+/// Flat task row for CSV output (tags joined with `;`, no history)
+#[derive(Debug, Serialize)]
+struct CsvTaskRow<'a> {
+    id: Uuid,
+    parent_id: Option<Uuid>,
+    section: &'a str,
+    title: &'a str,
+    status: RunStatus,
+    tags: String,
+    estimate_secs: i64,
+    elapsed_secs: i64,
+    running_secs: i64,
+    paused_secs: i64,
+    idle_secs: i64,
+    sessions: usize,
+    interruptions: usize,
+    created_at: String,
+    completed_at: String,
+    recurring_id: Option<Uuid>,
+}
+
+impl TaskRow {
+    /// Rows for an item and everything below it, each subtask carrying its
+    /// direct parent's ID
+    pub fn tree_rows(item: &Item, parent_id: Option<Uuid>, section: &'static str) -> Vec<Self> {
+        let mut rows = vec![Self::from_item(item, parent_id, section)];
+        for subtask in &item.subtasks {
+            rows.extend(Self::tree_rows(subtask, Some(item.id), section));
+        }
+        rows
+    }
+
+    pub fn from_item(item: &Item, parent_id: Option<Uuid>, section: &'static str) -> Self {
+        let (running_time, paused_time, idle_time) = item.time_in_each_state();
+        Self {
+            id: item.id,
+            parent_id,
+            section,
+            title: item.title.clone(),
+            status: item.status,
+            tags: item.tags.clone(),
+            estimate: item.track.estimate,
+            elapsed: item.track.elapsed,
+            running_time,
+            paused_time,
+            idle_time,
+            sessions: item.session_count(),
+            interruptions: item.interruption_count(),
+            created_at: item.created_at,
+            completed_at: item.completed_at,
+            recurring_id: item.recurring_id,
+            history: item
+                .state_history
+                .iter()
+                .map(|event| HistoryRow {
+                    timestamp: event.timestamp,
+                    from: event.from_status,
+                    to: event.to_status,
+                })
+                .collect(),
+        }
+    }
+
+    fn to_csv(&self) -> CsvTaskRow<'_> {
+        CsvTaskRow {
+            id: self.id,
+            parent_id: self.parent_id,
+            section: self.section,
+            title: &self.title,
+            status: self.status,
+            tags: self.tags.join(";"),
+            estimate_secs: self.estimate.num_seconds(),
+            elapsed_secs: self.elapsed.num_seconds(),
+            running_secs: self.running_time.num_seconds(),
+            paused_secs: self.paused_time.num_seconds(),
+            idle_secs: self.idle_time.num_seconds(),
+            sessions: self.sessions,
+            interruptions: self.interruptions,
+            created_at: self.created_at.to_rfc3339(),
+            completed_at: self.completed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+            recurring_id: self.recurring_id,
+        }
+    }
+}
+
+/// Build the report contents for a range from already loaded days
+pub fn build_report_data(range: &ReportRange, days: &[DayData], modes: &ModeSet) -> ReportData {
+    let (active, done, archived) = dedupe_tasks(days);
+
+    let mut tags: Vec<TagRow> = calculate_tag_stats(&active, &done, &archived)
+        .into_iter()
+        .map(|(tag, stats)| TagRow { tag, stats })
+        .collect();
+    // Sort by time spent, then name, so output is stable
+    tags.sort_by(|a, b| b.stats.elapsed.cmp(&a.stats.elapsed).then(a.tag.cmp(&b.tag)));
+
+    let mut recurring: Vec<RecurringRow> = calculate_recurring_stats(&active, &done, &archived)
+        .into_iter()
+        .map(|(rule_id, stats)| RecurringRow { rule_id, stats })
+        .collect();
+    recurring.sort_by(|a, b| b.stats.elapsed.cmp(&a.stats.elapsed).then(a.stats.title.cmp(&b.stats.title)));
+
+    let mut tasks = Vec::new();
+    for (section, items) in [("active", &active), ("done", &done), ("archived", &archived)] {
+        for item in items {
+            tasks.extend(TaskRow::tree_rows(item, None, section));
+        }
+    }
+
+    ReportData {
+        schema_version: SCHEMA_VERSION,
+        from: range.from,
+        to: range.to,
+        summary: calculate_global_stats(&active, &done, &archived),
+        modes: sum_mode_times(days)
+            .into_iter()
+            .map(|(mode, time)| ModeTime { mode, time })
+            .collect(),
+        estimation: calculate_estimation_stats(&done),
+        completion: calculate_completion_stats(&done),
+        tags,
+        recurring,
+        days: summarize_days(days, modes),
+        tasks,
+    }
+}
+
+/// Render report contents as pretty-printed JSON
+pub fn render_json(data: &ReportData) -> Result<String> {
+    Ok(serde_json::to_string_pretty(data)?)
+}
+
+/// Render the per-task rows as CSV
+pub fn render_csv(data: &ReportData) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for task in &data.tasks {
+        writer.serialize(task.to_csv())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Write a report for the range in the given format
+///
+/// Markdown goes through the existing daily/range generators; JSON and
+/// CSV are rendered from `ReportData`.
+pub fn write_report(
+    range: &ReportRange,
+    format: ReportFormat,
+    output_path: Option<PathBuf>,
+    modes: &ModeSet,
+) -> Result<PathBuf> {
+    let content = match format {
+        ReportFormat::Md if range.from == range.to => {
+            return crate::report::generate_report(range.from, output_path, modes);
+        }
+        ReportFormat::Md => return crate::report::generate_range_report(range, output_path, modes),
+        ReportFormat::Json => render_json(&build_report_data(range, &load_range(range)?, modes))?,
+        ReportFormat::Csv => render_csv(&build_report_data(range, &load_range(range)?, modes))?,
+    };
+
+    // Determine output path
+    let output = if let Some(path) = output_path {
+        path
+    } else {
+        crate::persistence::ensure_centre_dir()?
+            .join(format!("report-{}.{}", range.file_label(), format.extension()))
+    };
+
+    fs::write(&output, content)?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScheduleDay;
+
+    fn sample_data() -> ReportData {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+
+        let mut task = Item::new("Write docs".to_string(), Duration::hours(1), ScheduleDay::Today);
+        task.tags = vec!["docs".to_string(), "work".to_string()];
+        task.add_subtask(Item::new("Outline".to_string(), Duration::minutes(15), ScheduleDay::Today));
+        task.start();
+        task.mark_done();
+        let mut review = Item::new("Review, \"quoted\"".to_string(), Duration::hours(2), ScheduleDay::Today);
+        review.recurring_id = Some(Uuid::nil());
+
+        let day = DayData {
+            date,
+            active: vec![review],
+            done: vec![task],
+            archived: Vec::new(),
+            mode_times: vec![(GlobalMode::new("Working"), Duration::hours(4))],
+        };
+
+        build_report_data(&ReportRange::single(date), &[day], &ModeSet::default())
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let data = sample_data();
+        let json: serde_json::Value = serde_json::from_str(&render_json(&data).unwrap()).unwrap();
+
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["from"], "2026-10-05");
+        assert_eq!(json["summary"]["total_tasks"], 2);
+        assert_eq!(json["modes"][0]["mode"], "Working");
+        assert_eq!(json["modes"][0]["time"], 4 * 3600);
+        assert_eq!(json["estimation"]["under_estimate_count"], 1);
+        assert_eq!(json["completion"]["completed_count"], 1);
+        assert_eq!(json["completion"]["fastest_task"]["title"], "Write docs");
+        assert_eq!(json["tags"][0]["tag"], "docs");
+        assert_eq!(json["tags"][0]["task_count"], 1);
+        assert_eq!(json["days"][0]["done_count"], 1);
+        assert_eq!(json["recurring"][0]["rule_id"], Uuid::nil().to_string());
+        assert_eq!(json["recurring"][0]["occurrences"], 1);
+        assert_eq!(json["recurring"][0]["estimate"], 2 * 3600);
+
+        let tasks = json["tasks"].as_array().unwrap();
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0]["section"], "active");
+        assert_eq!(tasks[1]["status"], "Done");
+        assert_eq!(tasks[2]["parent_id"], tasks[1]["id"]);
+        assert_eq!(tasks[0]["recurring_id"], Uuid::nil().to_string());
+        assert!(tasks[1]["recurring_id"].is_null());
+        assert!(tasks[1]["history"].as_array().unwrap().len() >= 2);
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let data = sample_data();
+        let csv = render_csv(&data).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 4); // header + 3 tasks
+        assert!(lines[0].starts_with("id,parent_id,section,title,status,tags,estimate_secs"));
+        assert!(lines[1].contains("\"Review, \"\"quoted\"\"\""));
+        assert!(lines[2].contains(",docs;work,3600,"));
+    }
+}
@@ -1,11 +1,14 @@
-use crate::domain::{GlobalMode, Item};
-use crate::persistence::{daily_file, load_metadata, meta_file, parse_daily_file, read_file};
+use crate::domain::{GlobalMode, Item, ModeSet};
+use crate::persistence::{daily_file, load_mode_timeline, mode_file_for_date, parse_daily_file, read_file};
+use crate::report::range::{
+    dedupe_tasks, load_range, sum_mode_times, summarize_days, DaySummary, ReportRange,
+};
 use crate::report::stats::{
     calculate_completion_stats, calculate_estimation_stats, calculate_global_stats,
-    calculate_tag_stats,
+    calculate_recurring_stats, calculate_tag_stats,
 };
 use anyhow::Result;
-use chrono::{Duration, Local, NaiveDate};
+use chrono::{Duration, NaiveDate};
 use std::fs;
 use std::path::PathBuf;
 
@@ -30,32 +33,29 @@ fn format_percent(value: f64) -> String {
     format!("{:.1}%", value)
 }
 
-/// Generate a daily report for the specified date
-pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) -> Result<PathBuf> {
-    // Determine date (default to today)
-    let report_date = date.unwrap_or_else(|| Local::now().date_naive());
+/// List an item's subtasks (and theirs, indented a level deeper) under it
+fn push_subtask_lines(report: &mut String, item: &Item, depth: usize, marker: fn(&Item) -> &'static str) {
+    for subtask in &item.subtasks {
+        report.push_str(&format!("{}- [{}] {} ({} / {})\n",
+            "    ".repeat(depth),
+            marker(subtask),
+            subtask.title,
+            format_duration(subtask.track.elapsed),
+            format_duration(subtask.track.estimate)));
+        push_subtask_lines(report, subtask, depth + 1, marker);
+    }
+}
 
+/// Generate a daily report for the specified date
+pub fn generate_report(report_date: NaiveDate, output_path: Option<PathBuf>, modes: &ModeSet) -> Result<PathBuf> {
     // Load daily file
     let daily_path = daily_file(report_date)?;
     let content = read_file(&daily_path)?;
     let (active, done, archived) = parse_daily_file(&content)?;
 
-    // Load metadata for mode times
-    let metadata = load_metadata(meta_file()?).unwrap_or_default();
-    let mode_times = [
-        (GlobalMode::Working, Duration::seconds(metadata.mode_time_working_secs)),
-        (GlobalMode::Lunch, Duration::seconds(metadata.mode_time_lunch_secs)),
-        (GlobalMode::Gym, Duration::seconds(metadata.mode_time_gym_secs)),
-        (GlobalMode::Dinner, Duration::seconds(metadata.mode_time_dinner_secs)),
-        (GlobalMode::Personal, Duration::seconds(metadata.mode_time_personal_secs)),
-        (GlobalMode::Sleep, Duration::seconds(metadata.mode_time_sleep_secs)),
-    ];
-
-    // Calculate all statistics
-    let global = calculate_global_stats(&active, &done, &archived);
-    let estimation = calculate_estimation_stats(&done);
-    let completion = calculate_completion_stats(&done);
-    let tag_stats = calculate_tag_stats(&active, &done, &archived);
+    // Load the day's mode timeline for mode times
+    let timeline = load_mode_timeline(mode_file_for_date(report_date)?).unwrap_or_default();
+    let mode_times = timeline.totals();
 
     // Generate markdown report
     let mut report = String::new();
@@ -63,6 +63,78 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
     // Header
     report.push_str(&format!("# Daily Report - {}\n\n", report_date));
 
+    push_overview(&mut report, &active, &done, &archived, &mode_times, modes, "Completed Today");
+    push_tasks_breakdown(&mut report, &active, &done, &archived);
+
+    // Determine output path
+    let output = if let Some(path) = output_path {
+        path
+    } else {
+        crate::persistence::ensure_centre_dir()?.join(format!("report-{}.md", report_date))
+    };
+
+    // Write report to file
+    fs::write(&output, report)?;
+
+    Ok(output)
+}
+
+/// Generate a report covering every daily file in a date range
+///
+/// Carried-over tasks are counted once (their latest state wins), and a
+/// per-day breakdown table is added before the task listing.
+pub fn generate_range_report(
+    range: &ReportRange,
+    output_path: Option<PathBuf>,
+    modes: &ModeSet,
+) -> Result<PathBuf> {
+    let days = load_range(range)?;
+    let (active, done, archived) = dedupe_tasks(&days);
+    let mode_times = sum_mode_times(&days);
+    let summaries = summarize_days(&days, modes);
+
+    // Generate markdown report
+    let mut report = String::new();
+
+    // Header
+    report.push_str(&format!("# Report - {} to {}\n\n", range.from, range.to));
+    report.push_str(&format!("- **Days Tracked:** {} of {}\n\n", days.len(), range.len_days()));
+
+    push_overview(&mut report, &active, &done, &archived, &mode_times, modes, "Completed");
+    push_daily_breakdown(&mut report, &summaries, active.len() + done.len() + archived.len());
+    push_tasks_breakdown(&mut report, &active, &done, &archived);
+
+    // Determine output path
+    let output = if let Some(path) = output_path {
+        path
+    } else {
+        crate::persistence::ensure_centre_dir()?.join(format!("report-{}.md", range.file_label()))
+    };
+
+    // Write report to file
+    fs::write(&output, report)?;
+
+    Ok(output)
+}
+
+/// Push the Summary, Context Modes, Time & Productivity, Estimation,
+/// Completion, Tag and Recurring sections
+fn push_overview(
+    report: &mut String,
+    active: &[Item],
+    done: &[Item],
+    archived: &[Item],
+    mode_times: &[(GlobalMode, Duration)],
+    modes: &ModeSet,
+    completed_label: &str,
+) {
+    // Calculate all statistics
+    let global = calculate_global_stats(active, done, archived);
+    let estimation = calculate_estimation_stats(done);
+    let completion = calculate_completion_stats(done);
+    let tag_stats = calculate_tag_stats(active, done, archived);
+    let recurring_stats = calculate_recurring_stats(active, done, archived);
+
     // Summary Section
     report.push_str("## Summary\n\n");
     report.push_str(&format!("- **Total Tasks:** {} (Active: {}, Done: {}, Archived: {})\n",
@@ -92,7 +164,7 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
     if total_mode_time.num_minutes() > 0 {
         report.push_str("## Context Modes\n\n");
 
-        for (mode, time) in &mode_times {
+        for (mode, time) in mode_times {
             if time.num_minutes() > 0 {
                 let percentage = if total_mode_time > Duration::zero() {
                     (time.num_seconds() as f64 / total_mode_time.num_seconds() as f64) * 100.0
@@ -101,7 +173,7 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
                 };
 
                 report.push_str(&format!("- {} **{}:** {} ({})\n",
-                    mode.symbol(),
+                    modes.symbol(mode),
                     mode.name(),
                     format_duration(*time),
                     format_percent(percentage)));
@@ -170,7 +242,7 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
 
     // Task Completion Section
     report.push_str("## Task Completion\n\n");
-    report.push_str(&format!("- **Completed Today:** {} tasks\n", completion.completed_count));
+    report.push_str(&format!("- **{}:** {} tasks\n", completed_label, completion.completed_count));
     report.push_str(&format!("- **Average Time to Complete:** {}\n",
         format_duration(completion.avg_completion_time)));
 
@@ -202,13 +274,69 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
         }
     }
 
+    // Recurring Tasks Section (occurrences grouped by rule)
+    if !recurring_stats.is_empty() {
+        report.push_str("## Recurring Tasks\n\n");
+
+        let mut rules: Vec<_> = recurring_stats.values().collect();
+        rules.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then(a.title.cmp(&b.title))); // Sort by time spent, then title
+
+        for stats in rules {
+            report.push_str(&format!("### {}\n\n", stats.title));
+            report.push_str(&format!("- **Occurrences:** {} (Done: {})\n",
+                stats.occurrences, stats.done_count));
+            report.push_str(&format!("- **Time:** {} / {} estimated\n",
+                format_duration(stats.elapsed), format_duration(stats.estimate)));
+            report.push_str(&format!("- **Average per Occurrence:** {}\n\n",
+                format_duration(stats.avg_elapsed)));
+        }
+    }
+
+}
+
+/// Push the per-day breakdown table with a totals row
+fn push_daily_breakdown(report: &mut String, summaries: &[DaySummary], unique_tasks: usize) {
+    report.push_str("## Daily Breakdown\n\n");
+    report.push_str("| Date | Tasks | Done | Archived | Tracked | Working |\n");
+    report.push_str("|------|-------|------|----------|---------|---------|\n");
+
+    let mut done_total = 0;
+    let mut archived_total = 0;
+    let mut tracked_total = Duration::zero();
+    let mut working_total = Duration::zero();
+
+    for day in summaries {
+        report.push_str(&format!("| {} | {} | {} | {} | {} | {} |\n",
+            day.date.format("%a %Y-%m-%d"),
+            day.task_count,
+            day.done_count,
+            day.archived_count,
+            format_duration(day.tracked),
+            format_duration(day.working)));
+
+        done_total += day.done_count;
+        archived_total += day.archived_count;
+        tracked_total += day.tracked;
+        working_total += day.working;
+    }
+
+    report.push_str(&format!("| **Total** | {} | {} | {} | {} | {} |\n\n",
+        unique_tasks,
+        done_total,
+        archived_total,
+        format_duration(tracked_total),
+        format_duration(working_total)));
+}
+
+/// Push the per-task listing of done, active and archived tasks
+fn push_tasks_breakdown(report: &mut String, active: &[Item], done: &[Item], archived: &[Item]) {
     // Tasks Breakdown Section
     report.push_str("## Tasks Breakdown\n\n");
 
     // Done Tasks
     if !done.is_empty() {
         report.push_str("### Done Tasks\n\n");
-        for task in &done {
+        for task in done {
             let tags_str = if !task.tags.is_empty() {
                 format!(" ({})", task.tags.join(", "))
             } else {
@@ -234,14 +362,7 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
             }
 
             // Include subtasks if any
-            if !task.subtasks.is_empty() {
-                for subtask in &task.subtasks {
-                    report.push_str(&format!("    - [x] {} ({} / {})\n",
-                        subtask.title,
-                        format_duration(subtask.track.elapsed),
-                        format_duration(subtask.track.estimate)));
-                }
-            }
+            push_subtask_lines(report, task, 1, |_| "x");
             report.push_str("\n");
         }
     }
@@ -249,7 +370,7 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
     // Active Tasks
     if !active.is_empty() {
         report.push_str("### Active Tasks\n\n");
-        for task in &active {
+        for task in active {
             let tags_str = if !task.tags.is_empty() {
                 format!(" ({})", task.tags.join(", "))
             } else {
@@ -277,20 +398,11 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
                 task.session_count(), task.interruption_count()));
 
             // Include subtasks if any
-            if !task.subtasks.is_empty() {
-                for subtask in &task.subtasks {
-                    let sub_status = match subtask.status {
-                        crate::domain::RunStatus::Running => "▶",
-                        crate::domain::RunStatus::Paused => "⏸",
-                        _ => " ",
-                    };
-                    report.push_str(&format!("    - [{}] {} ({} / {})\n",
-                        sub_status,
-                        subtask.title,
-                        format_duration(subtask.track.elapsed),
-                        format_duration(subtask.track.estimate)));
-                }
-            }
+            push_subtask_lines(report, task, 1, |subtask| match subtask.status {
+                crate::domain::RunStatus::Running => "▶",
+                crate::domain::RunStatus::Paused => "⏸",
+                _ => " ",
+            });
             report.push_str("\n");
         }
     }
@@ -298,7 +410,7 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
     // Archived Tasks
     if !archived.is_empty() {
         report.push_str("### Archived Tasks\n\n");
-        for task in &archived {
+        for task in archived {
             let tags_str = if !task.tags.is_empty() {
                 format!(" ({})", task.tags.join(", "))
             } else {
@@ -312,16 +424,4 @@ pub fn generate_report(date: Option<NaiveDate>, output_path: Option<PathBuf>) ->
             report.push_str("\n");
         }
     }
-
-    // Determine output path
-    let output = if let Some(path) = output_path {
-        path
-    } else {
-        crate::persistence::ensure_centre_dir()?.join(format!("report-{}.md", report_date))
-    };
-
-    // Write report to file
-    fs::write(&output, report)?;
-
-    Ok(output)
 }
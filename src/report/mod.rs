@@ -1,4 +1,8 @@
+pub mod export;
 pub mod generator;
+pub mod range;
 pub mod stats;
 
-pub use generator::generate_report;
+pub use export::{write_report, ReportFormat};
+pub use generator::{generate_range_report, generate_report};
+pub use range::ReportRange;
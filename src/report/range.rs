@@ -0,0 +1,295 @@
+use crate::domain::{GlobalMode, Item, ModeSet};
+use crate::persistence::{
+    add_mode_time, daily_file_date, list_daily_files, load_mode_timeline, mode_file_for_date, parse_daily_file, read_file,
+};
+use crate::report::stats::serialize_secs;
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Inclusive range of days covered by a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl ReportRange {
+    pub fn new(from: NaiveDate, to: NaiveDate) -> Result<Self> {
+        if from > to {
+            return Err(anyhow!("Invalid range: {} is after {}", from, to));
+        }
+        Ok(Self { from, to })
+    }
+
+    /// Monday to Sunday week containing the given date
+    pub fn week_of(date: NaiveDate) -> Self {
+        let from = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        Self {
+            from,
+            to: from + Duration::days(6),
+        }
+    }
+
+    /// Calendar month containing the given date
+    pub fn month_of(date: NaiveDate) -> Self {
+        let from = date.with_day(1).unwrap_or(date);
+        let next_month = if from.month() == 12 {
+            NaiveDate::from_ymd_opt(from.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(from.year(), from.month() + 1, 1)
+        };
+        let to = next_month.map(|d| d - Duration::days(1)).unwrap_or(from);
+        Self { from, to }
+    }
+
+    /// A range covering a single day
+    pub fn single(date: NaiveDate) -> Self {
+        Self { from: date, to: date }
+    }
+
+    /// Label used in default report file names
+    pub fn file_label(&self) -> String {
+        if self.from == self.to {
+            self.from.to_string()
+        } else {
+            format!("{}_to_{}", self.from, self.to)
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        date >= self.from && date <= self.to
+    }
+
+    /// Number of calendar days in the range
+    pub fn len_days(&self) -> i64 {
+        (self.to - self.from).num_days() + 1
+    }
+}
+
+/// Everything recorded for a single day
+#[derive(Debug, Clone)]
+pub struct DayData {
+    pub date: NaiveDate,
+    pub active: Vec<Item>,
+    pub done: Vec<Item>,
+    pub archived: Vec<Item>,
+    pub mode_times: Vec<(GlobalMode, Duration)>,
+}
+
+/// One row of the per-day breakdown table
+#[derive(Debug, Clone, Serialize)]
+pub struct DaySummary {
+    pub date: NaiveDate,
+    pub task_count: usize,
+    pub done_count: usize,
+    pub archived_count: usize,
+    /// Time tracked on this day only (carried-over time is excluded)
+    #[serde(serialize_with = "serialize_secs")]
+    pub tracked: Duration,
+    /// Time in modes that keep timers running (e.g. Working)
+    #[serde(serialize_with = "serialize_secs")]
+    pub working: Duration,
+}
+
+/// Load every daily file in the range (days without a file are skipped)
+pub fn load_range(range: &ReportRange) -> Result<Vec<DayData>> {
+    let mut days = Vec::new();
+
+    for path in list_daily_files()? {
+        let date = match daily_file_date(&path) {
+            Some(date) if range.contains(date) => date,
+            _ => continue,
+        };
+
+        let content = read_file(&path)?;
+        let (active, done, archived) = parse_daily_file(&content)?;
+        let mode_times = load_mode_timeline(mode_file_for_date(date)?)
+            .unwrap_or_default()
+            .totals();
+
+        days.push(DayData {
+            date,
+            active,
+            done,
+            archived,
+            mode_times,
+        });
+    }
+
+    Ok(days)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Bucket {
+    Active,
+    Done,
+    Archived,
+}
+
+/// Merge the days into one set of tasks, keyed by task ID
+///
+/// Carried-over tasks appear in several daily files with cumulative
+/// elapsed time and history, so the latest copy of each task wins.
+/// `days` must be sorted by date.
+pub fn dedupe_tasks(days: &[DayData]) -> (Vec<Item>, Vec<Item>, Vec<Item>) {
+    let mut order: Vec<Uuid> = Vec::new();
+    let mut latest: HashMap<Uuid, (Bucket, &Item)> = HashMap::new();
+
+    for day in days {
+        let buckets = [
+            (Bucket::Active, &day.active),
+            (Bucket::Done, &day.done),
+            (Bucket::Archived, &day.archived),
+        ];
+        for (bucket, items) in buckets {
+            for item in items {
+                if latest.insert(item.id, (bucket, item)).is_none() {
+                    order.push(item.id);
+                }
+            }
+        }
+    }
+
+    let mut active = Vec::new();
+    let mut done = Vec::new();
+    let mut archived = Vec::new();
+
+    for id in order {
+        let (bucket, item) = latest[&id];
+        match bucket {
+            Bucket::Active => active.push(item.clone()),
+            Bucket::Done => done.push(item.clone()),
+            Bucket::Archived => archived.push(item.clone()),
+        }
+    }
+
+    (active, done, archived)
+}
+
+/// Build the per-day breakdown rows
+///
+/// Tracked time for a day is the growth of each task's elapsed time
+/// since the previous file it appeared in. `days` must be sorted by date.
+pub fn summarize_days(days: &[DayData], modes: &ModeSet) -> Vec<DaySummary> {
+    let mut last_elapsed: HashMap<Uuid, Duration> = HashMap::new();
+    let mut summaries = Vec::new();
+
+    for day in days {
+        let mut tracked = Duration::zero();
+
+        for item in day.active.iter().chain(&day.done).chain(&day.archived) {
+            let previous = last_elapsed.insert(item.id, item.track.elapsed);
+            let delta = item.track.elapsed - previous.unwrap_or(Duration::zero());
+            if delta > Duration::zero() {
+                tracked += delta;
+            }
+        }
+
+        let working = day
+            .mode_times
+            .iter()
+            .filter(|(mode, _)| !modes.should_pause_timers(mode))
+            .fold(Duration::zero(), |acc, (_, time)| acc + *time);
+
+        summaries.push(DaySummary {
+            date: day.date,
+            task_count: day.active.len() + day.done.len() + day.archived.len(),
+            done_count: day.done.len(),
+            archived_count: day.archived.len(),
+            tracked,
+            working,
+        });
+    }
+
+    summaries
+}
+
+/// Sum mode times across all days (keyed by mode name, in order of first use)
+pub fn sum_mode_times(days: &[DayData]) -> Vec<(GlobalMode, Duration)> {
+    let mut totals = Vec::new();
+    for (mode, time) in days.iter().flat_map(|day| day.mode_times.iter()) {
+        add_mode_time(&mut totals, mode, *time);
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ScheduleDay;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn day(date: NaiveDate, active: Vec<Item>, done: Vec<Item>) -> DayData {
+        DayData {
+            date,
+            active,
+            done,
+            archived: Vec::new(),
+            mode_times: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_week_and_month_ranges() {
+        // 2026-10-07 is a Wednesday
+        let week = ReportRange::week_of(date(2026, 10, 7));
+        assert_eq!(week.from, date(2026, 10, 5));
+        assert_eq!(week.to, date(2026, 10, 11));
+
+        let month = ReportRange::month_of(date(2026, 12, 15));
+        assert_eq!(month.from, date(2026, 12, 1));
+        assert_eq!(month.to, date(2026, 12, 31));
+        assert_eq!(ReportRange::month_of(date(2028, 2, 3)).len_days(), 29);
+
+        assert!(ReportRange::new(date(2026, 10, 11), date(2026, 10, 5)).is_err());
+    }
+
+    #[test]
+    fn test_dedupe_carried_tasks() {
+        let mut carried = Item::new("Carried".to_string(), Duration::hours(2), ScheduleDay::Today);
+        carried.track.elapsed = Duration::minutes(30);
+        let mut finished = carried.clone();
+        finished.track.elapsed = Duration::minutes(90);
+
+        let other = Item::new("Other".to_string(), Duration::hours(1), ScheduleDay::Today);
+
+        let days = vec![
+            day(date(2026, 10, 5), vec![carried, other], Vec::new()),
+            day(date(2026, 10, 6), Vec::new(), vec![finished]),
+        ];
+
+        let (active, done, archived) = dedupe_tasks(&days);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].title, "Other");
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].track.elapsed, Duration::minutes(90));
+        assert!(archived.is_empty());
+    }
+
+    #[test]
+    fn test_summarize_days_counts_only_new_time() {
+        let mut task = Item::new("Task".to_string(), Duration::hours(2), ScheduleDay::Today);
+        task.track.elapsed = Duration::minutes(45);
+        let mut next = task.clone();
+        next.track.elapsed = Duration::minutes(60);
+
+        let mut first = day(date(2026, 10, 5), vec![task], Vec::new());
+        first.mode_times = vec![
+            (GlobalMode::new("Working"), Duration::hours(3)),
+            (GlobalMode::new("Lunch"), Duration::hours(1)),
+        ];
+        let days = vec![first, day(date(2026, 10, 6), Vec::new(), vec![next])];
+
+        let summaries = summarize_days(&days, &ModeSet::default());
+        assert_eq!(summaries[0].tracked, Duration::minutes(45));
+        assert_eq!(summaries[0].working, Duration::hours(3));
+        assert_eq!(summaries[1].tracked, Duration::minutes(15));
+        assert_eq!(summaries[1].done_count, 1);
+    }
+}
@@ -1,57 +1,110 @@
-use crate::domain::Item;
+use crate::domain::{walk_items, Item};
 use chrono::Duration;
+use serde::{Serialize, Serializer};
 use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Serialize a duration as whole seconds (all report durations use seconds)
+pub fn serialize_secs<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_i64(duration.num_seconds())
+}
+
+/// Serialize an optional (title, duration) pair as `{ "title": ..., "secs": ... }`
+fn serialize_task_time<S: Serializer>(
+    task: &Option<(String, Duration)>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct TaskTime<'a> {
+        title: &'a str,
+        secs: i64,
+    }
+
+    task.as_ref()
+        .map(|(title, duration)| TaskTime { title, secs: duration.num_seconds() })
+        .serialize(serializer)
+}
 
 /// Global statistics for all tasks
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct GlobalStats {
     pub total_tasks: usize,
     pub active_count: usize,
     pub done_count: usize,
     pub archived_count: usize,
+    #[serde(serialize_with = "serialize_secs")]
     pub total_elapsed: Duration,
+    #[serde(serialize_with = "serialize_secs")]
     pub total_estimate: Duration,
+    #[serde(serialize_with = "serialize_secs")]
     pub running_time: Duration,
+    #[serde(serialize_with = "serialize_secs")]
     pub paused_time: Duration,
+    #[serde(serialize_with = "serialize_secs")]
     pub idle_time: Duration,
     pub total_sessions: usize,
     pub total_interruptions: usize,
+    #[serde(serialize_with = "serialize_secs")]
     pub avg_session: Duration,
+    #[serde(serialize_with = "serialize_secs")]
     pub longest_session: Duration,
 }
 
 /// Estimation accuracy statistics
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct EstimationStats {
     pub over_estimate_count: usize,
+    #[serde(serialize_with = "serialize_secs")]
     pub over_estimate_time: Duration,
     pub under_estimate_count: usize,
+    #[serde(serialize_with = "serialize_secs")]
     pub under_estimate_time: Duration,
     pub perfect_count: usize,
     pub avg_accuracy_percent: f64,
 }
 
 /// Task completion statistics
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct CompletionStats {
     pub completed_count: usize,
+    #[serde(serialize_with = "serialize_secs")]
     pub avg_completion_time: Duration,
+    #[serde(serialize_with = "serialize_task_time")]
     pub fastest_task: Option<(String, Duration)>,
+    #[serde(serialize_with = "serialize_task_time")]
     pub longest_task: Option<(String, Duration)>,
 }
 
 /// Per-tag statistics
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct TagStats {
     pub task_count: usize,
     pub done_count: usize,
     pub active_count: usize,
+    #[serde(serialize_with = "serialize_secs")]
     pub elapsed: Duration,
+    #[serde(serialize_with = "serialize_secs")]
     pub estimate: Duration,
     pub accuracy_percent: f64,
+    #[serde(serialize_with = "serialize_secs")]
     pub avg_session: Duration,
 }
 
+/// Statistics for the occurrences of one recurring task
+#[derive(Debug, Serialize)]
+pub struct RecurringStats {
+    /// Title of the latest occurrence
+    pub title: String,
+    pub occurrences: usize,
+    pub done_count: usize,
+    #[serde(serialize_with = "serialize_secs")]
+    pub elapsed: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub estimate: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub avg_elapsed: Duration,
+}
+
 /// Calculate global statistics across all tasks
 pub fn calculate_global_stats(
     active: &[Item],
@@ -286,3 +339,50 @@ pub fn calculate_tag_stats(
 
     tag_map
 }
+
+/// Calculate statistics for each recurring task, keyed by its rule ID
+pub fn calculate_recurring_stats(
+    active: &[Item],
+    done: &[Item],
+    archived: &[Item],
+) -> HashMap<Uuid, RecurringStats> {
+    let mut rule_map: HashMap<Uuid, (RecurringStats, &Item)> = HashMap::new();
+
+    for (items, is_done) in [(active, false), (done, true), (archived, false)] {
+        for task in walk_items(items) {
+            let Some(rule_id) = task.recurring_id else {
+                continue;
+            };
+            let (entry, latest) = rule_map.entry(rule_id).or_insert_with(|| {
+                let stats = RecurringStats {
+                    title: task.title.clone(),
+                    occurrences: 0,
+                    done_count: 0,
+                    elapsed: Duration::zero(),
+                    estimate: Duration::zero(),
+                    avg_elapsed: Duration::zero(),
+                };
+                (stats, task)
+            });
+
+            entry.occurrences += 1;
+            entry.elapsed += task.track.elapsed;
+            entry.estimate += task.track.estimate;
+            if is_done {
+                entry.done_count += 1;
+            }
+            if task.created_at > latest.created_at {
+                entry.title = task.title.clone();
+                *latest = task;
+            }
+        }
+    }
+
+    rule_map
+        .into_iter()
+        .map(|(rule_id, (mut stats, _))| {
+            stats.avg_elapsed = Duration::milliseconds(stats.elapsed.num_milliseconds() / stats.occurrences as i64);
+            (rule_id, stats)
+        })
+        .collect()
+}
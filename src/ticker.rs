@@ -3,9 +3,9 @@ use std::time::Duration;
 /// Default tick interval in milliseconds
 pub const DEFAULT_TICK_MS: u64 = 250;
 
-/// Get tick duration
-pub fn tick_duration() -> Duration {
-    Duration::from_millis(DEFAULT_TICK_MS)
+/// Get tick duration for a tick interval in milliseconds (see `[timer]` in config.toml)
+pub fn tick_duration(tick_ms: u64) -> Duration {
+    Duration::from_millis(tick_ms)
 }
 
 #[cfg(test)]
@@ -14,7 +14,7 @@ mod tests {
 
     #[test]
     fn test_tick_duration() {
-        let duration = tick_duration();
+        let duration = tick_duration(DEFAULT_TICK_MS);
         assert_eq!(duration, Duration::from_millis(250));
     }
 }
@@ -14,7 +14,9 @@ const FRAMES_PER_CYCLE: u32 = 12; // 12 frames at 4 FPS = 3 second loop
 pub fn render_animation_pane(f: &mut Frame, app: &AppState, area: Rect) {
     let frame_index = (app.animation_frame % FRAMES_PER_CYCLE) as usize;
 
-    let (title, animation_lines) = get_animation_for_mode(&app.global_mode, frame_index);
+    let pauses_timers = app.config.modes.should_pause_timers(&app.global_mode);
+    let animation_lines = get_animation_for_mode(&app.global_mode, pauses_timers, frame_index);
+    let title = app.config.modes.label(&app.global_mode);
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -32,21 +34,23 @@ pub fn render_animation_pane(f: &mut Frame, app: &AppState, area: Rect) {
     f.render_widget(animation, inner);
 }
 
-fn get_animation_for_mode(mode: &GlobalMode, frame: usize) -> (String, Vec<Line<'static>>) {
-    match mode {
-        GlobalMode::Working => working_animation(frame),
-        GlobalMode::Break => break_animation(frame),
-        GlobalMode::Lunch => lunch_animation(frame),
-        GlobalMode::Gym => gym_animation(frame),
-        GlobalMode::Dinner => dinner_animation(frame),
-        GlobalMode::Personal => personal_animation(frame),
-        GlobalMode::Sleep => sleep_animation(frame),
+/// Pick the animation for a mode: built-in modes have their own, other
+/// modes get the working or break animation depending on whether they pause timers
+fn get_animation_for_mode(mode: &GlobalMode, pauses_timers: bool, frame: usize) -> Vec<Line<'static>> {
+    match mode.name() {
+        "Working" => working_animation(frame),
+        "Break" => break_animation(frame),
+        "Lunch" => lunch_animation(frame),
+        "Gym" => gym_animation(frame),
+        "Dinner" => dinner_animation(frame),
+        "Personal" => personal_animation(frame),
+        "Sleep" => sleep_animation(frame),
+        _ if pauses_timers => break_animation(frame),
+        _ => working_animation(frame),
     }
 }
 
-fn working_animation(frame: usize) -> (String, Vec<Line<'static>>) {
-    let title = format!("{} Working", GlobalMode::Working.symbol());
-
+fn working_animation(frame: usize) -> Vec<Line<'static>> {
     // Animated coffee cup with steam
     let steam_frames = [
         vec!["  ) ) )  ", "  ) ) )  "],
@@ -71,12 +75,10 @@ fn working_animation(frame: usize) -> (String, Vec<Line<'static>>) {
         Line::from(Span::styled("  Focus  ", Style::default().add_modifier(Modifier::BOLD))),
     ];
 
-    (title, lines)
+    lines
 }
 
-fn break_animation(frame: usize) -> (String, Vec<Line<'static>>) {
-    let title = format!("{} Break", GlobalMode::Break.symbol());
-
+fn break_animation(frame: usize) -> Vec<Line<'static>> {
     // Cloud emojis drifting horizontally at different speeds
     let cloud_positions = [
         ("      ☁️       ", "  ☁️         ", "        ☁️   "),
@@ -108,12 +110,10 @@ fn break_animation(frame: usize) -> (String, Vec<Line<'static>>) {
         Line::from(Span::styled("  Drift ", Style::default().add_modifier(Modifier::BOLD))),
     ];
 
-    (title, lines)
+    lines
 }
 
-fn lunch_animation(frame: usize) -> (String, Vec<Line<'static>>) {
-    let title = format!("{} Lunch", GlobalMode::Lunch.symbol());
-
+fn lunch_animation(frame: usize) -> Vec<Line<'static>> {
     // Animated steaming plate
     let steam_patterns = ["~", "≈", "~", "≈"];
     let steam = steam_patterns[frame % steam_patterns.len()];
@@ -131,12 +131,10 @@ fn lunch_animation(frame: usize) -> (String, Vec<Line<'static>>) {
         Line::from(Span::styled(" Nourish ", Style::default().add_modifier(Modifier::BOLD))),
     ];
 
-    (title, lines)
+    lines
 }
 
-fn gym_animation(frame: usize) -> (String, Vec<Line<'static>>) {
-    let title = format!("{} Gym", GlobalMode::Gym.symbol());
-
+fn gym_animation(frame: usize) -> Vec<Line<'static>> {
     // Animated dumbbell lift
     let positions = [
         ("    ___    ", "   |   |   ", "===|   |==="),
@@ -161,12 +159,10 @@ fn gym_animation(frame: usize) -> (String, Vec<Line<'static>>) {
         Line::from(Span::styled(" Strength", Style::default().add_modifier(Modifier::BOLD))),
     ];
 
-    (title, lines)
+    lines
 }
 
-fn dinner_animation(frame: usize) -> (String, Vec<Line<'static>>) {
-    let title = format!("{} Dinner", GlobalMode::Dinner.symbol());
-
+fn dinner_animation(frame: usize) -> Vec<Line<'static>> {
     // Animated steaming bowl
     let steam_patterns = ["˚", "°", "˚", "°"];
     let steam = steam_patterns[frame % steam_patterns.len()];
@@ -184,12 +180,10 @@ fn dinner_animation(frame: usize) -> (String, Vec<Line<'static>>) {
         Line::from(Span::styled(" Evening ", Style::default().add_modifier(Modifier::BOLD))),
     ];
 
-    (title, lines)
+    lines
 }
 
-fn personal_animation(frame: usize) -> (String, Vec<Line<'static>>) {
-    let title = format!("{} Personal", GlobalMode::Personal.symbol());
-
+fn personal_animation(frame: usize) -> Vec<Line<'static>> {
     // Animated flower blooming - opens and closes gently
     let bloom_states = [
         // Closed bud
@@ -230,12 +224,10 @@ fn personal_animation(frame: usize) -> (String, Vec<Line<'static>>) {
         Line::from(Span::styled("  Bloom ", Style::default().add_modifier(Modifier::BOLD))),
     ];
 
-    (title, lines)
+    lines
 }
 
-fn sleep_animation(frame: usize) -> (String, Vec<Line<'static>>) {
-    let title = format!("{} Sleep", GlobalMode::Sleep.symbol());
-
+fn sleep_animation(frame: usize) -> Vec<Line<'static>> {
     // Animated twinkling stars and moon
     let star_patterns = [
         ("*", " ", "*"),
@@ -259,5 +251,5 @@ fn sleep_animation(frame: usize) -> (String, Vec<Line<'static>>) {
         Line::from(Span::styled("  Rest   ", Style::default().add_modifier(Modifier::BOLD))),
     ];
 
-    (title, lines)
+    lines
 }
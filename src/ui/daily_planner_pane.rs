@@ -1,5 +1,7 @@
 use crate::app::AppState;
-use crate::domain::RunStatus;
+use crate::config::PlannerConfig;
+use crate::day::minutes_into_day;
+use crate::domain::{schedule_order, Item, RunStatus};
 use crate::ui::styles::{idle_style, paused_style, running_style};
 use chrono::{Duration, Local, NaiveTime, Timelike};
 use ratatui::{
@@ -14,12 +16,6 @@ use uuid::Uuid;
 /// Time slice in minutes (15-minute intervals)
 const SLICE_MINUTES: i64 = 15;
 
-/// Start hour for planner (9am)
-const START_HOUR: u32 = 9;
-
-/// End hour for planner (12am = midnight = 24)
-const END_HOUR: u32 = 24;
-
 /// Task block scheduled for the planner
 #[derive(Debug, Clone)]
 struct TaskBlock {
@@ -43,12 +39,13 @@ struct TimeSlice {
 pub fn render_daily_planner_pane(f: &mut Frame, app: &AppState, area: Rect) {
     let now = Local::now();
     let current_time = now.time();
+    let day_start = app.config.day_start();
 
-    // Calculate time grid from now until midnight
-    let slices = build_time_grid(current_time);
+    // Calculate time grid for the configured planner hours
+    let slices = build_time_grid(&app.config.planner);
 
     // Schedule all active tasks into time blocks
-    let task_blocks = schedule_tasks(app, current_time);
+    let task_blocks = schedule_tasks(app, current_time, day_start);
 
     // Render the planner (no title line - it's in the Block border)
     let mut all_lines = Vec::new();
@@ -57,18 +54,19 @@ pub fn render_daily_planner_pane(f: &mut Frame, app: &AppState, area: Rect) {
 
     // Build all lines first (we'll slice later for scrolling)
     for (_idx, slice) in slices.iter().enumerate() {
-        let slice_mins = slice.hour() as i64 * 60 + slice.minute() as i64;
+        // Minutes into the day, so slices after midnight sort after the evening
+        let slice_mins = minutes_into_day(*slice, day_start);
         let next_slice_mins = slice_mins + SLICE_MINUTES;
 
         // Check if this is the current time slot
-        let current_mins = current_time.hour() as i64 * 60 + current_time.minute() as i64;
+        let current_mins = minutes_into_day(current_time, day_start);
         let is_current_slot = current_mins >= slice_mins && current_mins < next_slice_mins;
 
         // Find ALL blocks that occupy this time slot
         let mut slot_blocks: Vec<&TaskBlock> = Vec::new();
         for block in task_blocks.iter() {
-            let block_start = block.start_time.hour() as i64 * 60 + block.start_time.minute() as i64;
-            let block_end = block.end_time.hour() as i64 * 60 + block.end_time.minute() as i64;
+            let block_start = minutes_into_day(block.start_time, day_start);
+            let block_end = minutes_into_day(block.end_time, day_start);
 
             // Check if this slot overlaps with the block
             if block_start < next_slice_mins && block_end > slice_mins {
@@ -142,124 +140,100 @@ pub fn render_daily_planner_pane(f: &mut Frame, app: &AppState, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
-/// Build time grid from 9am to midnight in 15-minute intervals
-fn build_time_grid(_current_time: NaiveTime) -> Vec<NaiveTime> {
+/// Build time grid from the planner's start to end hour in 15-minute intervals
+/// (hours past 24 wrap around to the early morning of the next date)
+fn build_time_grid(planner: &PlannerConfig) -> Vec<NaiveTime> {
     let mut slices = Vec::new();
 
-    // Create 30-minute slices from START_HOUR to END_HOUR
-    let start_minutes = (START_HOUR * 60) as i64;
-    let end_minutes = (END_HOUR * 60) as i64;
+    // Create 15-minute slices from start_hour to end_hour
+    let start_minutes = (planner.start_hour * 60) as i64;
+    let end_minutes = (planner.end_hour * 60) as i64;
 
     let mut current_minutes = start_minutes;
     while current_minutes < end_minutes {
-        let hours = (current_minutes / 60) as u32;
-        let mins = (current_minutes % 60) as u32;
-        if let Some(time) = NaiveTime::from_hms_opt(hours, mins, 0) {
-            slices.push(time);
-        }
+        slices.push(time_of_day(current_minutes));
         current_minutes += SLICE_MINUTES;
     }
 
     slices
 }
 
+/// Time of day for a number of minutes into the day (wrapping past midnight)
+fn time_of_day(minutes: i64) -> NaiveTime {
+    let minutes = minutes.rem_euclid(24 * 60) as u32;
+    NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0).unwrap_or(NaiveTime::MIN)
+}
+
 /// Schedule tasks into time blocks based on their remaining time estimates
-fn schedule_tasks(app: &AppState, current_time: NaiveTime) -> Vec<TaskBlock> {
+fn schedule_tasks(app: &AppState, current_time: NaiveTime, day_start: NaiveTime) -> Vec<TaskBlock> {
     let mut blocks = Vec::new();
 
     // Start time accumulator - round UP to next 15-minute slice
-    let current_minutes = current_time.hour() as i64 * 60 + current_time.minute() as i64;
+    let current_minutes = minutes_into_day(current_time, day_start);
     let mut accumulated_minutes = ((current_minutes + SLICE_MINUTES - 1) / SLICE_MINUTES) * SLICE_MINUTES;
 
-    // Process all tasks and subtasks
-    for (task_idx, task) in app.tasks.iter().enumerate() {
-        let is_task_selected = task_idx == app.selected_index;
+    // Blocks stop at the end of the day
+    let day_end = minutes_into_day(day_start, day_start) + 24 * 60 - 1;
 
-        // If task has no subtasks, schedule it
-        if task.subtasks.is_empty() {
-            // Only schedule tasks that have remaining time
-            let remaining = task.track.estimate - task.track.elapsed;
-            if remaining > Duration::zero() {
-                let remaining_minutes = remaining.num_minutes();
-                if remaining_minutes > 0 {
-                    // Calculate start and end times
-                    let start_hours = (accumulated_minutes / 60) as u32;
-                    let start_mins = (accumulated_minutes % 60) as u32;
-                    let start_time = NaiveTime::from_hms_opt(start_hours.min(23), start_mins.min(59), 0)
-                        .unwrap_or_else(|| NaiveTime::from_hms_opt(23, 59, 0).unwrap());
-
-                    accumulated_minutes += remaining_minutes;
-
-                    // Cap at midnight
-                    if accumulated_minutes >= 24 * 60 {
-                        accumulated_minutes = 24 * 60 - 1;
-                    }
-
-                    let end_hours = (accumulated_minutes / 60) as u32;
-                    let end_mins = (accumulated_minutes % 60) as u32;
-                    let end_time = NaiveTime::from_hms_opt(end_hours.min(23), end_mins.min(59), 0)
-                        .unwrap_or_else(|| NaiveTime::from_hms_opt(23, 59, 0).unwrap());
-
-                    blocks.push(TaskBlock {
-                        task_id: task.id,
-                        title: task.title.trim().to_string(),
-                        start_time,
-                        end_time,
-                        status: task.status,
-                        is_selected: is_task_selected,
-                        color: task_color_from_id(&task.id),
-                    });
-                }
-            }
-        } else {
-            // Schedule all subtasks with parent task prefix
-            let parent_title = task.title.trim();
-            for subtask in &task.subtasks {
-                // Only schedule subtasks that have remaining time
-                let remaining = subtask.track.estimate - subtask.track.elapsed;
-                if remaining > Duration::zero() {
-                    let remaining_minutes = remaining.num_minutes();
-                    if remaining_minutes > 0 {
-                        // Calculate start and end times
-                        let start_hours = (accumulated_minutes / 60) as u32;
-                        let start_mins = (accumulated_minutes % 60) as u32;
-                        let start_time = NaiveTime::from_hms_opt(start_hours.min(23), start_mins.min(59), 0)
-                            .unwrap_or_else(|| NaiveTime::from_hms_opt(23, 59, 0).unwrap());
-
-                        accumulated_minutes += remaining_minutes;
-
-                        // Cap at midnight
-                        if accumulated_minutes >= 24 * 60 {
-                            accumulated_minutes = 24 * 60 - 1;
-                        }
-
-                        let end_hours = (accumulated_minutes / 60) as u32;
-                        let end_mins = (accumulated_minutes % 60) as u32;
-                        let end_time = NaiveTime::from_hms_opt(end_hours.min(23), end_mins.min(59), 0)
-                            .unwrap_or_else(|| NaiveTime::from_hms_opt(23, 59, 0).unwrap());
-
-                        // Prefix subtask title with parent task name
-                        let subtask_title = format!("{} > {}", parent_title, subtask.title.trim());
-
-                        blocks.push(TaskBlock {
-                            task_id: subtask.id,
-                            title: subtask_title,
-                            start_time,
-                            end_time,
-                            status: subtask.status,
-                            is_selected: is_task_selected,
-                            color: task_color_from_id(&subtask.id),
-                        });
-                    }
-                }
-            }
-        }
+    // Process all tasks and subtasks (blocked tasks after their blockers)
+    for task_idx in schedule_order(&app.tasks) {
+        let is_task_selected = task_idx == app.selected_index;
+        schedule_item(&app.tasks[task_idx], "", is_task_selected, &mut accumulated_minutes, day_end, &mut blocks);
     }
 
     blocks
 }
 
 
+/// Schedule an item with no subtasks, or else the subtasks below it (at any
+/// depth), with their titles prefixed by their parents' ("Epic > Task > Step")
+fn schedule_item(
+    item: &Item,
+    prefix: &str,
+    is_task_selected: bool,
+    accumulated_minutes: &mut i64,
+    day_end: i64,
+    blocks: &mut Vec<TaskBlock>,
+) {
+    // Waiting items are on hold, so they (and everything below them) aren't scheduled
+    if item.status == RunStatus::Waiting {
+        return;
+    }
+
+    let title = format!("{}{}", prefix, item.title.trim());
+
+    if !item.subtasks.is_empty() {
+        let prefix = format!("{} > ", title);
+        for idx in schedule_order(&item.subtasks) {
+            schedule_item(&item.subtasks[idx], &prefix, is_task_selected, accumulated_minutes, day_end, blocks);
+        }
+        return;
+    }
+
+    // Only schedule tasks that have remaining time
+    let remaining = item.track.estimate - item.track.elapsed;
+    if remaining > Duration::zero() {
+        let remaining_minutes = remaining.num_minutes();
+        if remaining_minutes > 0 {
+            // Calculate start and end times
+            let start_time = time_of_day((*accumulated_minutes).min(day_end));
+
+            *accumulated_minutes = (*accumulated_minutes + remaining_minutes).min(day_end);
+            let end_time = time_of_day(*accumulated_minutes);
+
+            blocks.push(TaskBlock {
+                task_id: item.id,
+                title,
+                start_time,
+                end_time,
+                status: item.status,
+                is_selected: is_task_selected,
+                color: task_color_from_id(&item.id),
+            });
+        }
+    }
+}
+
 /// Check if a time slice should show the NOW line
 /// NOW line appears at the hour that contains the current time
 fn is_near_current_time(slice_time: &NaiveTime, current_time: NaiveTime) -> bool {
@@ -274,7 +248,7 @@ fn get_task_style(status: RunStatus, is_current_slot: bool) -> Style {
         RunStatus::Paused => paused_style(),
         RunStatus::Idle => idle_style(),
         RunStatus::Done => Style::default().fg(Color::Green),
-        RunStatus::Postponed => Style::default().fg(Color::DarkGray),
+        RunStatus::Postponed | RunStatus::Waiting => Style::default().fg(Color::DarkGray),
     };
 
     // If it's the current time slot, make it bold
@@ -381,8 +355,7 @@ mod tests {
 
     #[test]
     fn test_build_time_grid() {
-        let current = NaiveTime::from_hms_opt(14, 7, 0).unwrap();
-        let grid = build_time_grid(current);
+        let grid = build_time_grid(&PlannerConfig::default());
 
         // Should show 9am to 11:45pm in 15-minute intervals (60 slots)
         assert_eq!(grid.len(), 60);
@@ -394,6 +367,12 @@ mod tests {
         assert_eq!(grid[2].minute(), 30);
         assert_eq!(grid[59].hour(), 23);
         assert_eq!(grid[59].minute(), 45);
+
+        let grid = build_time_grid(&PlannerConfig { start_hour: 7, end_hour: 19 });
+        assert_eq!(grid.len(), 48);
+        assert_eq!(grid[0].hour(), 7);
+        assert_eq!(grid[47].hour(), 18);
+        assert_eq!(grid[47].minute(), 45);
     }
 
     #[test]
@@ -406,6 +385,7 @@ mod tests {
             vec![],
             vec![],
             "".to_string(),
+            crate::config::Config::default(),
         );
 
         // Add test tasks
@@ -421,7 +401,7 @@ mod tests {
         app.tasks = vec![task1, task2, task3];
 
         let current_time = NaiveTime::from_hms_opt(15, 0, 0).unwrap();
-        let blocks = schedule_tasks(&app, current_time);
+        let blocks = schedule_tasks(&app, current_time, NaiveTime::MIN);
 
         println!("\n=== Test Schedule Output ===");
         println!("Current time: {:02}:{:02}", current_time.hour(), current_time.minute());
@@ -453,6 +433,35 @@ mod tests {
         assert_eq!(blocks[2].title, "Task 3");
     }
 
+    #[test]
+    fn test_schedule_tasks_past_midnight() {
+        use crate::domain::ScheduleDay;
+
+        let mut app = crate::app::AppState::new(
+            vec![],
+            vec![],
+            vec![],
+            "".to_string(),
+            crate::config::Config::default(),
+        );
+        app.tasks = vec![
+            Item::new("Late task".to_string(), Duration::hours(2), ScheduleDay::Today),
+            Item::new("Later task".to_string(), Duration::hours(4), ScheduleDay::Today),
+        ];
+
+        // With days starting at 03:00, blocks run past midnight and stop at 02:59
+        let day_start = NaiveTime::from_hms_opt(3, 0, 0).unwrap();
+        let blocks = schedule_tasks(&app, NaiveTime::from_hms_opt(23, 30, 0).unwrap(), day_start);
+
+        assert_eq!(blocks[0].end_time, NaiveTime::from_hms_opt(1, 30, 0).unwrap());
+        assert_eq!(blocks[1].start_time, NaiveTime::from_hms_opt(1, 30, 0).unwrap());
+        assert_eq!(blocks[1].end_time, NaiveTime::from_hms_opt(2, 59, 0).unwrap());
+
+        let grid = build_time_grid(&PlannerConfig { start_hour: 22, end_hour: 27 });
+        assert_eq!(grid.len(), 20);
+        assert_eq!(grid[19], NaiveTime::from_hms_opt(2, 45, 0).unwrap());
+    }
+
     #[test]
     fn test_block_overlap_detection() {
         let current_time = NaiveTime::from_hms_opt(15, 0, 0).unwrap();
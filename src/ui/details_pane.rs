@@ -1,5 +1,5 @@
 use crate::app::AppState;
-use crate::domain::{flatten_tasks, RunStatus};
+use crate::domain::{active_blockers, flatten_tasks, item_at, RunStatus};
 use crate::ui::styles::{border_style, default_style, running_style, title_style};
 use ratatui::{
     layout::Rect,
@@ -24,10 +24,8 @@ pub fn render_details_pane(f: &mut Frame, app: &AppState, area: Rect) {
     }
 
     let row = &flat_rows[app.selected_index];
-    let item = if let Some(st_idx) = row.subtask_index {
-        &app.tasks[row.task_index].subtasks[st_idx]
-    } else {
-        &app.tasks[row.task_index]
+    let Some(item) = item_at(&app.tasks, &row.path) else {
+        return;
     };
 
     let mut lines = Vec::new();
@@ -71,6 +69,16 @@ pub fn render_details_pane(f: &mut Frame, app: &AppState, area: Rect) {
         Span::styled("Status: ", title_style()),
         Span::raw(item.status.to_tag()),
     ]));
+
+    // What it waits on (can't start until these are done)
+    let blockers = active_blockers(&app.tasks, &row.path);
+    if !blockers.is_empty() {
+        let titles: Vec<&str> = blockers.iter().map(|blocker| blocker.title.trim()).collect();
+        lines.push(Line::from(vec![
+            Span::styled("Blocked by: ", title_style()),
+            Span::raw(titles.join(", ")),
+        ]));
+    }
     lines.push(Line::raw(""));
 
     // Notes
@@ -1,5 +1,5 @@
 use crate::app::AppState;
-use crate::domain::{tree_connector, Item};
+use crate::domain::{flatten_all_tasks, item_at, tree_connector, Item};
 use crate::ui::styles::{border_style, default_style, done_style, title_style, tree_style};
 use ratatui::{
     layout::Rect,
@@ -14,7 +14,7 @@ fn create_done_line(item: &Item, depth: usize, is_last: bool) -> Line {
 
     // Indentation and tree connector for subtasks
     if depth > 0 {
-        spans.push(Span::styled("     ".to_string(), tree_style()));
+        spans.push(Span::styled(format!("     {}", "   ".repeat(depth - 1)), tree_style()));
         spans.push(Span::styled(
             tree_connector(is_last).to_string(),
             tree_style(),
@@ -40,18 +40,10 @@ fn create_done_line(item: &Item, depth: usize, is_last: bool) -> Line {
 pub fn render_done_pane(f: &mut Frame, app: &AppState, area: Rect) {
     let mut all_items: Vec<ListItem> = Vec::new();
 
-    // Iterate through done tasks and their subtasks
-    for task in &app.done_today {
-        // Add the parent task
-        let line = create_done_line(task, 0, false);
-        all_items.push(ListItem::new(line));
-
-        // Add subtasks if any
-        let subtask_count = task.subtasks.len();
-        for (idx, subtask) in task.subtasks.iter().enumerate() {
-            let is_last = idx == subtask_count - 1;
-            let line = create_done_line(subtask, 1, is_last);
-            all_items.push(ListItem::new(line));
+    // Iterate through done tasks and their subtasks (at every depth)
+    for row in flatten_all_tasks(&app.done_today) {
+        if let Some(item) = item_at(&app.done_today, &row.path) {
+            all_items.push(ListItem::new(create_done_line(item, row.depth, row.is_last)));
         }
     }
 
@@ -180,9 +180,8 @@ pub fn render_garden_pane(f: &mut Frame, app: &mut AppState, area: Rect) {
                 first = false;
 
                 mode_spans.push(Span::raw(format!(
-                    "{} {} {}",
-                    mode.symbol(),
-                    mode.name(),
+                    "{} {}",
+                    app.config.modes.label(mode),
                     format_duration(*time)
                 )));
             }
@@ -218,8 +217,8 @@ pub fn render_garden_pane(f: &mut Frame, app: &mut AppState, area: Rect) {
     f.render_widget(Paragraph::new(lines), chunks[2]);
 
     // Render centered phrase at bottom - use contextual phrase for non-working modes
-    let display_phrase = if app.global_mode.should_pause_timers() {
-        app.global_mode.contextual_phrase()
+    let display_phrase = if app.config.modes.should_pause_timers(&app.global_mode) {
+        app.config.modes.contextual_phrase(&app.global_mode)
     } else {
         phrase
     };
@@ -1,4 +1,5 @@
 use crate::app::AppState;
+use crate::domain::TimeTracking;
 use crate::ui::{
     layout::create_modal_area,
     styles::{modal_bg_style, modal_title_style},
@@ -102,9 +103,11 @@ pub fn render_input_form(f: &mut Frame, app: &AppState, area: Rect) {
 
         // Only show default estimate when adding new items
         if form.editing_item_id.is_none() {
+            let default_estimate =
+                TimeTracking::from_hours(app.config.estimates.default_hours, 0.0).estimate_formatted();
             lines.push(Line::from(vec![
                 Span::raw("(Default estimate: "),
-                Span::styled("1h", modal_title_style()),
+                Span::styled(default_estimate, modal_title_style()),
                 Span::raw(")"),
             ]));
         }
@@ -1,15 +1,24 @@
-use crate::ui::styles::hint_style;
+use crate::ui::styles::{error_style, hint_style};
 use ratatui::{layout::Rect, text::{Line, Span}, widgets::Paragraph, Frame};
 
-/// Render the keybindings hint bar
-pub fn render_keybindings(f: &mut Frame, area: Rect) {
+/// Render the keybindings hint bar (or a status message in its place)
+pub fn render_keybindings(f: &mut Frame, area: Rect, message: Option<&str>) {
+    if let Some(message) = message {
+        f.render_widget(Paragraph::new(format!(" {}", message)).style(error_style()), area);
+        return;
+    }
+
     let hints = Line::from(vec![
         Span::raw(" ↑/↓ select   "),
         Span::raw("Shift+↑/↓ reorder   "),
+        Span::raw("Shift+←/→ promote/demote   "),
         Span::raw("Enter start/stop   "),
         Span::raw("+ / - est   "),
         Span::raw("d done   "),
-        Span::raw("u undo   "),
+        Span::raw("u/^r undo/redo   "),
+        Span::raw("h history   "),
+        Span::raw("b blocked-by   "),
+        Span::raw("w waiting   "),
         Span::raw("p tomorrow   "),
         Span::raw("x/r archive   "),
         Span::raw("a add   "),
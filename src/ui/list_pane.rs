@@ -1,7 +1,7 @@
 use crate::app::AppState;
-use crate::domain::{flatten_tasks, plant_glyph, status_badge, tree_connector, Item, RunStatus, TimeTracking};
+use crate::domain::{active_blockers, flatten_tasks, item_at, plant_glyph, schedule_order, status_badge, tree_connector, Item, RunStatus, TimeTracking};
 use crate::ui::styles::{
-    border_style, default_style, idle_style, over_estimate_style, paused_style, running_style,
+    border_style, default_style, error_style, idle_style, over_estimate_style, paused_style, running_style,
     running_style_selected, selected_style, tag_style, tag_style_selected, title_style, tree_style,
 };
 use chrono::{DateTime, Local, Timelike};
@@ -29,46 +29,42 @@ fn format_duration(duration: chrono::Duration) -> String {
     }
 }
 
-/// Calculate ETAs for all tasks and subtasks sequentially
+/// Calculate ETAs for all tasks and subtasks sequentially (blocked tasks
+/// after their blockers, as in the daily planner)
 fn calculate_etas(tasks: &[Item]) -> HashMap<Uuid, DateTime<Local>> {
     let mut etas = HashMap::new();
     let mut accumulated_time = chrono::Duration::zero();
     let now = Local::now();
 
-    for task in tasks {
-        // Calculate remaining time for task
-        let remaining = task.track.estimate - task.track.elapsed;
-
-        // Process subtasks if they exist
-        if !task.subtasks.is_empty() {
-            // For tasks with subtasks, first subtask starts at accumulated_time
-            // and each subsequent subtask starts after the previous one
-            for subtask in &task.subtasks {
-                let subtask_remaining = subtask.track.estimate - subtask.track.elapsed;
-
-                // Each subtask ETA is based on accumulated time so far
-                let eta = snap_to_5min(now + accumulated_time + subtask_remaining);
-                etas.insert(subtask.id, eta);
-
-                // Add this subtask's remaining time to accumulated time
-                accumulated_time = accumulated_time + subtask_remaining;
-            }
+    for idx in schedule_order(tasks) {
+        add_etas(&tasks[idx], now, &mut accumulated_time, &mut etas);
+    }
 
-            // Parent task ETA is when the last subtask finishes
-            // (accumulated_time already includes all subtasks)
-            let parent_eta = snap_to_5min(now + accumulated_time);
-            etas.insert(task.id, parent_eta);
-        } else {
-            // No subtasks: task ETA is accumulated time + remaining
-            let eta = snap_to_5min(now + accumulated_time + remaining);
-            etas.insert(task.id, eta);
+    etas
+}
 
-            // Add this task's remaining time to accumulated time
-            accumulated_time = accumulated_time + remaining;
+/// ETAs for an item and everything below it: leaves are worked through in
+/// order, and a parent's ETA is when its last subtask finishes. Waiting items
+/// (and everything below them) get none.
+fn add_etas(
+    item: &Item,
+    now: DateTime<Local>,
+    accumulated_time: &mut chrono::Duration,
+    etas: &mut HashMap<Uuid, DateTime<Local>>,
+) {
+    if item.status == RunStatus::Waiting {
+        return;
+    }
+    if item.subtasks.is_empty() {
+        // Leaf: ETA is accumulated time + remaining
+        let remaining = item.track.estimate - item.track.elapsed;
+        *accumulated_time = *accumulated_time + remaining;
+    } else {
+        for idx in schedule_order(&item.subtasks) {
+            add_etas(&item.subtasks[idx], now, accumulated_time, etas);
         }
     }
-
-    etas
+    etas.insert(item.id, snap_to_5min(now + *accumulated_time));
 }
 
 /// Snap time to nearest 5-minute increment
@@ -111,31 +107,34 @@ pub fn render_list_pane(f: &mut Frame, app: &AppState, area: Rect) {
     // Calculate ETAs for all tasks/subtasks
     let etas = calculate_etas(&app.tasks);
 
-    let items: Vec<ListItem> = flat_rows
-        .iter()
-        .enumerate()
-        .map(|(idx, row)| {
-            let item = if let Some(st_idx) = row.subtask_index {
-                &app.tasks[row.task_index].subtasks[st_idx]
-            } else {
-                &app.tasks[row.task_index]
-            };
-
-            let eta = etas.get(&item.id).copied();
-            let is_selected = idx == app.selected_index;
-            let line = create_task_line(item, row.depth, row.is_last, app.use_emoji, eta, is_selected);
-            let style = if is_selected {
-                selected_style()
-            } else {
-                default_style()
-            };
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut in_waiting_group = false;
+    for (idx, row) in flat_rows.iter().enumerate() {
+        let Some(item) = item_at(&app.tasks, &row.path) else {
+            continue;
+        };
 
-            ListItem::new(line).style(style)
-        })
-        .collect();
+        // Waiting tasks come last, under their own header
+        if row.depth == 0 && item.status == RunStatus::Waiting && !in_waiting_group {
+            in_waiting_group = true;
+            items.push(ListItem::new(Line::from(Span::styled(" ── Waiting ──", paused_style()))));
+        }
+
+        let eta = etas.get(&item.id).copied();
+        let is_selected = idx == app.selected_index;
+        let blocked = !active_blockers(&app.tasks, &row.path).is_empty();
+        let line = create_task_line(item, row.depth, row.is_last, app.use_emoji, eta, is_selected, blocked);
+        let style = if is_selected {
+            selected_style()
+        } else {
+            default_style()
+        };
+
+        items.push(ListItem::new(line).style(style));
+    }
 
     let date = Local::now().format("%a %b %d");
-    let title = format!(" Today's Centre 🌱 ({}) — {} {} ", date, app.global_mode.symbol(), app.global_mode.name());
+    let title = format!(" Today's Centre 🌱 ({}) — {} ", date, app.config.modes.label(&app.global_mode));
 
     let list = List::new(items).block(
         Block::default()
@@ -149,12 +148,12 @@ pub fn render_list_pane(f: &mut Frame, app: &AppState, area: Rect) {
 
 /// Create a single line for a task/subtask
 /// Format: [🌿] Write proposal  ⏱ 1.3h / 2.0h (RUNNING) [TAGS]   ⇢ 🕒 12:45 🌞
-fn create_task_line(item: &Item, depth: usize, is_last: bool, use_emoji: bool, eta: Option<DateTime<Local>>, is_selected: bool) -> Line<'static> {
+fn create_task_line(item: &Item, depth: usize, is_last: bool, use_emoji: bool, eta: Option<DateTime<Local>>, is_selected: bool, blocked: bool) -> Line<'static> {
     let mut spans = Vec::new();
 
-    // Indentation and tree connector for subtasks
+    // Indentation (deeper for each level) and tree connector for subtasks
     if depth > 0 {
-        spans.push(Span::styled("     ".to_string(), tree_style()));
+        spans.push(Span::styled(format!("     {}", "   ".repeat(depth - 1)), tree_style()));
         spans.push(Span::styled(
             tree_connector(is_last).to_string(),
             tree_style(),
@@ -205,6 +204,22 @@ fn create_task_line(item: &Item, depth: usize, is_last: bool, use_emoji: bool, e
     };
     spans.push(Span::styled(badge.to_string(), badge_style));
 
+    // What a waiting item is waiting on, and when to follow up
+    if item.status == RunStatus::Waiting {
+        if !item.waiting_reason.is_empty() {
+            spans.push(Span::styled(format!(" — {}", item.waiting_reason), paused_style()));
+        }
+        if let Some(follow_up) = item.follow_up {
+            spans.push(Span::styled(format!(" · follow up {}", follow_up.format("%a %b %d")), paused_style()));
+        }
+    }
+
+    // Blocked marker (waiting on a task that isn't done yet)
+    if blocked {
+        let marker = if use_emoji { " ⛔ blocked" } else { " [BLOCKED]" };
+        spans.push(Span::styled(marker.to_string(), error_style()));
+    }
+
     // ETA with phase emoji if available
     if let Some(eta_time) = eta {
         let phase = phase_emoji(eta_time);
@@ -243,7 +258,7 @@ mod tests {
             Duration::hours(2),
             ScheduleDay::Today,
         );
-        let line = create_task_line(&item, 0, false, true, None, false);
+        let line = create_task_line(&item, 0, false, true, None, false, false);
 
         // Check that line contains expected components
         let line_str = format!("{:?}", line);
@@ -257,10 +272,19 @@ mod tests {
             Duration::hours(1),
             ScheduleDay::Today,
         );
-        let line = create_task_line(&item, 1, true, true, None, false);
+        let line = create_task_line(&item, 1, true, true, None, false, false);
 
         // Subtask should have indentation
         let line_str = format!("{:?}", line);
         assert!(line_str.contains("Subtask"));
     }
+
+    #[test]
+    fn test_blocked_task_line() {
+        let item = Item::new("Deploy".to_string(), Duration::hours(1), ScheduleDay::Today);
+        let line = create_task_line(&item, 0, false, false, None, false, true);
+
+        let line_str = format!("{:?}", line);
+        assert!(line_str.contains("[BLOCKED]"));
+    }
 }
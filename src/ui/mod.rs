@@ -23,7 +23,10 @@ use journal_pane::render_journal_pane;
 use keybindings::render_keybindings;
 use layout::create_layout;
 use list_pane::render_list_pane;
-use modal::{render_day_changed_modal, render_idle_check_modal, render_modal, render_mode_selector};
+use modal::{
+    render_away_check_modal, render_blocker_picker, render_crash_recovery_modal, render_external_change_modal, render_history_editor, render_idle_check_modal,
+    render_idle_trimmed_modal, render_modal, render_mode_selector, render_waiting_form,
+};
 use ratatui::Frame;
 
 /// Main render function - draws the entire UI
@@ -32,7 +35,7 @@ pub fn render(f: &mut Frame, app: &mut AppState) {
     let layout = create_layout(size, app.show_done, app.show_planner);
 
     // Render keybindings bar
-    render_keybindings(f, layout.keybindings_area);
+    render_keybindings(f, layout.keybindings_area, app.status_message.as_deref());
 
     // Render panes
     render_list_pane(f, app, layout.list_area);
@@ -53,12 +56,6 @@ pub fn render(f: &mut Frame, app: &mut AppState) {
         render_animation_pane(f, app, animation_area);
     }
 
-    // Render day changed modal (takes precedence)
-    if app.ui_mode == UiMode::DayChanged {
-        render_day_changed_modal(f, app, size);
-        return; // Don't render other modals
-    }
-
     // Render modal if active
     if app.modal.is_some() {
         render_modal(f, app, size);
@@ -69,6 +66,36 @@ pub fn render(f: &mut Frame, app: &mut AppState) {
         render_idle_check_modal(f, app, size);
     }
 
+    // Render idle trim prompt if active
+    if app.ui_mode == UiMode::IdleTrimmed {
+        render_idle_trimmed_modal(f, app, size);
+    }
+
+    // Render crash recovery prompt if active
+    if app.ui_mode == UiMode::CrashRecovery {
+        render_crash_recovery_modal(f, app, size);
+    }
+
+    // Render history editor if active
+    if app.ui_mode == UiMode::EditingHistory {
+        render_history_editor(f, app, size);
+    }
+
+    // Render blocker picker if active
+    if app.ui_mode == UiMode::PickingBlockers {
+        render_blocker_picker(f, app, size);
+    }
+
+    // Render waiting form if active
+    if app.ui_mode == UiMode::SettingWaiting {
+        render_waiting_form(f, app, size);
+    }
+
+    // Render away prompt if active
+    if app.ui_mode == UiMode::AwayCheck {
+        render_away_check_modal(f, app, size);
+    }
+
     // Render input form if active
     if app.input_form.is_some() {
         render_input_form(f, app, size);
@@ -78,4 +105,9 @@ pub fn render(f: &mut Frame, app: &mut AppState) {
     if app.ui_mode == UiMode::ModeSelector {
         render_mode_selector(f, app, size);
     }
+
+    // Render external change prompt if active
+    if app.ui_mode == UiMode::ExternalChange {
+        render_external_change_modal(f, app, size);
+    }
 }
@@ -1,8 +1,8 @@
-use crate::app::AppState;
-use crate::domain::{GlobalMode, UiMode};
+use crate::app::{AppState, ExternalChange, HistoryInput};
+use crate::domain::{walk_items, UiMode};
 use crate::ui::{
     layout::create_modal_area,
-    styles::{modal_bg_style, modal_title_style},
+    styles::{error_style, modal_bg_style, modal_title_style, selected_style},
 };
 use ratatui::{
     layout::Rect,
@@ -11,48 +11,6 @@ use ratatui::{
     Frame,
 };
 
-/// Render the day changed modal (forces restart)
-pub fn render_day_changed_modal(f: &mut Frame, app: &AppState, area: Rect) {
-    if app.ui_mode == UiMode::DayChanged {
-        let modal_area = create_modal_area(area);
-
-        // Clear the area behind the modal
-        f.render_widget(Clear, modal_area);
-
-        let mut lines = Vec::new();
-
-        // Message
-        lines.push(Line::raw(""));
-        lines.push(Line::raw("  A new day has begun!"));
-        lines.push(Line::raw(""));
-        lines.push(Line::raw("  The date has changed since you started the app."));
-        lines.push(Line::raw("  Please close and restart Centre to continue."));
-        lines.push(Line::raw(""));
-        lines.push(Line::raw("  Your work has been saved."));
-        lines.push(Line::raw(""));
-
-        // Options
-        lines.push(Line::from(vec![
-            Span::styled("  [q]", modal_title_style()),
-            Span::raw(" Close Centre  "),
-        ]));
-
-        let paragraph = Paragraph::new(lines)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(Span::styled(
-                        " \u{1F305} Day Changed ",
-                        modal_title_style(),
-                    ))
-                    .style(modal_bg_style()),
-            )
-            .wrap(Wrap { trim: false });
-
-        f.render_widget(paragraph, modal_area);
-    }
-}
-
 /// Render the estimate-hit modal
 pub fn render_modal(f: &mut Frame, app: &AppState, area: Rect) {
     if let Some(modal) = &app.modal {
@@ -120,7 +78,7 @@ pub fn render_idle_check_modal(f: &mut Frame, app: &AppState, area: Rect) {
                 String::from("0 seconds")
             }
         } else {
-            String::from("30 minutes")
+            format!("{} minutes", app.config.idle.auto_pause_minutes)
         };
 
         // Message
@@ -158,6 +116,416 @@ pub fn render_idle_check_modal(f: &mut Frame, app: &AppState, area: Rect) {
     }
 }
 
+/// Render the prompt shown after the idle check timed out
+pub fn render_idle_trimmed_modal(f: &mut Frame, app: &AppState, area: Rect) {
+    if let (UiMode::IdleTrimmed, Some(trim)) = (app.ui_mode, &app.idle_trim) {
+        let modal_area = create_modal_area(area);
+
+        // Clear the area behind the modal
+        f.render_widget(Clear, modal_area);
+
+        let mut lines = Vec::new();
+
+        // Message
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(format!(
+            "  Nobody answered the idle check, so these tasks were paused as of your last keypress at {} (or when they started, if later):",
+            trim.last_input.format("%H:%M")
+        )));
+        lines.push(Line::raw(""));
+        for item in walk_items(&app.tasks) {
+            if let Some((_, from)) = trim.items.iter().find(|(id, _)| *id == item.id) {
+                lines.push(Line::raw(format!(
+                    "    • {} ({} taken off)",
+                    item.title.trim(),
+                    format_duration(trim.trimmed_since(*from))
+                )));
+            }
+        }
+        lines.push(Line::raw(""));
+
+        // Options
+        lines.push(Line::from(vec![
+            Span::styled("  [k]", modal_title_style()),
+            Span::raw(" Keep it (I was working)  "),
+            Span::styled("[Enter]", modal_title_style()),
+            Span::raw(" OK"),
+        ]));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        " ⏰ Idle Time Trimmed ",
+                        modal_title_style(),
+                    ))
+                    .style(modal_bg_style()),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, modal_area);
+    }
+}
+
+/// Render the prompt shown after the computer was suspended (or the clock jumped)
+pub fn render_away_check_modal(f: &mut Frame, app: &AppState, area: Rect) {
+    if let (UiMode::AwayCheck, Some(gap)) = (app.ui_mode, app.away_gap) {
+        let modal_area = create_modal_area(area);
+
+        // Clear the area behind the modal
+        f.render_widget(Clear, modal_area);
+
+        let away = gap.duration();
+        let mut lines = Vec::new();
+
+        // Message
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(format!(
+            "  You were away for {}h {:02}m ({} - {})",
+            away.num_hours(),
+            away.num_minutes() % 60,
+            gap.from.format("%H:%M"),
+            gap.to.format("%H:%M")
+        )));
+        lines.push(Line::raw("  (the computer was asleep or the clock changed)."));
+        lines.push(Line::raw(""));
+
+        // Options
+        lines.push(Line::from(vec![
+            Span::styled("  [Enter]", modal_title_style()),
+            Span::raw(" Count it for running tasks and the current mode"),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  [Esc]", modal_title_style()),
+            Span::raw(" Discard it"),
+        ]));
+        lines.push(Line::raw(""));
+        lines.push(Line::raw("  Or assign it to a mode:"));
+        for mode in app.config.modes.all() {
+            if let Some(key) = mode.key {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  [{}]", key), modal_title_style()),
+                    Span::raw(format!(" {} {}", mode.symbol, mode.name)),
+                ]));
+            }
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        " 💤 Welcome Back ",
+                        modal_title_style(),
+                    ))
+                    .style(modal_bg_style()),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, modal_area);
+    }
+}
+
+/// Render the startup prompt for tasks left running by a crashed session
+pub fn render_crash_recovery_modal(f: &mut Frame, app: &AppState, area: Rect) {
+    if let (UiMode::CrashRecovery, Some(gap)) = (app.ui_mode, &app.crash_gap) {
+        let modal_area = create_modal_area(area);
+
+        // Clear the area behind the modal
+        f.render_widget(Clear, modal_area);
+
+        let mut lines = Vec::new();
+
+        // Message
+        lines.push(Line::raw(""));
+        lines.push(Line::raw("  Centre didn't shut down cleanly last time. It was last seen"));
+        lines.push(Line::raw(format!(
+            "  at {} with these tasks running:",
+            gap.heartbeat.format("%Y-%m-%d %H:%M")
+        )));
+        lines.push(Line::raw(""));
+        for task in app.tasks.iter().filter(|task| task.has_open_interval(gap.heartbeat)) {
+            lines.push(Line::raw(format!("    • {}", task.title.trim())));
+        }
+        lines.push(Line::raw(""));
+
+        // Options (or the end time being typed)
+        match &gap.time_input {
+            Some(input) => {
+                lines.push(Line::from(vec![
+                    Span::raw("  Stopped at (HH:MM): "),
+                    Span::styled(format!("{}_", input), modal_title_style()),
+                ]));
+                lines.push(Line::raw(""));
+                lines.push(Line::from(vec![
+                    Span::styled("  [Enter]", modal_title_style()),
+                    Span::raw(" Confirm  "),
+                    Span::styled("[Esc]", modal_title_style()),
+                    Span::raw(" Back"),
+                ]));
+            }
+            None => {
+                lines.push(Line::from(vec![
+                    Span::styled("  [h]", modal_title_style()),
+                    Span::raw(format!(" Stop them at the last heartbeat ({})", gap.heartbeat.format("%H:%M"))),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::styled("  [t]", modal_title_style()),
+                    Span::raw(" Stop them at a time of your choice"),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::styled("  [k]", modal_title_style()),
+                    Span::raw(" Keep the time (count it all as work)"),
+                ]));
+            }
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        " ⚠ Unfinished Session ",
+                        modal_title_style(),
+                    ))
+                    .style(modal_bg_style()),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, modal_area);
+    }
+}
+
+/// Render the history editor for the selected item
+pub fn render_history_editor(f: &mut Frame, app: &AppState, area: Rect) {
+    if let (Some(editor), Some((item, sessions))) = (&app.history_editor, app.history_item()) {
+        let modal_area = create_modal_area(area);
+
+        // Clear the area behind the modal
+        f.render_widget(Clear, modal_area);
+
+        let mut lines = Vec::new();
+
+        // Sessions
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(format!("  {}", item.title.trim())));
+        lines.push(Line::raw(""));
+        if sessions.is_empty() {
+            lines.push(Line::raw("  No time recorded yet"));
+        }
+        for (idx, session) in sessions.iter().enumerate() {
+            let end = match session.end {
+                Some(end) => end.format("%H:%M").to_string(),
+                None => "now  ".to_string(),
+            };
+            let text = format!(
+                "  {} {} – {}   {}",
+                if idx == editor.selected { "▸" } else { " " },
+                session.start.format("%H:%M"),
+                end,
+                format_duration(session.duration())
+            );
+            if idx == editor.selected {
+                lines.push(Line::styled(text, selected_style()));
+            } else {
+                lines.push(Line::raw(text));
+            }
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(format!("  Total: {}", item.track.elapsed_formatted())));
+        lines.push(Line::raw(""));
+
+        if let Some(error) = &editor.error {
+            lines.push(Line::styled(format!("  {}", error), error_style()));
+            lines.push(Line::raw(""));
+        }
+
+        // Times being typed, or the available actions
+        match &editor.input {
+            Some((kind, input)) => {
+                let prompt = match kind {
+                    HistoryInput::Add => "  New session (HH:MM-HH:MM): ",
+                    HistoryInput::Edit => "  Session (HH:MM-HH:MM): ",
+                    HistoryInput::Split => "  Break (HH:MM-HH:MM, or HH:MM to split): ",
+                };
+                lines.push(Line::from(vec![
+                    Span::raw(prompt),
+                    Span::styled(format!("{}_", input), modal_title_style()),
+                ]));
+                lines.push(Line::raw(""));
+                lines.push(Line::from(vec![
+                    Span::styled("  [Enter]", modal_title_style()),
+                    Span::raw(" Confirm  "),
+                    Span::styled("[Esc]", modal_title_style()),
+                    Span::raw(" Back"),
+                ]));
+            }
+            None => {
+                lines.push(Line::from(vec![
+                    Span::styled("  [a]", modal_title_style()),
+                    Span::raw(" Add  "),
+                    Span::styled("[e]", modal_title_style()),
+                    Span::raw(" Edit  "),
+                    Span::styled("[s]", modal_title_style()),
+                    Span::raw(" Split  "),
+                    Span::styled("[d]", modal_title_style()),
+                    Span::raw(" Delete"),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::styled("  [←/→]", modal_title_style()),
+                    Span::raw(" Shift 5 min  "),
+                    Span::styled("[Esc]", modal_title_style()),
+                    Span::raw(" Close"),
+                ]));
+            }
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        " Time History ",
+                        modal_title_style(),
+                    ))
+                    .style(modal_bg_style()),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, modal_area);
+    }
+}
+
+/// Render the form for putting the selected item on hold
+pub fn render_waiting_form(f: &mut Frame, app: &AppState, area: Rect) {
+    if let Some(form) = &app.waiting_form {
+        let modal_area = create_modal_area(area);
+
+        // Clear the area behind the modal
+        f.render_widget(Clear, modal_area);
+
+        let title = walk_items(&app.tasks)
+            .find(|item| item.id == form.item_id)
+            .map(|item| item.title.trim().to_string())
+            .unwrap_or_default();
+        let field = |label: &'static str, value: &str, active: bool| {
+            let value = if active {
+                Span::styled(format!("{}_", value), modal_title_style())
+            } else {
+                Span::raw(value.to_string())
+            };
+            Line::from(vec![Span::raw(label), value])
+        };
+
+        let mut lines = vec![
+            Line::raw(""),
+            Line::raw(format!("  {}", title)),
+            Line::raw(""),
+            field("  Waiting on: ", &form.reason, form.editing_field == 0),
+            field("  Follow up:  ", &form.follow_up, form.editing_field == 1),
+            Line::raw("              (YYYY-MM-DD, +N days or tomorrow; empty for none)"),
+            Line::raw(""),
+        ];
+
+        if let Some(error) = &form.error {
+            lines.push(Line::styled(format!("  {}", error), error_style()));
+            lines.push(Line::raw(""));
+        }
+
+        lines.push(Line::from(vec![
+            Span::styled("  [Enter]", modal_title_style()),
+            Span::raw(" Confirm  "),
+            Span::styled("[Tab]", modal_title_style()),
+            Span::raw(" Next field  "),
+            Span::styled("[Esc]", modal_title_style()),
+            Span::raw(" Cancel"),
+        ]));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        " Waiting ",
+                        modal_title_style(),
+                    ))
+                    .style(modal_bg_style()),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, modal_area);
+    }
+}
+
+/// Render the picker for the tasks the selected item waits on
+pub fn render_blocker_picker(f: &mut Frame, app: &AppState, area: Rect) {
+    if let (Some(picker), Some((item, candidates))) = (&app.blocker_picker, app.blocker_candidates()) {
+        let modal_area = create_modal_area(area);
+
+        // Clear the area behind the modal
+        f.render_widget(Clear, modal_area);
+
+        let mut lines = Vec::new();
+
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(format!("  {} waits on:", item.title.trim())));
+        lines.push(Line::raw(""));
+        if candidates.is_empty() {
+            lines.push(Line::raw("  No other tasks"));
+        }
+        for (idx, candidate) in candidates.iter().enumerate() {
+            let text = format!(
+                "  {} [{}] {}",
+                if idx == picker.selected { "▸" } else { " " },
+                if item.blocked_by.contains(&candidate.id) { "x" } else { " " },
+                candidate.title.trim()
+            );
+            if idx == picker.selected {
+                lines.push(Line::styled(text, selected_style()));
+            } else {
+                lines.push(Line::raw(text));
+            }
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::from(vec![
+            Span::styled("  [Enter]", modal_title_style()),
+            Span::raw(" Toggle  "),
+            Span::styled("[Esc]", modal_title_style()),
+            Span::raw(" Close"),
+        ]));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        " Blocked By ",
+                        modal_title_style(),
+                    ))
+                    .style(modal_bg_style()),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, modal_area);
+    }
+}
+
+/// Helper to format duration for display
+fn format_duration(duration: chrono::Duration) -> String {
+    let total_minutes = duration.num_minutes();
+    let hours = total_minutes / 60;
+    let minutes = total_minutes % 60;
+
+    if hours > 0 && minutes > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if hours > 0 {
+        format!("{}h", hours)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
 /// Render the mode selector modal
 pub fn render_mode_selector(f: &mut Frame, app: &AppState, area: Rect) {
     if app.ui_mode == UiMode::ModeSelector {
@@ -174,29 +542,48 @@ pub fn render_mode_selector(f: &mut Frame, app: &AppState, area: Rect) {
         lines.push(Line::raw(""));
 
         // Mode options with keys
-        let modes = GlobalMode::all();
-        let keys = ['1', '2', '3', '4', '5', '6', '7'];
-
-        for (idx, mode) in modes.iter().enumerate() {
-            let key = keys[idx];
-            let is_current = *mode == app.global_mode;
+        for mode in app.config.modes.all() {
+            let key = match mode.key {
+                Some(key) => format!("  [{}] ", key),
+                None => "      ".to_string(), // Not selectable from the keyboard
+            };
+            let is_current = mode.name == app.global_mode.name();
 
             let line = if is_current {
                 Line::from(vec![
-                    Span::styled(format!("  [{}] ", key), modal_title_style()),
-                    Span::raw(format!("{} ", mode.symbol())),
-                    Span::styled(mode.name(), modal_title_style()),
+                    Span::styled(key, modal_title_style()),
+                    Span::raw(format!("{} ", mode.symbol)),
+                    Span::styled(mode.name.clone(), modal_title_style()),
                     Span::raw(" ← Current"),
                 ])
             } else {
                 Line::from(vec![
-                    Span::styled(format!("  [{}] ", key), modal_title_style()),
-                    Span::raw(format!("{} {}", mode.symbol(), mode.name())),
+                    Span::styled(key, modal_title_style()),
+                    Span::raw(format!("{} {}", mode.symbol, mode.name)),
                 ])
             };
             lines.push(line);
         }
 
+        // Optional time the switch actually happened
+        lines.push(Line::raw(""));
+        match (&app.mode_since, app.editing_mode_since) {
+            (since, true) => lines.push(Line::from(vec![
+                Span::raw("  Since (HH:MM): "),
+                Span::styled(format!("{}_", since.as_deref().unwrap_or("")), modal_title_style()),
+                Span::raw("  (Enter to confirm)"),
+            ])),
+            (Some(since), false) if !since.is_empty() => lines.push(Line::from(vec![
+                Span::raw("  Since "),
+                Span::styled(since.clone(), modal_title_style()),
+                Span::raw(" - pick a mode"),
+            ])),
+            _ => lines.push(Line::from(vec![
+                Span::styled("  [Tab]", modal_title_style()),
+                Span::raw(" Switched earlier? Set since when"),
+            ])),
+        }
+
         lines.push(Line::raw(""));
         lines.push(Line::from(vec![
             Span::styled("  [Esc]", modal_title_style()),
@@ -219,3 +606,55 @@ pub fn render_mode_selector(f: &mut Frame, app: &AppState, area: Rect) {
         f.render_widget(paragraph, modal_area);
     }
 }
+
+/// Render the prompt shown when a data file was changed outside this session
+pub fn render_external_change_modal(f: &mut Frame, app: &AppState, area: Rect) {
+    if app.ui_mode == UiMode::ExternalChange {
+        let modal_area = create_modal_area(area);
+
+        // Clear the area behind the modal
+        f.render_widget(Clear, modal_area);
+
+        let (file, merge_hint) = match &app.external_change {
+            Some(ExternalChange::Journal(_)) => ("today's journal", " Keep my text and add new lines from disk"),
+            _ => ("today's task file", " Combine both, matching tasks by ID"),
+        };
+
+        let mut lines = Vec::new();
+
+        // Message
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(format!("  {} was changed outside this session", file)));
+        lines.push(Line::raw("  (another terminal, the CLI, or an editor)."));
+        lines.push(Line::raw(""));
+
+        // Options
+        lines.push(Line::from(vec![
+            Span::styled("  [r]", modal_title_style()),
+            Span::raw(" Reload from disk, discarding my unsaved changes"),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  [m]", modal_title_style()),
+            Span::raw(merge_hint),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  [o]", modal_title_style()),
+            Span::raw(" Overwrite the file with my version"),
+        ]));
+        lines.push(Line::raw(""));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        " ⚠ File Changed on Disk ",
+                        modal_title_style(),
+                    ))
+                    .style(modal_bg_style()),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, modal_area);
+    }
+}